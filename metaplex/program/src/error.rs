@@ -526,6 +526,294 @@ pub enum MetaplexError {
     /// Public keys don't match in assert check.
     #[error("PublicKeyMismatch")]
     PublicKeyMismatch,
+
+    /// Redemption has been paused by the fraction manager authority
+    #[error("Redemption has been paused by the fraction manager authority")]
+    RedemptionPaused,
+
+    /// Safety deposit config is not rent exempt for its allocated size
+    #[error("Safety deposit config is not rent exempt for its allocated size")]
+    ConfigNotRentExempt,
+
+    /// This mint has been denylisted by the store
+    #[error("This mint has been denylisted by the store")]
+    MintDenylisted,
+
+    /// Cant add more than 100 mints to a denylist
+    #[error("Cant add more than 100 mints to a denylist")]
+    MaxMintDenylistSizeReached,
+
+    /// Vault pricing lookup address does not match the fraction manager's external pricing account
+    #[error("Vault pricing lookup address does not match the fraction manager's external pricing account")]
+    PricingLookupMismatch,
+
+    /// This fraction safety deposit config was created immutable and cannot be updated
+    #[error("This fraction safety deposit config was created immutable and cannot be updated")]
+    ConfigImmutable,
+
+    /// The cluster has passed the max slot this transaction was allowed to land in
+    #[error("The cluster has passed the max slot this transaction was allowed to land in")]
+    TransactionTooOld,
+
+    /// Only verified creators may redeem until the creator priority window closes
+    #[error("Only verified creators may redeem until the creator priority window closes")]
+    CreatorPriorityWindowActive,
+
+    /// Token types were added to the vault after the fraction manager was initialized, reinitialize the manager
+    #[error("Token types were added to the vault after the fraction manager was initialized, reinitialize the manager")]
+    VaultTokenTypesAdded,
+
+    /// The fraction mint account already exists
+    #[error("The fraction mint account already exists")]
+    FractionMintAlreadyExists,
+
+    /// FractionToken boxes must pass the system program as their edition account
+    #[error("FractionToken boxes must pass the system program as their edition account")]
+    UnexpectedEditionForTokenBox,
+
+    /// Validate and activate is only allowed on single box vaults unless multiple boxes are allowed
+    #[error("Validate and activate is only allowed on single box vaults unless multiple boxes are allowed")]
+    ActivationRequiresSingleBox,
+
+    /// A fixed price must be set on the config to activate redemption
+    #[error("A fixed price must be set on the config to activate redemption")]
+    ActivationPriceMissing,
+
+    /// Redemption can only be activated by validating the last box of the vault
+    #[error("Redemption can only be activated by validating the last box of the vault")]
+    ActivationBeforeAllBoxesValidated,
+
+    /// This vault has reached the store's limit on fraction managers
+    #[error("This vault has reached the store's limit on fraction managers")]
+    VaultFractionalizationLimitReached,
+
+    /// This creator was not verified on the metadata when its creators were snapshotted
+    #[error("This creator was not verified on the metadata when its creators were snapshotted")]
+    UnverifiedCreatorPayout,
+
+    /// The external price is outside the fraction manager's price bounds
+    #[error("The external price is outside the fraction manager's price bounds")]
+    PriceOutOfBounds,
+
+    /// The minimum price cannot be above the maximum price
+    #[error("The minimum price cannot be above the maximum price")]
+    InvalidPriceBounds,
+
+    /// A store can approve at most 10 authority programs
+    #[error("A store can approve at most 10 authority programs")]
+    TooManyApprovedAuthorityPrograms,
+
+    /// The metadata update authority is not a PDA of a program approved by the store
+    #[error("The metadata update authority is not a PDA of a program approved by the store")]
+    AuthorityProgramNotApproved,
+
+    /// A whitelisted creator account was provided but has never been created
+    #[error("A whitelisted creator account was provided but has never been created")]
+    WhitelistedCreatorUninitialized,
+
+    /// The whitelisted creator account is not owned by this program
+    #[error("The whitelisted creator account is not owned by this program")]
+    WhitelistedCreatorWrongOwner,
+
+    /// The safety deposit token store is not held by the vault program's authority for this vault
+    #[error("The safety deposit token store is not held by the vault program's authority for this vault")]
+    StoreNotVaultDerived,
+
+    /// The price per share is below the fraction manager's redemption floor
+    #[error("The price per share is below the fraction manager's redemption floor")]
+    PriceBelowFloor,
+
+    /// The metadata authority can only be returned to a wallet
+    #[error("The metadata authority can only be returned to a wallet")]
+    InvalidReturnToAddress,
+
+    /// Buyout fee cannot be more than 10000 basis points
+    #[error("Buyout fee cannot be more than 10000 basis points")]
+    InvalidBuyoutFeeBps,
+
+    /// Metadata update authority is not held by the fraction manager
+    #[error("Metadata update authority is not held by the fraction manager")]
+    MetadataNotHeldByFractionManager,
+
+    /// Original authority lookup still exists, return authority through it instead
+    #[error("Original authority lookup still exists, return authority through it instead")]
+    OriginalAuthorityLookupNotLost,
+
+    /// The dispute window on this metadata rescue has not passed yet
+    #[error("The dispute window on this metadata rescue has not passed yet")]
+    RescueDisputeWindowOpen,
+
+    /// This metadata rescue has already returned update authority
+    #[error("This metadata rescue has already returned update authority")]
+    RescueAlreadyExecuted,
+
+    /// Claimed original authority does not match the one recorded on the rescue
+    #[error("Claimed original authority does not match the one recorded on the rescue")]
+    RescueClaimMismatch,
+
+    /// Prepaid teardown rent is below the deposit the store requires
+    #[error("Prepaid teardown rent is below the deposit the store requires")]
+    InsufficientTeardownRentPrepay,
+
+    /// Winning config type differs from the one the config was pre-allocated with
+    #[error("Winning config type differs from the one the config was pre-allocated with")]
+    PreallocatedTypeMismatch,
+
+    /// Royalty vesting has already started on this fraction manager
+    #[error("Royalty vesting has already started on this fraction manager")]
+    RoyaltyVestingAlreadySet,
+
+    /// Creator proceeds account does not belong to this fraction manager and creator
+    #[error("Creator proceeds account does not belong to this fraction manager and creator")]
+    CreatorProceedsMismatch,
+
+    /// None of the creator's proceeds are vested and unwithdrawn
+    #[error("None of the creator's proceeds are vested and unwithdrawn")]
+    NoVestedCreatorProceeds,
+
+    /// Vault is the resource of an auction that has not ended
+    #[error("Vault is the resource of an auction that has not ended")]
+    VaultInActiveAuction,
+
+    /// This box may only be validated by the metadata authority its config expects
+    #[error("This box may only be validated by the metadata authority its config expects")]
+    UnexpectedMetadataAuthority,
+
+    /// Mint must have zero decimals and a supply of one, held entirely by the token store
+    #[error("Mint must have zero decimals and a supply of one, held entirely by the token store")]
+    NotAGenuineNft,
+
+    /// Edition is owned by a metadata program the store has not approved
+    #[error("Edition is owned by a metadata program the store has not approved")]
+    EditionProgramNotApproved,
+
+    /// A store can approve at most 5 metadata programs
+    #[error("A store can approve at most 5 metadata programs")]
+    TooManyApprovedMetadataPrograms,
+
+    /// Fraction manager metadata uri has been locked
+    #[error("Fraction manager metadata uri has been locked")]
+    ManagerUriImmutable,
+
+    /// Fraction manager metadata uri can be at most 200 bytes
+    #[error("Fraction manager metadata uri can be at most 200 bytes")]
+    ManagerUriTooLong,
+
+    /// Depositor must sign and hold the NFT in its own token account
+    #[error("Depositor must sign and hold the NFT in its own token account")]
+    DepositorDoesNotOwnNft,
+
+    /// Metadata is not the one the creator snapshot was taken of
+    #[error("Metadata is not the one the creator snapshot was taken of")]
+    CreatorSnapshotMetadataMismatch,
+
+    /// Fraction manager is Validated without every box validated
+    #[error("Fraction manager is Validated without every box validated")]
+    ManagerInvariantViolated,
+
+    /// Minting would take the fraction mint past the manager's max fraction supply
+    #[error("Minting would take the fraction mint past the manager's max fraction supply")]
+    FractionSupplyCapExceeded,
+
+    /// Minting would leave a wallet holding more fraction shares than the manager allows
+    #[error("Minting would leave a wallet holding more fraction shares than the manager allows")]
+    FractionWalletCapExceeded,
+
+    /// Recipient token account must be the recipient's associated token account of the fraction mint
+    #[error("Recipient token account must be the recipient's associated token account of the fraction mint")]
+    FractionShareRecipientMismatch,
+
+    /// Fraction treasury is not the vault's
+    #[error("Fraction treasury is not the vault's")]
+    VaultTreasuryMismatch,
+
+    /// Vault fraction treasury still holds fraction tokens
+    #[error("Vault fraction treasury still holds fraction tokens")]
+    VaultTreasuryNotEmpty,
+
+    /// Safety deposit box order is outside the order range being validated
+    #[error("Safety deposit box order is outside the order range being validated")]
+    SafetyDepositOrderOutsideRange,
+
+    /// Prepaid teardown rent can only be refunded to the payer who deposited it
+    #[error("Prepaid teardown rent can only be refunded to the payer who deposited it")]
+    TeardownRentPayerMismatch,
+
+    /// Deposit record does not match the depositor, vault and NFT
+    #[error("Deposit record does not match the depositor, vault and NFT")]
+    DepositRecordMismatch,
+
+    /// Deposits can only be recorded for a vault that is still being filled
+    #[error("Deposits can only be recorded for a vault that is still being filled")]
+    DepositRecordedTooLate,
+
+    /// Fraction mint is not the one the fraction manager created
+    #[error("Fraction mint is not the one the fraction manager created")]
+    FractionMintMismatch,
+
+    /// Vault does not match the fraction manager's vault
+    #[error("Vault does not match the fraction manager's vault")]
+    FractionManagerVaultMismatch,
+
+    /// Store fee account must hold the payment mint and be owned by the store admin
+    #[error("Store fee account must hold the payment mint and be owned by the store admin")]
+    StoreFeeAccountMismatch,
+
+    /// Royalty vesting period is longer than the maximum allowed
+    #[error("Royalty vesting period is longer than the maximum allowed")]
+    RoyaltyVestingTooLong,
+
+    /// Only a pre-allocated config can name an expected metadata authority
+    #[error("Only a pre-allocated config can name an expected metadata authority")]
+    MetadataAuthorityRequiresPreallocation,
+
+    /// Config does not match the one pre-allocated for this safety deposit box
+    #[error("Config does not match the one pre-allocated for this safety deposit box")]
+    PreallocatedConfigMismatch,
+
+    /// There are no fraction shares to buy out or redeem
+    #[error("There are no fraction shares to buy out or redeem")]
+    NoFractionShares,
+
+    /// Only shares of the fraction manager's own fraction mint can be bought out
+    #[error("Only shares of the fraction manager's own fraction mint can be bought out")]
+    BuyoutRequiresManagerFractionMint,
+
+    /// Token burn failed
+    #[error("Token burn failed")]
+    TokenBurnFailed,
+
+    /// Fraction holders have not all been paid their buyout proceeds
+    #[error("Fraction holders have not all been paid their buyout proceeds")]
+    HolderProceedsOutstanding,
+
+    /// Creator royalties have not all been withdrawn
+    #[error("Creator royalties have not all been withdrawn")]
+    RoyaltiesOutstanding,
+
+    /// Every record of the fraction manager must be closed before it is
+    #[error("Every record of the fraction manager must be closed before it is")]
+    FractionRecordsStillOpen,
+
+    /// Account is not a record of this fraction manager
+    #[error("Account is not a record of this fraction manager")]
+    NotAFractionManagerRecord,
+
+    /// Metadata rescue has not been executed
+    #[error("Metadata rescue has not been executed")]
+    RescuePending,
+
+    /// The dispute window on this metadata rescue has passed
+    #[error("The dispute window on this metadata rescue has passed")]
+    RescueDisputeWindowClosed,
+
+    /// Store admin does not match the one that requested the rescue
+    #[error("Store admin does not match the one that requested the rescue")]
+    RescueAdminMismatch,
+
+    /// Fraction manager is not in the original layout and has nothing to migrate
+    #[error("Fraction manager is not in the original layout and has nothing to migrate")]
+    FractionManagerNotLegacy,
 }
 
 impl PrintProgramError for MetaplexError {
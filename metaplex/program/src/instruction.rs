@@ -1,6 +1,10 @@
 use crate::{
     deprecated_state::AuctionManagerSettingsV1,
-    state::{FractionSafetyDepositConfig, SafetyDepositConfig, TupleNumericType, PREFIX},
+    state::{
+        FractionSafetyDepositConfig, FractionWinningConfigType, SafetyDepositConfig,
+        TupleNumericType, CLAIM, CREATORS, DENYLIST, DEPOSIT, FRACTION_CONFIG, FRACTION_MINT,
+        PREFIX, PROCEEDS, RESCUE,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE;
@@ -9,6 +13,7 @@ use solana_program::{
     pubkey::Pubkey,
     sysvar,
 };
+use spl_associated_token_account::get_associated_token_address;
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct SetStoreArgs {
     pub public: bool,
@@ -79,6 +84,163 @@ pub struct InitFractionManagerArgs {
     // Number of fraction tokens to be used when setting up a Serum exchange.
     // If this is 0, no market is created. A Serum market can optionally be made later on.
     pub orderbook_market_pool_size: u64,
+    /// Until this unix timestamp only verified creators may redeem
+    pub creator_priority_until: Option<i64>,
+    /// Redemption and buyout never go below this price per share, fixed price or not
+    pub min_redemption_price_per_share: u64,
+    /// Lamports moved from the payer to the manager for its teardown, refunded once Closed
+    pub prepaid_teardown_rent: u64,
+    /// Also require the vault's auction account and reject if that auction has not ended
+    pub check_vault_auction: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct InitFractionManagerWithMintArgs {
+    pub orderbook_market_pool_size: u64,
+    /// Until this unix timestamp only verified creators may redeem
+    pub creator_priority_until: Option<i64>,
+    /// Redemption and buyout never go below this price per share, fixed price or not
+    pub min_redemption_price_per_share: u64,
+    /// Lamports moved from the payer to the manager for its teardown, refunded once Closed
+    pub prepaid_teardown_rent: u64,
+    /// Decimals of the fraction mint created for the manager
+    pub decimals: u8,
+    /// Also require the vault's auction account and reject if that auction has not ended
+    pub check_vault_auction: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetRedemptionPausedArgs {
+    pub paused: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetPriceBoundsArgs {
+    pub min_price: Option<u64>,
+    pub max_price: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetMintDenylistedArgs {
+    pub denylisted: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ValidateFractionSafetyDepositBoxArgs {
+    pub safety_deposit_config: FractionSafetyDepositConfig,
+    /// If set, validation fails once the cluster is past this slot
+    pub max_slot: Option<u64>,
+    /// Set when the metadata update authority is a PDA of a store approved program.
+    /// The authority is then recorded instead of transferred.
+    pub pda_authority: Option<PdaAuthority>,
+    /// Sets a ComputeUnitReport as return data, for planning batch sizes
+    pub report_cu: bool,
+    /// Wallet teardown hands the metadata authority to instead of the original authority
+    pub return_to: Option<Pubkey>,
+    /// Also require the depositor to sign and to have recorded the deposit with RecordFractionDeposit
+    pub require_depositor_custody: bool,
+    /// Only validate a box whose order falls in this range, for transactions validating
+    /// disjoint chunks of a large vault concurrently
+    pub order_range: Option<FractionOrderRange>,
+}
+
+/// Box orders from start up to, but excluding, end
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FractionOrderRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl FractionOrderRange {
+    pub fn contains(&self, order: u64) -> bool {
+        self.start <= order && order < self.end
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ValidateAndActivateArgs {
+    /// Must carry the fixed price redemption opens at
+    pub safety_deposit_config: FractionSafetyDepositConfig,
+    /// If set, validation fails once the cluster is past this slot
+    pub max_slot: Option<u64>,
+    /// Allows activating a vault with more than one box, all others already validated
+    pub allow_multiple_boxes: bool,
+    /// Set when the metadata update authority is a PDA of a store approved program
+    pub pda_authority: Option<PdaAuthority>,
+    /// Sets a ComputeUnitReport as return data, for planning batch sizes
+    pub report_cu: bool,
+    /// Wallet teardown hands the metadata authority to instead of the original authority
+    pub return_to: Option<Pubkey>,
+    /// Also require the depositor to sign and to have recorded the deposit with RecordFractionDeposit
+    pub require_depositor_custody: bool,
+    /// Only validate a box whose order falls in this range
+    pub order_range: Option<FractionOrderRange>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct UpdateFractionSafetyDepositConfigArgs {
+    pub fixed_price: Option<u64>,
+    /// Locks the config against any further update
+    pub immutable: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetFractionStoreConfigArgs {
+    /// How many fraction managers a single vault may ever have, None for no cap
+    pub max_manager_generations: Option<u64>,
+    /// Replaces the store's approved authority programs
+    pub approved_authority_programs: Vec<Pubkey>,
+    /// Basis points of every buyout taken by the store, at most 10000
+    pub buyout_fee_bps: u16,
+    /// Lamports each new manager must prepay towards its teardown, None for no deposit
+    pub teardown_rent_deposit: Option<u64>,
+    /// Replaces the metadata programs, besides the store's own, that editions may live on
+    pub approved_metadata_programs: Vec<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct PreallocateFractionSafetyDepositConfigArgs {
+    /// Type the box must be validated with
+    pub fraction_winning_config_type: FractionWinningConfigType,
+    /// Only this metadata update authority may validate the box, if set
+    pub metadata_authority: Option<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetRoyaltyVestingArgs {
+    /// Seconds from now over which creator royalties vest linearly
+    pub vesting_seconds: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetFractionManagerMetadataUriArgs {
+    pub uri: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetFractionSupplyCapsArgs {
+    pub max_fraction_supply: Option<u64>,
+    pub max_fraction_shares_per_wallet: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct FractionShareAllocation {
+    /// Wallet owning the token account the shares are minted to
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct BatchMintFractionSharesArgs {
+    pub allocations: Vec<FractionShareAllocation>,
+}
+
+/// Proves a metadata update authority is a PDA of the given program
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct PdaAuthority {
+    pub program: Pubkey,
+    /// Seeds of the PDA, bump included
+    pub seeds: Vec<Vec<u8>>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
@@ -571,7 +733,7 @@ pub enum MetaplexInstruction {
     ///
     /// Validates that a given safety deposit box has in it contents that match the given SafetyDepositConfig, and creates said config.
     /// A stateful call, this will error out if you call it a second time after validation has occurred.
-    ///   0. `[writable]` Uninitialized Safety deposit config, pda of seed ['metaplex', program id, auction manager key, safety deposit key]
+    ///   0. `[writable]` Uninitialized or pre-allocated Safety deposit config, pda of seed ['metaplex', program id, auction manager key, safety deposit key]
     ///   1. `[writable]` AuctionWinnerTokenTypeTracker, pda of seed ['metaplex', program id, auction manager key, 'totals']
     ///   2. `[writable]` Auction manager
     ///   3. `[writable]` Metadata account
@@ -586,6 +748,7 @@ pub enum MetaplexInstruction {
     ///   10. `[]` Edition OR MasterEdition record key
     ///           Remember this does not need to be an existing account (may not be depending on token), just is a pda with seed
     ///            of ['metadata', program id, Printing mint id, 'edition']. - remember PDA is relative to token metadata program.
+    ///           FractionToken boxes have no edition and must pass the system program here.
     ///   11. `[]` Vault account
     ///   12. `[signer]` Authority
     ///   13. `[signer optional]` Metadata Authority - Signer only required if doing a full ownership txfer
@@ -702,45 +865,403 @@ pub enum MetaplexInstruction {
     ///
     /// NOTE: It is not possible to use MasterEditionV1s for fractionalisation.
     ///
-    ///   0. `[writable]` Uninitialized, unallocated fraction manager account with pda of ['metaplex', vault, fraction mint]
-    ///   1. `[]` Combined vault account with authority set to auction manager account (this will be checked)
-    ///           Note in addition that this vault account should have authority set to this program's pda of ['metaplex', auction_key]
-    ///   2. `[]` Auction with auctioned item being set to the vault given and authority set to this program's pda of ['metaplex', auction_key]
-    ///   3. `[]` Authority for the Auction Manager
-    ///   4. `[signer]` Payer
-    ///   5. `[]` Accept payment account of same token mint as the auction for taking payment for open editions, owner should be auction manager key
-    ///   6. `[]` Store that this auction manager will belong to
-    ///   7. `[]` System sysvar    
-    ///   8. `[]` Rent sysvar
+    /// InitFractionManagerArgs carries creator_priority_until, min_redemption_price_per_share,
+    /// prepaid_teardown_rent and check_vault_auction after orderbook_market_pool_size, so data
+    /// holding only the pool size no longer deserializes. The accept payment mint and external
+    /// price account follow the vault, and the vault claim and fraction store config follow the
+    /// rent sysvar.
+    ///
+    ///   0. `[writable]` Uninitialized, unallocated fraction manager account with pda of ['metaplex', vault]
+    ///   1. `[]` Active vault account with authority set to the fraction manager or the authority
+    ///   2. `[]` Mint of the accept payment account and the external price account
+    ///   3. `[]` External price account, must be the vault's pricing lookup address
+    ///   4. `[]` Authority for the Fraction Manager
+    ///   5. `[signer]` Payer
+    ///   6. `[]` Accept payment account, owner should be the fraction manager
+    ///   7. `[]` Store that this fraction manager will belong to
+    ///   8. `[]` System
+    ///   9. `[]` Rent sysvar
+    ///   10. `[writable]` Vault claim, pda of ['metaplex', program id, vault key, 'claim']
+    ///   11. `[]` Fraction store config, pda of ['metaplex', program id, store key, 'fraction_config']
+    ///            (may be an empty account if the store has never set one)
+    ///   12. `[]` Optional - only with check_vault_auction - Auction of the vault, pda of ['auction', auction program id, vault key]
+    ///            (may be an empty account if the vault was never auctioned)
     InitFractionManager(InitFractionManagerArgs),
 
     /// NOTE: Requires a FractionManager
-    /// TODO: FIX NOTES HERE! instruction = 26
+    ///
     /// Validates that a given safety deposit box has in it contents that match the given FractionSafetyDepositConfig, and creates said config.
     /// A stateful call, this will error out if you call it a second time after validation has occurred.
-    ///   0. `[writable]` Uninitialized Safety deposit config, pda of seed ['metaplex', program id, auction manager key, safety deposit key]
-    ///   1. `[writable]` AuctionWinnerTokenTypeTracker, pda of seed ['metaplex', program id, auction manager key, 'totals']
-    ///   2. `[writable]` Auction manager
-    ///   3. `[writable]` Metadata account
-    ///   4. `[writable]` Original authority lookup - unallocated uninitialized pda account with seed ['metaplex', auction key, metadata key]
+    ///
+    /// Takes ValidateFractionSafetyDepositBoxArgs, no longer a bare FractionSafetyDepositConfig,
+    /// and accounts 17 to 20 are required where the original instruction ended at 16.
+    ///
+    ///   0. `[writable]` Uninitialized Safety deposit config, pda of seed ['metaplex', program id, fraction manager key, safety deposit key]
+    ///   1. `[writable]` Fraction manager
+    ///   2. `[writable]` Metadata account
+    ///   3. `[writable]` Original authority lookup - unallocated uninitialized pda account with seed ['metaplex', vault key, metadata key]
     ///                   We will store original authority here to return it later.
-    ///   5. `[]` A whitelisted creator entry for the store of this auction manager pda of ['metaplex', store key, creator key]
+    ///   4. `[]` A whitelisted creator entry for the store of this fraction manager pda of ['metaplex', store key, creator key]
     ///   where creator key comes from creator list of metadata, any will do
-    ///   6. `[]` The auction manager's store key
-    ///   7. `[]` Safety deposit box account
-    ///   8. `[]` Safety deposit box storage account where the actual nft token is stored
-    ///   9. `[]` Mint account of the token in the safety deposit box
-    ///   10. `[]` Edition OR MasterEdition record key
+    ///   5. `[]` The fraction manager's store key
+    ///   6. `[]` Safety deposit box account
+    ///   7. `[]` Safety deposit box storage account where the actual nft token is stored
+    ///   8. `[]` Mint account of the token in the safety deposit box
+    ///   9. `[]` Edition OR MasterEdition record key
     ///           Remember this does not need to be an existing account (may not be depending on token), just is a pda with seed
     ///            of ['metadata', program id, Printing mint id, 'edition']. - remember PDA is relative to token metadata program.
-    ///   11. `[]` Vault account
-    ///   12. `[signer]` Authority
-    ///   13. `[signer optional]` Metadata Authority - Signer only required if doing a full ownership txfer
-    ///   14. `[signer]` Payer
-    ///   15. `[]` Token metadata program
-    ///   16. `[]` System
-    ///   17. `[]` Rent sysvar
-    ValidateFractionSafetyDepositBox(FractionSafetyDepositConfig),
+    ///   10. `[]` Vault account
+    ///   11. `[signer]` Authority
+    ///   12. `[signer optional]` Metadata Authority - Signer only required if doing a full ownership txfer
+    ///           and not proving a PDA authority
+    ///   13. `[signer]` Payer
+    ///   14. `[]` Token metadata program
+    ///   15. `[]` System
+    ///   16. `[]` Rent sysvar
+    ///   17. `[]` Mint denylist of the store, pda of ['metaplex', program id, store key, 'denylist']
+    ///            (may be an empty account if the store has never denylisted a mint)
+    ///   18. `[]` Clock sysvar
+    ///   19. `[]` Fraction store config, pda of ['metaplex', program id, store key, 'fraction_config']
+    ///            (may be an empty account if the store has never set one)
+    ///   20. `[]` Fraction treasury of the vault, which must be empty once the last box validates
+    ///   21. `[signer]` Depositor (only if require_depositor_custody is set)
+    ///   22. `[]` Deposit record, pda of ['metaplex', program id, vault key, mint key, 'deposit']
+    ///            (only if require_depositor_custody is set)
+    ValidateFractionSafetyDepositBox(ValidateFractionSafetyDepositBoxArgs),
+
+    /// NOTE: Requires a FractionManager in the Redeemable state.
+    ///
+    /// Pauses or resumes redemption and buyout on a fraction manager.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetRedemptionPaused(SetRedemptionPausedArgs),
+
+    /// Given a signer wallet that owns a store, add or remove a mint on that store's denylist,
+    /// creating the denylist if it does not exist. Denylisted mints cannot be fractionalized.
+    ///
+    ///   0. `[writable]` Mint denylist, pda of ['metaplex', program id, store key, 'denylist']
+    ///   1. `[signer]` The admin wallet
+    ///   2. `[signer]` Payer
+    ///   3. `[]` Mint being added to or removed from the denylist
+    ///   4. `[]` The store key, seed of ['metaplex', admin wallet]
+    ///   5. `[]` System
+    ///   6. `[]` Rent sysvar
+    SetMintDenylisted(SetMintDenylistedArgs),
+
+    /// NOTE: Requires a FractionManager that has not been activated.
+    ///
+    /// Updates the fixed price on a validated FractionSafetyDepositConfig, optionally locking it.
+    /// Configs created or locked as immutable cannot be updated.
+    ///
+    ///   0. `[writable]` Fraction safety deposit config, pda of seed ['metaplex', program id, fraction manager key, safety deposit key]
+    ///   1. `[]` Fraction manager
+    ///   2. `[]` Safety deposit box account
+    ///   3. `[signer]` Fraction manager authority
+    UpdateFractionSafetyDepositConfig(UpdateFractionSafetyDepositConfigArgs),
+
+    /// Initializes a Fraction Manager V1 like InitFractionManager, and creates its fraction mint
+    /// with the fraction manager as mint and freeze authority.
+    ///
+    ///   0. `[writable]` Uninitialized, unallocated fraction manager account with pda of ['metaplex', vault]
+    ///   1. `[]` Active vault account with authority set to the fraction manager or the authority
+    ///   2. `[]` Mint of the accept payment account and the external price account
+    ///   3. `[]` External price account, must be the vault's pricing lookup address
+    ///   4. `[]` Authority for the Fraction Manager
+    ///   5. `[signer]` Payer
+    ///   6. `[]` Accept payment account, owner should be the fraction manager
+    ///   7. `[]` Store that this fraction manager will belong to
+    ///   8. `[]` System
+    ///   9. `[]` Rent sysvar
+    ///   10. `[writable]` Vault claim, pda of ['metaplex', program id, vault key, 'claim']
+    ///   11. `[]` Fraction store config, pda of ['metaplex', program id, store key, 'fraction_config']
+    ///            (may be an empty account if the store has never set one)
+    ///   12. `[writable]` Uncreated fraction mint, pda of ['metaplex', program id, fraction manager key, 'fraction_mint']
+    ///   13. `[]` Token program
+    ///   14. `[]` Optional - only with check_vault_auction - Auction of the vault, pda of ['auction', auction program id, vault key]
+    ///            (may be an empty account if the vault was never auctioned)
+    InitFractionManagerWithMint(InitFractionManagerWithMintArgs),
+
+    /// NOTE: Requires a FractionManager
+    ///
+    /// Validates the last safety deposit box of the vault exactly like ValidateFractionSafetyDepositBox
+    /// and moves the fraction manager straight to Redeemable at the config's fixed price, which
+    /// buyouts then settle at. The price must be at or above the manager's redemption floor.
+    /// Only single box vaults are accepted unless allow_multiple_boxes is set.
+    ///
+    /// Takes the same accounts as ValidateFractionSafetyDepositBox.
+    ValidateAndActivate(ValidateAndActivateArgs),
+
+    /// Given a signer wallet that owns a store, create or update the store's fraction config.
+    ///
+    ///   0. `[writable]` Fraction store config, pda of ['metaplex', program id, store key, 'fraction_config']
+    ///   1. `[signer]` The admin wallet
+    ///   2. `[signer]` Payer
+    ///   3. `[]` The store key, seed of ['metaplex', admin wallet]
+    ///   4. `[]` System
+    ///   5. `[]` Rent sysvar
+    SetFractionStoreConfig(SetFractionStoreConfigArgs),
+
+    /// NOTE: Requires a FractionManager that has not been activated
+    ///
+    /// Records the creators of an NFT in the vault, along with whether each had verified the
+    /// metadata. Creator payouts are only made to creators verified in this snapshot, which is
+    /// taken once per metadata and can't be retaken. Once any is taken every buyout of the manager
+    /// must credit the snapshotted creators.
+    ///
+    ///   0. `[writable]` Creator snapshot, pda of ['metaplex', program id, fraction manager key, metadata key, 'creators']
+    ///   1. `[writable]` Fraction manager
+    ///   2. `[]` Safety deposit box holding the NFT
+    ///   3. `[]` Metadata of the NFT
+    ///   4. `[signer]` Authority of the fraction manager
+    ///   5. `[signer]` Payer
+    ///   6. `[]` Store
+    ///   7. `[]` System
+    ///   8. `[]` Rent sysvar
+    SnapshotFractionCreators,
+
+    /// NOTE: Requires a FractionManager
+    ///
+    /// Sets the external price per share range redemption and buyout will accept.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetPriceBounds(SetPriceBoundsArgs),
+
+    /// NOTE: Requires a Combined FractionManager whose original authority lookup of the metadata
+    /// has been closed
+    ///
+    /// Returns update authority of metadata stuck with a fraction manager to the wallet the store
+    /// admin claims held it. The first call records the claim, a call after the dispute window
+    /// moves the authority. Both are logged and kept on the rescue account. The fraction manager
+    /// authority may drop the claim with DisputeMetadataRescue while the window is open.
+    ///
+    ///   0. `[writable]` Rescue record, pda of ['metaplex', program id, metadata key, 'rescue']
+    ///   1. `[writable]` Fraction manager, pda of ['metaplex', vault key]
+    ///   2. `[writable]` Metadata whose update authority is the fraction manager
+    ///   3. `[]` Closed original authority lookup, pda of ['metaplex', vault key, metadata key]
+    ///   4. `[]` Claimed original authority
+    ///   5. `[signer]` Store admin
+    ///   6. `[signer]` Payer
+    ///   7. `[]` Store, seed of ['metaplex', admin wallet]
+    ///   8. `[]` Token metadata program of the store
+    ///   9. `[]` System
+    ///   10. `[]` Rent sysvar
+    ///   11. `[]` Clock sysvar
+    RescueStuckMetadata,
+
+    /// NOTE: Requires a FractionManager that has not been validated
+    ///
+    /// Allocates the config of a safety deposit box ahead of its validation, recording the
+    /// winning config type it must later be validated with, and optionally the only metadata
+    /// authority allowed to validate it.
+    ///
+    ///   0. `[writable]` Uninitialized Safety deposit config, pda of seed ['metaplex', program id, fraction manager key, safety deposit key]
+    ///   1. `[writable]` Fraction manager
+    ///   2. `[]` Safety deposit box
+    ///   3. `[signer]` Authority of the fraction manager
+    ///   4. `[signer]` Payer
+    ///   5. `[]` Store
+    ///   6. `[]` System
+    ///   7. `[]` Rent sysvar
+    PreallocateFractionSafetyDepositConfig(PreallocateFractionSafetyDepositConfigArgs),
+
+    /// NOTE: Requires a FractionManager
+    ///
+    /// Sets a linear vesting schedule on creator royalty withdrawals, of at most
+    /// MAX_ROYALTY_VESTING_SECONDS. Vesting starts once redemption opens, after which the
+    /// schedule can no longer change.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetRoyaltyVesting(SetRoyaltyVestingArgs),
+
+    /// NOTE: Requires a FractionManager with a creator snapshot
+    ///
+    /// Withdraws the vested, not yet withdrawn part of a creator's royalties from the accept payment
+    /// account. Only creators verified in the snapshot may withdraw.
+    ///
+    ///   0. `[writable]` Creator proceeds, pda of ['metaplex', program id, fraction manager key, creator key, 'proceeds']
+    ///   1. `[]` Fraction manager, pda of ['metaplex', vault key]
+    ///   2. `[writable]` Accept payment account of the fraction manager
+    ///   3. `[writable]` Destination token account of the accept payment mint
+    ///   4. `[signer]` Creator
+    ///   5. `[]` Creator snapshot, pda of ['metaplex', program id, fraction manager key, metadata key, 'creators']
+    ///   6. `[]` Store
+    ///   7. `[]` Token program
+    ///   8. `[]` Clock sysvar
+    WithdrawCreatorProceeds,
+
+    /// NOTE: Requires a Combined FractionManager whose holders have redeemed every share and whose
+    /// creators have withdrawn every royalty
+    ///
+    /// Hands the vault authority and any metadata authority still held back, then closes every
+    /// config, lookup, snapshot, proceeds and rescue account of the manager, its accept payment
+    /// account and the manager itself. Prepaid teardown rent goes back to the payer who deposited
+    /// it and every other lamport to the recipient. Logs a FractionManagerClosed summary which is
+    /// also set as return data. Fails while any record the manager created is left out.
+    ///
+    ///   0. `[writable]` Combined fraction manager
+    ///   1. `[signer]` Fraction manager authority, which the vault authority is returned to
+    ///   2. `[writable]` Recipient of the closed accounts' lamports
+    ///   3. `[writable]` Payer of the fraction manager at init, refunded the prepaid teardown rent
+    ///   4. `[writable]` Vault
+    ///   5. `[writable]` Accept payment account
+    ///   6. `[writable]` Token account of the accept payment mint, sent any tokens left in accept payment
+    ///   7. `[]` Store
+    ///   8. `[]` Token program
+    ///   9. `[]` Token vault program
+    ///   10. `[]` Token metadata program
+    ///   11+. `[writable]` Every record of the manager. An original authority lookup is followed by
+    ///        `[writable]` its metadata and `[]` the wallet the lookup returns authority to.
+    FinalizeFractionManagerTeardown,
+
+    /// NOTE: Requires a FractionManager whose metadata uri is not locked
+    ///
+    /// Sets the descriptive uri marketplaces display for the fractionalized asset.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetFractionManagerMetadataUri(SetFractionManagerMetadataUriArgs),
+
+    /// NOTE: Requires a FractionManager that has been validated
+    ///
+    /// Locks the fraction manager's metadata uri for good. There is no way to unlock it.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetFractionManagerMetadataUriImmutable,
+
+    /// NOTE: Requires a creator snapshot
+    ///
+    /// Read only. Sets a FractionCreatorDrift as return data, telling whether the metadata's
+    /// creators changed since they were snapshotted.
+    ///
+    ///   0. `[]` Creator snapshot, pda of ['metaplex', program id, fraction manager key, metadata key, 'creators']
+    ///   1. `[]` Metadata the snapshot was taken of
+    ///   2. `[]` Store
+    ReportFractionCreatorDrift,
+
+    /// NOTE: Requires a FractionManager
+    ///
+    /// Sets the caps batch mints of fraction shares must respect.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    SetFractionSupplyCaps(SetFractionSupplyCapsArgs),
+
+    /// NOTE: Requires a validated, not yet redeemable, FractionManager that created its fraction mint
+    ///
+    /// Mints fraction shares to many recipients at once, within the manager's supply caps.
+    /// Mints to the first MAX_BATCH_MINT_RECIPIENTS allocations only, setting a
+    /// FractionShareBatchMinted as return data either way.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[signer]` Fraction manager authority
+    ///   2. `[writable]` Fraction mint, pda of ['metaplex', program id, fraction manager key, 'fraction_mint']
+    ///   3. `[]` Store
+    ///   4. `[]` Token program
+    ///   5+. `[writable]` Associated token account of the fraction mint for each allocation's recipient, in allocation order
+    BatchMintFractionShares(BatchMintFractionSharesArgs),
+
+    /// NOTE: Requires an inactive vault
+    ///
+    /// Records that the signing depositor holds an NFT in its own token account before adding it
+    /// to the vault, which validation with require_depositor_custody later checks. Written once.
+    ///
+    ///   0. `[writable]` Uninitialized deposit record, pda of ['metaplex', program id, vault key, mint key, 'deposit']
+    ///   1. `[]` Inactive vault the NFT is going into
+    ///   2. `[]` Metadata of the NFT
+    ///   3. `[signer]` Depositor
+    ///   4. `[]` Depositor's token account holding the NFT
+    ///   5. `[signer]` Payer
+    ///   6. `[]` Store
+    ///   7. `[]` System
+    ///   8. `[]` Rent sysvar
+    RecordFractionDeposit,
+    /// NOTE: Requires a Redeemable FractionManager whose redemption is not paused, that is the
+    /// vault's authority and minted its shares from its own fraction mint
+    ///
+    /// While the creator priority window is open only a verified creator may buy out. Buys every
+    /// outstanding fraction share at the activation price, or else the external price per share
+    /// within the manager's price bounds, at or above its redemption price floor. The buyer's
+    /// tokens are paid into accept payment, the vault is combined and handed to the buyer, and the
+    /// manager moves to Combined for holders to redeem their shares. The store's buyout fee is
+    /// paid to the store fee account first, and creators verified in the snapshot are credited
+    /// royalties on the rest to withdraw as they vest.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[writable]` Vault
+    ///   2. `[]` Fraction mint of the manager
+    ///   3. `[]` External price account of the manager
+    ///   4. `[writable]` Accept payment account of the manager
+    ///   5. `[writable]` Buyer's token account of the payment mint
+    ///   6. `[writable, signer]` Buyer, transfer authority of its token accounts and new authority of the vault
+    ///   7. `[]` Store
+    ///   8. `[]` Token program
+    ///   9. `[]` Fraction store config, pda of ['metaplex', program id, store key, 'fraction_config']
+    ///   10. `[writable]` Store fee account, a token account of the payment mint owned by the store admin
+    ///   11. `[]` Store admin wallet the store is derived from
+    ///   12. `[]` Clock sysvar
+    ///   13. `[]` Token vault program
+    ///   14. `[writable]` Buyer's token account of the vault's fraction mint, whose shares are burned
+    ///   15. `[writable]` Fraction mint of the vault
+    ///   16. `[writable]` Fraction treasury of the vault
+    ///   17. `[writable]` Redeem treasury of the vault, paid for any of the vault's circulating shares
+    ///   18. `[]` Burn authority of the vault, pda of ['vault', token vault program, vault key]
+    ///
+    ///   Only when the manager has a creator priority window or a creator snapshot:
+    ///   19. `[]` Creator snapshot, pda of ['metaplex', program id, fraction manager key, metadata key, 'creators']
+    ///   20. `[]` Metadata the creator snapshot was taken of
+    ///   21. `[signer]` Payer
+    ///   22. `[]` System
+    ///   23. `[]` Rent sysvar
+    ///   24+. `[writable]` Creator proceeds of each creator verified in the snapshot, in snapshot order,
+    ///            pda of ['metaplex', program id, fraction manager key, creator key, 'proceeds']
+    BuyoutFractionManager,
+
+    /// NOTE: Requires a Combined FractionManager whose redemption is not paused
+    ///
+    /// Burns every share in the holder's token account and pays the holder their part of the
+    /// buyout out of accept payment. Shares are paid pro rata, rounding down, and the last shares
+    /// redeemed take whatever the buyout left for holders.
+    ///
+    ///   0. `[writable]` Fraction manager
+    ///   1. `[]` Vault
+    ///   2. `[writable]` Fraction mint of the manager
+    ///   3. `[writable]` Holder's token account of the fraction mint
+    ///   4. `[signer]` Holder, owner of its token account
+    ///   5. `[writable]` Accept payment account of the manager
+    ///   6. `[writable]` Token account of the payment mint the holder is paid into
+    ///   7. `[]` Store
+    ///   8. `[]` Token program
+    RedeemFractionShares,
+
+    /// NOTE: Requires a metadata rescue whose dispute window is still open
+    ///
+    /// Drops a store admin's rescue claim on metadata held by the fraction manager, closing the
+    /// rescue account and refunding its rent to the admin. The admin may request again.
+    ///
+    ///   0. `[writable]` Rescue record, pda of ['metaplex', program id, metadata key, 'rescue']
+    ///   1. `[writable]` Fraction manager
+    ///   2. `[signer]` Fraction manager authority
+    ///   3. `[writable]` Store admin that requested the rescue
+    ///   4. `[]` Clock sysvar
+    DisputeMetadataRescue,
+
+    /// NOTE: Requires a FractionManager created before its state was extended
+    ///
+    /// Grows a fraction manager still in the original LEGACY_FRACTION_MANAGER_SIZE layout to the
+    /// current one, which every other fraction manager instruction reads. The payer covers the
+    /// added rent and is recorded as the manager's teardown rent payer.
+    ///
+    ///   0. `[writable]` Fraction manager, pda of ['metaplex', vault key]
+    ///   1. `[signer]` Fraction manager authority
+    ///   2. `[]` Vault
+    ///   3. `[signer, writable]` Payer
+    ///   4. `[]` System
+    ///   5. `[]` Rent sysvar
+    MigrateFractionManager,
 }
 
 /// Creates an DeprecatedInitAuctionManager instruction
@@ -819,26 +1340,64 @@ pub fn create_init_fraction_manager_instruction(
     program_id: Pubkey,
     fraction_manager: Pubkey,
     vault: Pubkey,
+    token_mint: Pubkey,
+    external_price_account: Pubkey,
     fraction_manager_authority: Pubkey,
     payer: Pubkey,
     accept_payment_account_key: Pubkey,
     store: Pubkey,
     orderbook_market_pool_size: u64,
+    creator_priority_until: Option<i64>,
+    min_redemption_price_per_share: u64,
+    prepaid_teardown_rent: u64,
+    vault_auction: Option<Pubkey>,
 ) -> Instruction {
+    let (vault_claim, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault.as_ref(),
+            CLAIM.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (fraction_store_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(fraction_manager, false),
+        AccountMeta::new_readonly(vault, false),
+        AccountMeta::new_readonly(token_mint, false),
+        AccountMeta::new_readonly(external_price_account, false),
+        AccountMeta::new_readonly(fraction_manager_authority, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(accept_payment_account_key, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(vault_claim, false),
+        AccountMeta::new_readonly(fraction_store_config, false),
+    ];
+    if let Some(vault_auction) = vault_auction {
+        accounts.push(AccountMeta::new_readonly(vault_auction, false));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(fraction_manager, false),
-            AccountMeta::new_readonly(vault, false),
-            AccountMeta::new_readonly(fraction_manager_authority, false),
-            AccountMeta::new_readonly(payer, true),
-            AccountMeta::new_readonly(accept_payment_account_key, false),
-            AccountMeta::new_readonly(store, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-        ],
+        accounts,
         data: MetaplexInstruction::InitFractionManager(InitFractionManagerArgs {
             orderbook_market_pool_size,
+            creator_priority_until,
+            min_redemption_price_per_share,
+            prepaid_teardown_rent,
+            check_vault_auction: vault_auction.is_some(),
         })
         .try_to_vec()
         .unwrap(),
@@ -1004,25 +1563,65 @@ pub fn create_validate_safety_deposit_box_v2_instruction(
     }
 }
 
+/// Accounts of a ValidateFractionSafetyDepositBox or ValidateAndActivate instruction that aren't
+/// derived from the others
+pub struct ValidateFractionSafetyDepositBoxAccounts {
+    pub fraction_manager: Pubkey,
+    pub metadata: Pubkey,
+    pub original_authority_lookup: Pubkey,
+    pub whitelisted_creator: Pubkey,
+    pub store: Pubkey,
+    pub safety_deposit_box: Pubkey,
+    pub safety_deposit_token_store: Pubkey,
+    pub safety_deposit_mint: Pubkey,
+    pub edition: Pubkey,
+    pub vault: Pubkey,
+    pub fraction_treasury: Pubkey,
+    pub fraction_manager_authority: Pubkey,
+    pub metadata_authority: Pubkey,
+    pub payer: Pubkey,
+}
+
+/// Optional checks and behaviour of a validation, all off by default
+#[derive(Clone, Default)]
+pub struct ValidateFractionSafetyDepositBoxOptions {
+    /// If set, validation fails once the cluster is past this slot
+    pub max_slot: Option<u64>,
+    /// Set when the metadata update authority is a PDA of a store approved program
+    pub pda_authority: Option<PdaAuthority>,
+    /// Sets a ComputeUnitReport as return data
+    pub report_cu: bool,
+    /// Wallet teardown hands the metadata authority to instead of the original authority
+    pub return_to: Option<Pubkey>,
+    /// Depositor that must sign and have recorded the deposit with RecordFractionDeposit
+    pub depositor: Option<Pubkey>,
+    /// Only validate a box whose order falls in this range
+    pub order_range: Option<FractionOrderRange>,
+}
+
 /// Creates an ValidateFractionSafetyDepositBox instruction
-#[allow(clippy::too_many_arguments)]
 pub fn create_validate_fraction_safety_deposit_box_instruction(
     program_id: Pubkey,
-    fraction_manager: Pubkey,
-    metadata: Pubkey,
-    original_authority_lookup: Pubkey,
-    whitelisted_creator: Pubkey,
-    store: Pubkey,
-    safety_deposit_box: Pubkey,
-    safety_deposit_token_store: Pubkey,
-    safety_deposit_mint: Pubkey,
-    edition: Pubkey,
-    vault: Pubkey,
-    fraction_manager_authority: Pubkey,
-    metadata_authority: Pubkey,
-    payer: Pubkey,
+    accounts: ValidateFractionSafetyDepositBoxAccounts,
     safety_deposit_config: FractionSafetyDepositConfig,
+    options: ValidateFractionSafetyDepositBoxOptions,
 ) -> Instruction {
+    let ValidateFractionSafetyDepositBoxAccounts {
+        fraction_manager,
+        metadata,
+        original_authority_lookup,
+        whitelisted_creator,
+        store,
+        safety_deposit_box,
+        safety_deposit_token_store,
+        safety_deposit_mint,
+        edition,
+        vault,
+        fraction_treasury,
+        fraction_manager_authority,
+        metadata_authority,
+        payer,
+    } = accounts;
     let (validation, _) = Pubkey::find_program_address(
         &[
             PREFIX.as_bytes(),
@@ -1032,7 +1631,25 @@ pub fn create_validate_fraction_safety_deposit_box_instruction(
         ],
         &program_id,
     );
-    let accounts = vec![
+    let (mint_denylist, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            DENYLIST.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (fraction_store_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        &program_id,
+    );
+    let mut accounts = vec![
         AccountMeta::new(validation, false),
         AccountMeta::new(fraction_manager, false),
         AccountMeta::new(metadata, false),
@@ -1045,19 +1662,48 @@ pub fn create_validate_fraction_safety_deposit_box_instruction(
         AccountMeta::new_readonly(edition, false),
         AccountMeta::new_readonly(vault, false),
         AccountMeta::new_readonly(fraction_manager_authority, true),
-        AccountMeta::new_readonly(metadata_authority, true),
+        // A PDA authority cannot sign, it is proven with seeds instead
+        AccountMeta::new_readonly(metadata_authority, options.pda_authority.is_none()),
         AccountMeta::new_readonly(payer, true),
         AccountMeta::new_readonly(mpl_token_metadata::id(), false),
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(mint_denylist, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(fraction_store_config, false),
+        AccountMeta::new_readonly(fraction_treasury, false),
     ];
+    if let Some(depositor) = options.depositor {
+        let (deposit_record, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                vault.as_ref(),
+                safety_deposit_mint.as_ref(),
+                DEPOSIT.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new_readonly(depositor, true));
+        accounts.push(AccountMeta::new_readonly(deposit_record, false));
+    }
 
     Instruction {
         program_id,
         accounts,
-        data: MetaplexInstruction::ValidateFractionSafetyDepositBox(safety_deposit_config)
-            .try_to_vec()
-            .unwrap(),
+        data: MetaplexInstruction::ValidateFractionSafetyDepositBox(
+            ValidateFractionSafetyDepositBoxArgs {
+                safety_deposit_config,
+                max_slot: options.max_slot,
+                require_depositor_custody: options.depositor.is_some(),
+                pda_authority: options.pda_authority,
+                report_cu: options.report_cu,
+                return_to: options.return_to,
+                order_range: options.order_range,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
     }
 }
 
@@ -1733,3 +2379,938 @@ pub fn create_end_auction_instruction(
             .unwrap(),
     }
 }
+
+/// Creates an SetRedemptionPaused instruction
+pub fn create_set_redemption_paused_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    paused: bool,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetRedemptionPaused(SetRedemptionPausedArgs { paused })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetMintDenylisted instruction
+pub fn create_set_mint_denylisted_instruction(
+    program_id: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    denylisted: bool,
+) -> Instruction {
+    let (store, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), admin.as_ref()],
+        &program_id,
+    );
+    let (mint_denylist, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            DENYLIST.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(mint_denylist, false),
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::SetMintDenylisted(SetMintDenylistedArgs { denylisted })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an UpdateFractionSafetyDepositConfig instruction
+pub fn create_update_fraction_safety_deposit_config_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    safety_deposit_box: Pubkey,
+    fraction_manager_authority: Pubkey,
+    fixed_price: Option<u64>,
+    immutable: bool,
+) -> Instruction {
+    let (safety_deposit_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            safety_deposit_box.as_ref(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(safety_deposit_config, false),
+            AccountMeta::new_readonly(fraction_manager, false),
+            AccountMeta::new_readonly(safety_deposit_box, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::UpdateFractionSafetyDepositConfig(
+            UpdateFractionSafetyDepositConfigArgs {
+                fixed_price,
+                immutable,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an InitFractionManagerWithMint instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_init_fraction_manager_with_mint_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    token_mint: Pubkey,
+    external_price_account: Pubkey,
+    fraction_manager_authority: Pubkey,
+    payer: Pubkey,
+    accept_payment_account_key: Pubkey,
+    store: Pubkey,
+    orderbook_market_pool_size: u64,
+    creator_priority_until: Option<i64>,
+    min_redemption_price_per_share: u64,
+    prepaid_teardown_rent: u64,
+    decimals: u8,
+    vault_auction: Option<Pubkey>,
+) -> Instruction {
+    let (fraction_manager, _) =
+        Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id);
+    let (vault_claim, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault.as_ref(),
+            CLAIM.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (fraction_store_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (fraction_mint, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            FRACTION_MINT.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(fraction_manager, false),
+        AccountMeta::new_readonly(vault, false),
+        AccountMeta::new_readonly(token_mint, false),
+        AccountMeta::new_readonly(external_price_account, false),
+        AccountMeta::new_readonly(fraction_manager_authority, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(accept_payment_account_key, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(vault_claim, false),
+        AccountMeta::new_readonly(fraction_store_config, false),
+        AccountMeta::new(fraction_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    if let Some(vault_auction) = vault_auction {
+        accounts.push(AccountMeta::new_readonly(vault_auction, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::InitFractionManagerWithMint(InitFractionManagerWithMintArgs {
+            orderbook_market_pool_size,
+            creator_priority_until,
+            min_redemption_price_per_share,
+            prepaid_teardown_rent,
+            decimals,
+            check_vault_auction: vault_auction.is_some(),
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an ValidateAndActivate instruction
+pub fn create_validate_and_activate_instruction(
+    program_id: Pubkey,
+    accounts: ValidateFractionSafetyDepositBoxAccounts,
+    safety_deposit_config: FractionSafetyDepositConfig,
+    allow_multiple_boxes: bool,
+    options: ValidateFractionSafetyDepositBoxOptions,
+) -> Instruction {
+    let mut instruction = create_validate_fraction_safety_deposit_box_instruction(
+        program_id,
+        accounts,
+        safety_deposit_config.clone(),
+        options.clone(),
+    );
+    instruction.data = MetaplexInstruction::ValidateAndActivate(ValidateAndActivateArgs {
+        safety_deposit_config,
+        max_slot: options.max_slot,
+        allow_multiple_boxes,
+        require_depositor_custody: options.depositor.is_some(),
+        pda_authority: options.pda_authority,
+        report_cu: options.report_cu,
+        return_to: options.return_to,
+        order_range: options.order_range,
+    })
+    .try_to_vec()
+    .unwrap();
+
+    instruction
+}
+
+/// Creates an SetFractionStoreConfig instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_set_fraction_store_config_instruction(
+    program_id: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    max_manager_generations: Option<u64>,
+    approved_authority_programs: Vec<Pubkey>,
+    buyout_fee_bps: u16,
+    teardown_rent_deposit: Option<u64>,
+    approved_metadata_programs: Vec<Pubkey>,
+) -> Instruction {
+    let (store, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), admin.as_ref()],
+        &program_id,
+    );
+    let (fraction_store_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_store_config, false),
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::SetFractionStoreConfig(SetFractionStoreConfigArgs {
+            max_manager_generations,
+            approved_authority_programs,
+            buyout_fee_bps,
+            teardown_rent_deposit,
+            approved_metadata_programs,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an SnapshotFractionCreators instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_snapshot_fraction_creators_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    safety_deposit_box: Pubkey,
+    metadata: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    let (creator_snapshot, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            metadata.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_snapshot, false),
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(safety_deposit_box, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::SnapshotFractionCreators
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetPriceBounds instruction
+pub fn create_set_price_bounds_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetPriceBounds(SetPriceBoundsArgs {
+            min_price,
+            max_price,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an RescueStuckMetadata instruction
+pub fn create_rescue_stuck_metadata_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    metadata: Pubkey,
+    claimed_original_authority: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    token_metadata_program: Pubkey,
+) -> Instruction {
+    let (store, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), admin.as_ref()],
+        &program_id,
+    );
+    let (fraction_manager, _) =
+        Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id);
+    let (original_authority_lookup, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), vault.as_ref(), metadata.as_ref()],
+        &program_id,
+    );
+    let (rescue, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.as_ref(),
+            RESCUE.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(rescue, false),
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(original_authority_lookup, false),
+            AccountMeta::new_readonly(claimed_original_authority, false),
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(token_metadata_program, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::RescueStuckMetadata
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an PreallocateFractionSafetyDepositConfig instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_preallocate_fraction_safety_deposit_config_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    safety_deposit_box: Pubkey,
+    fraction_manager_authority: Pubkey,
+    payer: Pubkey,
+    store: Pubkey,
+    fraction_winning_config_type: FractionWinningConfigType,
+    metadata_authority: Option<Pubkey>,
+) -> Instruction {
+    let (safety_deposit_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            safety_deposit_box.as_ref(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(safety_deposit_config, false),
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(safety_deposit_box, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::PreallocateFractionSafetyDepositConfig(
+            PreallocateFractionSafetyDepositConfigArgs {
+                fraction_winning_config_type,
+                metadata_authority,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an SetRoyaltyVesting instruction
+pub fn create_set_royalty_vesting_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    vesting_seconds: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetRoyaltyVesting(SetRoyaltyVestingArgs { vesting_seconds })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an WithdrawCreatorProceeds instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_withdraw_creator_proceeds_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    accept_payment: Pubkey,
+    destination: Pubkey,
+    creator: Pubkey,
+    metadata: Pubkey,
+    store: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    let (creator_proceeds, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            creator.as_ref(),
+            PROCEEDS.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (creator_snapshot, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            metadata.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_proceeds, false),
+            AccountMeta::new_readonly(fraction_manager, false),
+            AccountMeta::new(accept_payment, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(creator, true),
+            AccountMeta::new_readonly(creator_snapshot, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::WithdrawCreatorProceeds
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Original authority lookup closed by teardown, with the accounts handing its metadata back
+pub struct TeardownAuthorityLookup {
+    pub metadata: Pubkey,
+    /// The lookup's return_to, or its original authority when return_to isn't set
+    pub return_authority: Pubkey,
+}
+
+/// Creates an FinalizeFractionManagerTeardown instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_finalize_fraction_manager_teardown_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    recipient: Pubkey,
+    teardown_rent_payer: Pubkey,
+    vault: Pubkey,
+    accept_payment: Pubkey,
+    leftover_destination: Pubkey,
+    store: Pubkey,
+    token_vault_program: Pubkey,
+    records: Vec<Pubkey>,
+    authority_lookups: Vec<TeardownAuthorityLookup>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(fraction_manager, false),
+        AccountMeta::new_readonly(fraction_manager_authority, true),
+        AccountMeta::new(recipient, false),
+        AccountMeta::new(teardown_rent_payer, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new(accept_payment, false),
+        AccountMeta::new(leftover_destination, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(token_vault_program, false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+    ];
+    accounts.extend(
+        records
+            .into_iter()
+            .map(|record| AccountMeta::new(record, false)),
+    );
+    for lookup in authority_lookups {
+        let (original_authority_lookup, _) = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), vault.as_ref(), lookup.metadata.as_ref()],
+            &program_id,
+        );
+        accounts.extend([
+            AccountMeta::new(original_authority_lookup, false),
+            AccountMeta::new(lookup.metadata, false),
+            AccountMeta::new_readonly(lookup.return_authority, false),
+        ]);
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::FinalizeFractionManagerTeardown
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetFractionManagerMetadataUri instruction
+pub fn create_set_fraction_manager_metadata_uri_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    uri: String,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetFractionManagerMetadataUri(
+            SetFractionManagerMetadataUriArgs { uri },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an SetFractionManagerMetadataUriImmutable instruction
+pub fn create_set_fraction_manager_metadata_uri_immutable_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetFractionManagerMetadataUriImmutable
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an ReportFractionCreatorDrift instruction
+pub fn create_report_fraction_creator_drift_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    metadata: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    let (creator_snapshot, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            metadata.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(creator_snapshot, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(store, false),
+        ],
+        data: MetaplexInstruction::ReportFractionCreatorDrift
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetFractionSupplyCaps instruction
+pub fn create_set_fraction_supply_caps_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    max_fraction_supply: Option<u64>,
+    max_fraction_shares_per_wallet: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::SetFractionSupplyCaps(SetFractionSupplyCapsArgs {
+            max_fraction_supply,
+            max_fraction_shares_per_wallet,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an BatchMintFractionShares instruction
+pub fn create_batch_mint_fraction_shares_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    fraction_manager_authority: Pubkey,
+    store: Pubkey,
+    allocations: Vec<FractionShareAllocation>,
+) -> Instruction {
+    let (fraction_mint, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            FRACTION_MINT.as_bytes(),
+        ],
+        &program_id,
+    );
+    let mut accounts = vec![
+        AccountMeta::new(fraction_manager, false),
+        AccountMeta::new_readonly(fraction_manager_authority, true),
+        AccountMeta::new(fraction_mint, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(allocations.iter().map(|allocation| {
+        AccountMeta::new(
+            get_associated_token_address(&allocation.recipient, &fraction_mint),
+            false,
+        )
+    }));
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::BatchMintFractionShares(BatchMintFractionSharesArgs {
+            allocations,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an RecordFractionDeposit instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_record_fraction_deposit_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    depositor: Pubkey,
+    depositor_token_account: Pubkey,
+    payer: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    let (deposit_record, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault.as_ref(),
+            mint.as_ref(),
+            DEPOSIT.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(deposit_record, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(depositor, true),
+            AccountMeta::new_readonly(depositor_token_account, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::RecordFractionDeposit
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creator accounts of a buyout, needed once the manager has a creator priority window or a
+/// creator snapshot
+pub struct BuyoutCreatorAccounts {
+    /// Metadata the creator snapshot was taken of
+    pub metadata: Pubkey,
+    /// Payer of any creator proceeds accounts created
+    pub payer: Pubkey,
+    /// Creators verified in the snapshot, in snapshot order
+    pub verified_creators: Vec<Pubkey>,
+}
+
+/// Creates an BuyoutFractionManager instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_buyout_fraction_manager_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    vault: Pubkey,
+    fraction_mint: Pubkey,
+    external_price_account: Pubkey,
+    accept_payment: Pubkey,
+    buyer_token_account: Pubkey,
+    buyer: Pubkey,
+    store: Pubkey,
+    store_fee_account: Pubkey,
+    store_admin: Pubkey,
+    token_vault_program: Pubkey,
+    buyer_vault_shares: Pubkey,
+    vault_fraction_mint: Pubkey,
+    vault_fraction_treasury: Pubkey,
+    vault_redeem_treasury: Pubkey,
+    creators: Option<BuyoutCreatorAccounts>,
+) -> Instruction {
+    let (fraction_store_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (vault_burn_authority, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_vault::state::PREFIX.as_bytes(),
+            token_vault_program.as_ref(),
+            vault.as_ref(),
+        ],
+        &token_vault_program,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(fraction_manager, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(fraction_mint, false),
+        AccountMeta::new_readonly(external_price_account, false),
+        AccountMeta::new(accept_payment, false),
+        AccountMeta::new(buyer_token_account, false),
+        AccountMeta::new(buyer, true),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fraction_store_config, false),
+        AccountMeta::new(store_fee_account, false),
+        AccountMeta::new_readonly(store_admin, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(token_vault_program, false),
+        AccountMeta::new(buyer_vault_shares, false),
+        AccountMeta::new(vault_fraction_mint, false),
+        AccountMeta::new(vault_fraction_treasury, false),
+        AccountMeta::new(vault_redeem_treasury, false),
+        AccountMeta::new_readonly(vault_burn_authority, false),
+    ];
+    if let Some(creators) = creators {
+        let (creator_snapshot, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.as_ref(),
+                creators.metadata.as_ref(),
+                CREATORS.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.extend([
+            AccountMeta::new_readonly(creator_snapshot, false),
+            AccountMeta::new_readonly(creators.metadata, false),
+            AccountMeta::new(creators.payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ]);
+        accounts.extend(creators.verified_creators.into_iter().map(|creator| {
+            let (creator_proceeds, _) = Pubkey::find_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    fraction_manager.as_ref(),
+                    creator.as_ref(),
+                    PROCEEDS.as_bytes(),
+                ],
+                &program_id,
+            );
+            AccountMeta::new(creator_proceeds, false)
+        }));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::BuyoutFractionManager
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an RedeemFractionShares instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_redeem_fraction_shares_instruction(
+    program_id: Pubkey,
+    fraction_manager: Pubkey,
+    vault: Pubkey,
+    fraction_mint: Pubkey,
+    holder_shares: Pubkey,
+    holder: Pubkey,
+    accept_payment: Pubkey,
+    destination: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new(holder_shares, false),
+            AccountMeta::new_readonly(holder, true),
+            AccountMeta::new(accept_payment, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetaplexInstruction::RedeemFractionShares
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an DisputeMetadataRescue instruction
+pub fn create_dispute_metadata_rescue_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    metadata: Pubkey,
+    fraction_manager_authority: Pubkey,
+    admin: Pubkey,
+) -> Instruction {
+    let (fraction_manager, _) =
+        Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id);
+    let (rescue, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.as_ref(),
+            RESCUE.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(rescue, false),
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+            AccountMeta::new(admin, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::DisputeMetadataRescue
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an MigrateFractionManager instruction
+pub fn create_migrate_fraction_manager_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    fraction_manager_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    let (fraction_manager, _) =
+        Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_manager, false),
+            AccountMeta::new_readonly(fraction_manager_authority, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::MigrateFractionManager
+            .try_to_vec()
+            .unwrap(),
+    }
+}
@@ -1,54 +1,100 @@
 use {
     crate::instruction::MetaplexInstruction,
+    batch_mint_fraction_shares::process_batch_mint_fraction_shares,
     borsh::BorshDeserialize,
+    buyout_fraction_manager::process_buyout_fraction_manager,
     claim_bid::process_claim_bid,
     decommission_auction_manager::process_decommission_auction_manager,
     deprecated_init_auction_manager_v1::process_deprecated_init_auction_manager_v1,
     deprecated_populate_participation_printing_account::process_deprecated_populate_participation_printing_account,
     deprecated_validate_participation::process_deprecated_validate_participation,
     deprecated_validate_safety_deposit_box_v1::process_deprecated_validate_safety_deposit_box_v1,
+    dispute_metadata_rescue::process_dispute_metadata_rescue,
     empty_payment_account::process_empty_payment_account,
     end_auction::process_end_auction,
+    finalize_fraction_manager_teardown::process_finalize_fraction_manager_teardown,
     init_auction_manager_v2::process_init_auction_manager_v2,
     init_fraction_manager::process_init_fraction_manager,
+    init_fraction_manager_with_mint::process_init_fraction_manager_with_mint,
+    migrate_fraction_manager::process_migrate_fraction_manager,
+    preallocate_fraction_safety_deposit_config::process_preallocate_fraction_safety_deposit_config,
+    record_fraction_deposit::process_record_fraction_deposit,
     redeem_bid::process_redeem_bid,
+    redeem_fraction_shares::process_redeem_fraction_shares,
     redeem_full_rights_transfer_bid::process_full_rights_transfer_bid,
     redeem_participation_bid::process_redeem_participation_bid,
     redeem_printing_v2_bid::process_redeem_printing_v2_bid,
     redeem_unused_winning_config_items_as_auctioneer::process_redeem_unused_winning_config_items_as_auctioneer,
+    report_fraction_creator_drift::process_report_fraction_creator_drift,
+    rescue_stuck_metadata::process_rescue_stuck_metadata,
     set_auction_cache::process_set_auction_cache,
+    set_fraction_manager_metadata_uri::process_set_fraction_manager_metadata_uri,
+    set_fraction_manager_metadata_uri_immutable::process_set_fraction_manager_metadata_uri_immutable,
+    set_fraction_store_config::process_set_fraction_store_config,
+    set_fraction_supply_caps::process_set_fraction_supply_caps,
+    set_mint_denylisted::process_set_mint_denylisted,
+    set_price_bounds::process_set_price_bounds,
+    set_redemption_paused::process_set_redemption_paused,
+    set_royalty_vesting::process_set_royalty_vesting,
     set_store::{process_set_store, process_set_store_v2},
     set_store_index::process_set_store_index,
     set_whitelisted_creator::process_set_whitelisted_creator,
+    snapshot_fraction_creators::process_snapshot_fraction_creators,
     solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey},
     start_auction::process_start_auction,
+    update_fraction_safety_deposit_config::process_update_fraction_safety_deposit_config,
+    validate_and_activate::process_validate_and_activate,
     validate_fraction_safety_deposit_box::process_validate_fraction_safety_deposit_box,
     validate_safety_deposit_box_v2::process_validate_safety_deposit_box_v2,
+    withdraw_creator_proceeds::process_withdraw_creator_proceeds,
     withdraw_master_edition::process_withdraw_master_edition,
 };
 
+pub mod batch_mint_fraction_shares;
+pub mod buyout_fraction_manager;
 pub mod claim_bid;
 pub mod decommission_auction_manager;
 pub mod deprecated_init_auction_manager_v1;
 pub mod deprecated_populate_participation_printing_account;
 pub mod deprecated_validate_participation;
 pub mod deprecated_validate_safety_deposit_box_v1;
+pub mod dispute_metadata_rescue;
 pub mod empty_payment_account;
 pub mod end_auction;
+pub mod finalize_fraction_manager_teardown;
 pub mod init_auction_manager_v2;
 pub mod init_fraction_manager;
+pub mod init_fraction_manager_with_mint;
+pub mod migrate_fraction_manager;
+pub mod preallocate_fraction_safety_deposit_config;
+pub mod record_fraction_deposit;
 pub mod redeem_bid;
+pub mod redeem_fraction_shares;
 pub mod redeem_full_rights_transfer_bid;
 pub mod redeem_participation_bid;
 pub mod redeem_printing_v2_bid;
 pub mod redeem_unused_winning_config_items_as_auctioneer;
+pub mod report_fraction_creator_drift;
+pub mod rescue_stuck_metadata;
 pub mod set_auction_cache;
+pub mod set_fraction_manager_metadata_uri;
+pub mod set_fraction_manager_metadata_uri_immutable;
+pub mod set_fraction_store_config;
+pub mod set_fraction_supply_caps;
+pub mod set_mint_denylisted;
+pub mod set_price_bounds;
+pub mod set_redemption_paused;
+pub mod set_royalty_vesting;
 pub mod set_store;
 pub mod set_store_index;
 pub mod set_whitelisted_creator;
+pub mod snapshot_fraction_creators;
 pub mod start_auction;
+pub mod update_fraction_safety_deposit_config;
+pub mod validate_and_activate;
 pub mod validate_fraction_safety_deposit_box;
 pub mod validate_safety_deposit_box_v2;
+pub mod withdraw_creator_proceeds;
 pub mod withdraw_master_edition;
 
 pub fn process_instruction<'a>(
@@ -151,18 +197,33 @@ pub fn process_instruction<'a>(
         }
         MetaplexInstruction::InitFractionManager(args) => {
             msg!("Instruction: Init Fraction Manager");
-            process_init_fraction_manager(program_id, accounts, args.orderbook_market_pool_size)
+            process_init_fraction_manager(
+                program_id,
+                accounts,
+                args.orderbook_market_pool_size,
+                args.creator_priority_until,
+                args.min_redemption_price_per_share,
+                args.prepaid_teardown_rent,
+                args.check_vault_auction,
+                None,
+            )
         }
         MetaplexInstruction::ValidateSafetyDepositBoxV2(safety_deposit_config) => {
             msg!("Instruction: Validate Safety Deposit Box V2");
             process_validate_safety_deposit_box_v2(program_id, accounts, safety_deposit_config)
         }
-        MetaplexInstruction::ValidateFractionSafetyDepositBox(safety_deposit_config) => {
+        MetaplexInstruction::ValidateFractionSafetyDepositBox(args) => {
             msg!("Instruction: Validate Fraction Safety Deposit Box V1");
             process_validate_fraction_safety_deposit_box(
                 program_id,
                 accounts,
-                safety_deposit_config,
+                args.safety_deposit_config,
+                args.max_slot,
+                args.pda_authority,
+                args.report_cu,
+                args.return_to,
+                args.require_depositor_custody,
+                args.order_range,
             )
         }
         MetaplexInstruction::RedeemParticipationBidV3(args) => {
@@ -181,5 +242,135 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Set Auction Cache");
             process_set_auction_cache(program_id, accounts)
         }
+        MetaplexInstruction::SetRedemptionPaused(args) => {
+            msg!("Instruction: Set Redemption Paused");
+            process_set_redemption_paused(program_id, accounts, args.paused)
+        }
+        MetaplexInstruction::SetMintDenylisted(args) => {
+            msg!("Instruction: Set Mint Denylisted");
+            process_set_mint_denylisted(program_id, accounts, args.denylisted)
+        }
+        MetaplexInstruction::UpdateFractionSafetyDepositConfig(args) => {
+            msg!("Instruction: Update Fraction Safety Deposit Config");
+            process_update_fraction_safety_deposit_config(program_id, accounts, args)
+        }
+        MetaplexInstruction::InitFractionManagerWithMint(args) => {
+            msg!("Instruction: Init Fraction Manager With Mint");
+            process_init_fraction_manager_with_mint(
+                program_id,
+                accounts,
+                args.orderbook_market_pool_size,
+                args.creator_priority_until,
+                args.min_redemption_price_per_share,
+                args.prepaid_teardown_rent,
+                args.decimals,
+                args.check_vault_auction,
+            )
+        }
+        MetaplexInstruction::ValidateAndActivate(args) => {
+            msg!("Instruction: Validate And Activate");
+            process_validate_and_activate(
+                program_id,
+                accounts,
+                args.safety_deposit_config,
+                args.max_slot,
+                args.allow_multiple_boxes,
+                args.pda_authority,
+                args.report_cu,
+                args.return_to,
+                args.require_depositor_custody,
+                args.order_range,
+            )
+        }
+        MetaplexInstruction::SetFractionStoreConfig(args) => {
+            msg!("Instruction: Set Fraction Store Config");
+            process_set_fraction_store_config(
+                program_id,
+                accounts,
+                args.max_manager_generations,
+                args.approved_authority_programs,
+                args.buyout_fee_bps,
+                args.teardown_rent_deposit,
+                args.approved_metadata_programs,
+            )
+        }
+        MetaplexInstruction::SnapshotFractionCreators => {
+            msg!("Instruction: Snapshot Fraction Creators");
+            process_snapshot_fraction_creators(program_id, accounts)
+        }
+        MetaplexInstruction::SetPriceBounds(args) => {
+            msg!("Instruction: Set Price Bounds");
+            process_set_price_bounds(program_id, accounts, args.min_price, args.max_price)
+        }
+        MetaplexInstruction::RescueStuckMetadata => {
+            msg!("Instruction: Rescue Stuck Metadata");
+            process_rescue_stuck_metadata(program_id, accounts)
+        }
+        MetaplexInstruction::PreallocateFractionSafetyDepositConfig(args) => {
+            msg!("Instruction: Preallocate Fraction Safety Deposit Config");
+            process_preallocate_fraction_safety_deposit_config(
+                program_id,
+                accounts,
+                args.fraction_winning_config_type,
+                args.metadata_authority,
+            )
+        }
+        MetaplexInstruction::SetRoyaltyVesting(args) => {
+            msg!("Instruction: Set Royalty Vesting");
+            process_set_royalty_vesting(program_id, accounts, args.vesting_seconds)
+        }
+        MetaplexInstruction::WithdrawCreatorProceeds => {
+            msg!("Instruction: Withdraw Creator Proceeds");
+            process_withdraw_creator_proceeds(program_id, accounts)
+        }
+        MetaplexInstruction::FinalizeFractionManagerTeardown => {
+            msg!("Instruction: Finalize Fraction Manager Teardown");
+            process_finalize_fraction_manager_teardown(program_id, accounts)
+        }
+        MetaplexInstruction::SetFractionManagerMetadataUri(args) => {
+            msg!("Instruction: Set Fraction Manager Metadata Uri");
+            process_set_fraction_manager_metadata_uri(program_id, accounts, args.uri)
+        }
+        MetaplexInstruction::SetFractionManagerMetadataUriImmutable => {
+            msg!("Instruction: Set Fraction Manager Metadata Uri Immutable");
+            process_set_fraction_manager_metadata_uri_immutable(program_id, accounts)
+        }
+        MetaplexInstruction::ReportFractionCreatorDrift => {
+            msg!("Instruction: Report Fraction Creator Drift");
+            process_report_fraction_creator_drift(program_id, accounts)
+        }
+        MetaplexInstruction::SetFractionSupplyCaps(args) => {
+            msg!("Instruction: Set Fraction Supply Caps");
+            process_set_fraction_supply_caps(
+                program_id,
+                accounts,
+                args.max_fraction_supply,
+                args.max_fraction_shares_per_wallet,
+            )
+        }
+        MetaplexInstruction::BatchMintFractionShares(args) => {
+            msg!("Instruction: Batch Mint Fraction Shares");
+            process_batch_mint_fraction_shares(program_id, accounts, args.allocations)
+        }
+        MetaplexInstruction::RecordFractionDeposit => {
+            msg!("Instruction: Record Fraction Deposit");
+            process_record_fraction_deposit(program_id, accounts)
+        }
+        MetaplexInstruction::BuyoutFractionManager => {
+            msg!("Instruction: Buyout Fraction Manager");
+            process_buyout_fraction_manager(program_id, accounts)
+        }
+        MetaplexInstruction::RedeemFractionShares => {
+            msg!("Instruction: Redeem Fraction Shares");
+            process_redeem_fraction_shares(program_id, accounts)
+        }
+        MetaplexInstruction::DisputeMetadataRescue => {
+            msg!("Instruction: Dispute Metadata Rescue");
+            process_dispute_metadata_rescue(program_id, accounts)
+        }
+        MetaplexInstruction::MigrateFractionManager => {
+            msg!("Instruction: Migrate Fraction Manager");
+            process_migrate_fraction_manager(program_id, accounts)
+        }
     }
 }
@@ -0,0 +1,166 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::FractionShareAllocation,
+        state::{
+            FractionManager, FractionManagerStatus, FractionManagerV1, FractionShareBatchMinted,
+            Store, MAX_BATCH_MINT_RECIPIENTS, PREFIX,
+        },
+        utils::{
+            assert_authority_correct, assert_derivation, assert_initialized, assert_owned_by,
+            spl_token_mint_to,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::set_return_data,
+        pubkey::Pubkey,
+    },
+    spl_associated_token_account::get_associated_token_address,
+    spl_token::state::{Account, Mint},
+};
+
+/// Shares a wallet holds over the batch: the balance of its associated token account, plus
+/// whatever the batch already minted to it.
+struct WalletHolding {
+    wallet: Pubkey,
+    held: u64,
+}
+
+/// Mints the allocations in order, up to MAX_BATCH_MINT_RECIPIENTS of them. The return data
+/// says how many were minted, so the rest can be sent again.
+pub fn process_batch_mint_fraction_shares<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    allocations: Vec<FractionShareAllocation>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    // Once redeemable the buyout price is set by the supply, so it can't grow any more
+    match fraction_manager.status() {
+        FractionManagerStatus::Validated | FractionManagerStatus::Active => (),
+        _ => return Err(MetaplexError::InvalidStatus.into()),
+    }
+
+    // Only the mint the manager created itself has the manager as mint authority.
+    if fraction_manager.state.fraction_mint != Some(*fraction_mint_info.key) {
+        return Err(MetaplexError::FractionMintMismatch.into());
+    }
+    assert_owned_by(fraction_mint_info, &store.token_program)?;
+    let fraction_mint: Mint = assert_initialized(fraction_mint_info)?;
+
+    let vault_key = fraction_manager.vault();
+    let bump = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_key.as_ref()],
+    )?;
+    let authority_seeds = &[PREFIX.as_bytes(), vault_key.as_ref(), &[bump]];
+
+    let (max_fraction_supply, max_fraction_shares_per_wallet) =
+        fraction_manager.fraction_supply_caps();
+    let mut supply = fraction_mint.supply;
+    let mut holdings: Vec<WalletHolding> = vec![];
+    let mut minted = FractionShareBatchMinted {
+        recipients_minted: 0,
+        amount_minted: 0,
+    };
+
+    for allocation in allocations.iter().take(MAX_BATCH_MINT_RECIPIENTS) {
+        // Shares only ever land in the recipient's associated token account, so its balance is
+        // everything the cap has let the wallet mint, whichever batch it came from.
+        let token_account_info = next_account_info(account_info_iter)?;
+        if *token_account_info.key
+            != get_associated_token_address(&allocation.recipient, fraction_mint_info.key)
+        {
+            return Err(MetaplexError::FractionShareRecipientMismatch.into());
+        }
+        assert_owned_by(token_account_info, &store.token_program)?;
+        let token_account: Account = assert_initialized(token_account_info)?;
+        if token_account.mint != *fraction_mint_info.key
+            || token_account.owner != allocation.recipient
+        {
+            return Err(MetaplexError::FractionShareRecipientMismatch.into());
+        }
+
+        let holding_index = match holdings
+            .iter()
+            .position(|holding| holding.wallet == allocation.recipient)
+        {
+            Some(index) => index,
+            None => {
+                holdings.push(WalletHolding {
+                    wallet: allocation.recipient,
+                    held: token_account.amount,
+                });
+                holdings.len() - 1
+            }
+        };
+        let holding = &mut holdings[holding_index];
+
+        holding.held = holding
+            .held
+            .checked_add(allocation.amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        if let Some(max_fraction_shares_per_wallet) = max_fraction_shares_per_wallet {
+            if holding.held > max_fraction_shares_per_wallet {
+                return Err(MetaplexError::FractionWalletCapExceeded.into());
+            }
+        }
+
+        supply = supply
+            .checked_add(allocation.amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        if let Some(max_fraction_supply) = max_fraction_supply {
+            if supply > max_fraction_supply {
+                return Err(MetaplexError::FractionSupplyCapExceeded.into());
+            }
+        }
+
+        spl_token_mint_to(
+            fraction_mint_info.clone(),
+            token_account_info.clone(),
+            allocation.amount,
+            fraction_manager_info.clone(),
+            authority_seeds,
+            token_program_info.clone(),
+        )?;
+
+        minted.recipients_minted += 1;
+        minted.amount_minted = minted
+            .amount_minted
+            .checked_add(allocation.amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+    }
+
+    fraction_manager.add_fractions_minted(minted.amount_minted)?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    set_return_data(&minted.try_to_vec()?);
+
+    Ok(())
+}
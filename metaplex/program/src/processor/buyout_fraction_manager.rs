@@ -0,0 +1,361 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FractionCreatorProceeds, FractionCreatorSnapshot, FractionManager,
+            FractionManagerStatus, FractionManagerV1, Key, Store, CREATORS,
+            MAX_FRACTION_CREATOR_PROCEEDS_SIZE, PREFIX, PROCEEDS,
+        },
+        utils::{
+            assert_creator_priority_window, assert_derivation,
+            assert_fraction_redemption_not_paused, assert_initialized, assert_owned_by,
+            assert_price_within_bounds, assert_redemption_price_floor, assert_signer,
+            combine_vault, create_or_allocate_account_raw, load_fraction_store_config,
+            spl_token_transfer, split_buyout_proceeds,
+        },
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    mpl_token_vault::state::{ExternalPriceAccount, Vault},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_token::state::{Account, Mint},
+    std::convert::TryInto,
+};
+
+/// Credits each creator verified in the snapshot its share of the royalties on `proceeds`,
+/// taking their proceeds accounts from `account_info_iter` in snapshot order and creating each
+/// on its first credit. Royalties round down, so dust stays with holders. Returns the total
+/// credited.
+#[allow(clippy::too_many_arguments)]
+pub fn credit_creator_royalties<'a>(
+    program_id: &Pubkey,
+    fraction_manager: &mut dyn FractionManager,
+    fraction_manager_info: &AccountInfo<'a>,
+    creator_snapshot: &FractionCreatorSnapshot,
+    seller_fee_basis_points: u16,
+    proceeds: u64,
+    account_info_iter: &mut std::slice::Iter<AccountInfo<'a>>,
+    payer_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+) -> Result<u64, ProgramError> {
+    let royalties = (proceeds as u128)
+        .checked_mul(seller_fee_basis_points as u128)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_div(10000)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    let mut total_credited: u64 = 0;
+    for creator in creator_snapshot.creators.iter().filter(|c| c.verified) {
+        let creator_proceeds_info = next_account_info(account_info_iter)?;
+        let proceeds_bump = assert_derivation(
+            program_id,
+            creator_proceeds_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager_info.key.as_ref(),
+                creator.address.as_ref(),
+                PROCEEDS.as_bytes(),
+            ],
+        )?;
+
+        if creator_proceeds_info.data_is_empty() {
+            create_or_allocate_account_raw(
+                *program_id,
+                creator_proceeds_info,
+                rent_info,
+                system_info,
+                payer_info,
+                MAX_FRACTION_CREATOR_PROCEEDS_SIZE,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    fraction_manager_info.key.as_ref(),
+                    creator.address.as_ref(),
+                    PROCEEDS.as_bytes(),
+                    &[proceeds_bump],
+                ],
+            )?;
+        } else {
+            assert_owned_by(creator_proceeds_info, program_id)?;
+        }
+
+        let amount: u64 = royalties
+            .checked_mul(creator.share as u128)
+            .ok_or(MetaplexError::NumericalOverflowError)?
+            .checked_div(100)
+            .ok_or(MetaplexError::NumericalOverflowError)?
+            .try_into()
+            .map_err(|_| MetaplexError::NumericalOverflowError)?;
+
+        let mut creator_proceeds =
+            FractionCreatorProceeds::from_account_info_or_uninitialized(creator_proceeds_info)?;
+        if creator_proceeds.key == Key::Uninitialized {
+            fraction_manager.add_open_record()?;
+        }
+        creator_proceeds.key = Key::FractionCreatorProceedsV1;
+        creator_proceeds.fraction_manager = *fraction_manager_info.key;
+        creator_proceeds.creator = creator.address;
+        creator_proceeds.total_owed = creator_proceeds
+            .total_owed
+            .checked_add(amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        creator_proceeds.serialize(&mut *creator_proceeds_info.data.borrow_mut())?;
+
+        total_credited = total_credited
+            .checked_add(amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+    }
+
+    Ok(total_credited)
+}
+
+pub fn process_buyout_fraction_manager<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let external_price_account_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let buyer_token_account_info = next_account_info(account_info_iter)?;
+    let buyer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let fraction_store_config_info = next_account_info(account_info_iter)?;
+    let store_fee_account_info = next_account_info(account_info_iter)?;
+    let store_admin_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let token_vault_program_info = next_account_info(account_info_iter)?;
+    let buyer_vault_shares_info = next_account_info(account_info_iter)?;
+    let vault_fraction_mint_info = next_account_info(account_info_iter)?;
+    let vault_fraction_treasury_info = next_account_info(account_info_iter)?;
+    let vault_redeem_treasury_info = next_account_info(account_info_iter)?;
+    let vault_burn_authority_info = next_account_info(account_info_iter)?;
+
+    assert_signer(buyer_info)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    if *token_vault_program_info.key != store.token_vault_program {
+        return Err(MetaplexError::AuctionManagerTokenVaultProgramMismatch.into());
+    }
+
+    if fraction_manager.status() != FractionManagerStatus::Redeemable {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    assert_fraction_redemption_not_paused(&fraction_manager)?;
+
+    // Creators need only be given when they get priority or royalties
+    let creator_snapshot = if fraction_manager.creator_priority_until().is_some()
+        || fraction_manager.creators_snapshotted()
+    {
+        let creator_snapshot_info = next_account_info(account_info_iter)?;
+        assert_owned_by(creator_snapshot_info, program_id)?;
+        let creator_snapshot = FractionCreatorSnapshot::from_account_info(creator_snapshot_info)?;
+        assert_derivation(
+            program_id,
+            creator_snapshot_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager_info.key.as_ref(),
+                creator_snapshot.metadata.as_ref(),
+                CREATORS.as_bytes(),
+            ],
+        )?;
+        assert_creator_priority_window(
+            &fraction_manager,
+            &creator_snapshot,
+            buyer_info.key,
+            clock_info,
+        )?;
+        Some(creator_snapshot)
+    } else {
+        None
+    };
+
+    if fraction_manager.vault() != *vault_info.key {
+        return Err(MetaplexError::FractionManagerVaultMismatch.into());
+    }
+    assert_owned_by(vault_info, &store.token_vault_program)?;
+    let vault = Vault::from_account_info(vault_info)?;
+
+    if vault.authority != *fraction_manager_info.key {
+        return Err(MetaplexError::FractionVaultAuthorityMismatch.into());
+    }
+
+    // Shares of the vault's own mint are redeemed through the vault, not accept payment
+    if fraction_manager.state.fraction_mint.is_none() {
+        return Err(MetaplexError::BuyoutRequiresManagerFractionMint.into());
+    }
+    if fraction_manager.fraction_mint(&vault) != *fraction_mint_info.key {
+        return Err(MetaplexError::FractionMintMismatch.into());
+    }
+    let fraction_mint: Mint = assert_initialized(fraction_mint_info)?;
+
+    if fraction_mint.supply == 0 {
+        return Err(MetaplexError::NoFractionShares.into());
+    }
+
+    if fraction_manager.external_pricing() != *external_price_account_info.key {
+        return Err(MetaplexError::PricingLookupMismatch.into());
+    }
+    let external_price_account =
+        ExternalPriceAccount::from_account_info(external_price_account_info)?;
+
+    if fraction_manager.accept_payment() != *accept_payment_info.key {
+        return Err(MetaplexError::AcceptPaymentMismatch.into());
+    }
+
+    // Every outstanding share is bought at the same price, fixed at activation if it was
+    let price_per_share = match fraction_manager.fixed_price_per_share() {
+        Some(fixed_price_per_share) => fixed_price_per_share,
+        None => {
+            assert_price_within_bounds(&fraction_manager, &external_price_account)?;
+            external_price_account.price_per_share
+        }
+    };
+    assert_redemption_price_floor(&fraction_manager, price_per_share)?;
+    let amount = price_per_share
+        .checked_mul(fraction_mint.supply)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    // The store's cut comes off the top, before anything reaches holders
+    let fraction_store_config =
+        load_fraction_store_config(program_id, store_info.key, fraction_store_config_info)?;
+    let (fee, remaining) = split_buyout_proceeds(&fraction_store_config, amount)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_admin_info.key.as_ref(),
+        ],
+    )?;
+    let store_fee_account: Account = assert_initialized(store_fee_account_info)?;
+    if store_fee_account.owner != *store_admin_info.key
+        || store_fee_account.mint != fraction_manager.token_mint
+    {
+        return Err(MetaplexError::StoreFeeAccountMismatch.into());
+    }
+
+    if fee > 0 {
+        spl_token_transfer(
+            buyer_token_account_info.clone(),
+            store_fee_account_info.clone(),
+            fee,
+            buyer_info.clone(),
+            &[],
+            token_program_info.clone(),
+        )?;
+    }
+
+    spl_token_transfer(
+        buyer_token_account_info.clone(),
+        accept_payment_info.clone(),
+        remaining,
+        buyer_info.clone(),
+        &[],
+        token_program_info.clone(),
+    )?;
+
+    // Royalties stay in accept payment for creators to withdraw as they vest
+    let royalties = match &creator_snapshot {
+        Some(creator_snapshot) => {
+            let metadata_info = next_account_info(account_info_iter)?;
+            let payer_info = next_account_info(account_info_iter)?;
+            let system_info = next_account_info(account_info_iter)?;
+            let rent_info = next_account_info(account_info_iter)?;
+
+            assert_signer(payer_info)?;
+            if creator_snapshot.metadata != *metadata_info.key {
+                return Err(MetaplexError::CreatorSnapshotMetadataMismatch.into());
+            }
+            assert_owned_by(metadata_info, &store.token_metadata_program)?;
+            let metadata = Metadata::from_account_info(metadata_info)?;
+            credit_creator_royalties(
+                program_id,
+                &mut fraction_manager,
+                fraction_manager_info,
+                creator_snapshot,
+                metadata.data.seller_fee_basis_points,
+                remaining,
+                account_info_iter,
+                payer_info,
+                rent_info,
+                system_info,
+            )?
+        }
+        None => 0,
+    };
+
+    // What's left of accept payment beyond the royalties is the holders', share for share
+    let holder_proceeds = remaining
+        .checked_sub(royalties)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    fraction_manager.record_buyout(holder_proceeds, fraction_mint.supply);
+
+    let bump_seed = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_info.key.as_ref()],
+    )?;
+    let authority_seeds = &[PREFIX.as_bytes(), vault_info.key.as_ref(), &[bump_seed]];
+
+    // The buyer takes the vault, and with it the asset in its boxes
+    combine_vault(
+        token_vault_program_info.clone(),
+        vault_info.clone(),
+        buyer_vault_shares_info.clone(),
+        buyer_token_account_info.clone(),
+        vault_fraction_mint_info.clone(),
+        vault_fraction_treasury_info.clone(),
+        vault_redeem_treasury_info.clone(),
+        buyer_info.clone(),
+        fraction_manager_info.clone(),
+        buyer_info.clone(),
+        vault_burn_authority_info.clone(),
+        external_price_account_info.clone(),
+        token_program_info.clone(),
+        authority_seeds,
+    )?;
+
+    fraction_manager.set_status(FractionManagerStatus::Combined);
+    fraction_manager.save(fraction_manager_info)?;
+
+    msg!(
+        "Fraction manager {} bought out by {} for {}, {} of it to the store and {} to creators",
+        fraction_manager_info.key,
+        buyer_info.key,
+        amount,
+        fee,
+        royalties
+    );
+
+    Ok(())
+}
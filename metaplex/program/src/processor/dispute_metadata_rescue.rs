@@ -0,0 +1,67 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerV1, FractionMetadataRescue},
+        utils::{assert_authority_correct, assert_owned_by, close_program_account},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub fn process_dispute_metadata_rescue<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let rescue_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(rescue_info, program_id)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let rescue = FractionMetadataRescue::from_account_info(rescue_info)?;
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    if rescue.fraction_manager != *fraction_manager_info.key {
+        return Err(MetaplexError::NotAFractionManagerRecord.into());
+    }
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if rescue.admin != *admin_wallet_info.key {
+        return Err(MetaplexError::RescueAdminMismatch.into());
+    }
+
+    if rescue.executed_at.is_some() {
+        return Err(MetaplexError::RescueAlreadyExecuted.into());
+    }
+
+    let clock = Clock::from_account_info(clock_info)?;
+    if clock.unix_timestamp >= rescue.dispute_window_ends()? {
+        return Err(MetaplexError::RescueDisputeWindowClosed.into());
+    }
+
+    msg!(
+        "Metadata rescue of {} to {} disputed by fraction manager authority {}",
+        rescue.metadata,
+        rescue.claimed_original_authority,
+        authority_info.key
+    );
+
+    // A disputed claim is dropped rather than held, so the admin can request a corrected one
+    close_program_account(rescue_info, admin_wallet_info)?;
+    fraction_manager.close_open_record()?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,303 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FractionCreatorProceeds, FractionCreatorSnapshot, FractionManager,
+            FractionManagerClosed, FractionManagerStatus, FractionManagerV1,
+            FractionMetadataRescue, FractionOriginalAuthorityLookup, FractionSafetyDepositConfig,
+            Key, Store, PREFIX,
+        },
+        utils::{
+            assert_authority_correct, assert_derivation, assert_initialized, assert_owned_by,
+            close_program_account, refund_prepaid_teardown_rent, spl_token_close_account,
+            spl_token_transfer, transfer_metadata_ownership, transfer_vault_authority,
+        },
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    mpl_token_vault::state::Vault,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        log::sol_log_data,
+        program::set_return_data,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+/// Checks a dependent account belongs to the manager being torn down and is settled, handing the
+/// metadata authority back first when the account is an original authority lookup.
+fn settle_record<'a>(
+    program_id: &Pubkey,
+    record_info: &AccountInfo<'a>,
+    fraction_manager_info: &AccountInfo<'a>,
+    fraction_manager: &FractionManagerV1,
+    token_metadata_program_info: &AccountInfo<'a>,
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'a>>,
+    authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    assert_owned_by(record_info, program_id)?;
+
+    let key = record_info.data.borrow().first().copied();
+    let owner = match key {
+        Some(k)
+            if k == Key::FractionSafetyDepositConfigV1 as u8
+                || k == Key::FractionSafetyDepositConfigPreallocatedV1 as u8 =>
+        {
+            FractionSafetyDepositConfig::get_fraction_manager(record_info)
+        }
+        Some(k) if k == Key::FractionCreatorSnapshotV1 as u8 => {
+            FractionCreatorSnapshot::from_account_info(record_info)?.fraction_manager
+        }
+        Some(k) if k == Key::FractionCreatorProceedsV1 as u8 => {
+            let proceeds = FractionCreatorProceeds::from_account_info(record_info)?;
+            if proceeds.withdrawn < proceeds.total_owed {
+                return Err(MetaplexError::RoyaltiesOutstanding.into());
+            }
+            proceeds.fraction_manager
+        }
+        Some(k) if k == Key::FractionMetadataRescueV1 as u8 => {
+            let rescue = FractionMetadataRescue::from_account_info(record_info)?;
+            if rescue.executed_at.is_none() {
+                return Err(MetaplexError::RescuePending.into());
+            }
+            rescue.fraction_manager
+        }
+        Some(k) if k == Key::FractionOriginalAuthorityLookupV1 as u8 => {
+            let metadata_info = next_account_info(account_info_iter)?;
+            let return_authority_info = next_account_info(account_info_iter)?;
+            return return_metadata_authority(
+                program_id,
+                record_info,
+                metadata_info,
+                return_authority_info,
+                fraction_manager_info,
+                fraction_manager,
+                token_metadata_program_info,
+                authority_seeds,
+            );
+        }
+        _ => return Err(MetaplexError::NotAFractionManagerRecord.into()),
+    };
+
+    if owner != *fraction_manager_info.key {
+        return Err(MetaplexError::NotAFractionManagerRecord.into());
+    }
+
+    Ok(())
+}
+
+/// Hands a metadata held by the manager back to the lookup's return authority. A lookup keyed to
+/// another vault or metadata is refused, so one manager can't release metadata held by another.
+#[allow(clippy::too_many_arguments)]
+fn return_metadata_authority<'a>(
+    program_id: &Pubkey,
+    original_authority_lookup_info: &AccountInfo<'a>,
+    metadata_info: &AccountInfo<'a>,
+    return_authority_info: &AccountInfo<'a>,
+    fraction_manager_info: &AccountInfo<'a>,
+    fraction_manager: &FractionManagerV1,
+    token_metadata_program_info: &AccountInfo<'a>,
+    authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    let vault_key = fraction_manager.vault();
+    let (expected_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            vault_key.as_ref(),
+            metadata_info.key.as_ref(),
+        ],
+        program_id,
+    );
+    if expected_key != *original_authority_lookup_info.key {
+        return Err(MetaplexError::FractionOriginalAuthorityLookupKeyMismatch.into());
+    }
+
+    let original_authority_lookup =
+        FractionOriginalAuthorityLookup::from_account_info(original_authority_lookup_info)?;
+    if original_authority_lookup.return_authority() != *return_authority_info.key {
+        return Err(MetaplexError::OriginalAuthorityMismatch.into());
+    }
+
+    // A pda authority never handed the metadata over, so there is nothing to give back
+    if original_authority_lookup.pda_authority {
+        return Ok(());
+    }
+
+    assert_owned_by(metadata_info, token_metadata_program_info.key)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.update_authority == *fraction_manager_info.key {
+        transfer_metadata_ownership(
+            token_metadata_program_info.clone(),
+            metadata_info.clone(),
+            fraction_manager_info.clone(),
+            return_authority_info.clone(),
+            authority_seeds,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn process_finalize_fraction_manager_teardown<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let recipient_info = next_account_info(account_info_iter)?;
+    let teardown_rent_payer_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let leftover_destination_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_vault_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    // Only a manager whose shares were bought out back into the asset has nothing left to hold
+    if fraction_manager.status() != FractionManagerStatus::Combined {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    if fraction_manager.holder_proceeds_outstanding() {
+        return Err(MetaplexError::HolderProceedsOutstanding.into());
+    }
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+    if *token_vault_program_info.key != store.token_vault_program {
+        return Err(MetaplexError::AuctionManagerTokenVaultProgramMismatch.into());
+    }
+    if *token_metadata_program_info.key != store.token_metadata_program {
+        return Err(MetaplexError::AuctionManagerTokenMetadataProgramMismatch.into());
+    }
+    if fraction_manager.vault() != *vault_info.key {
+        return Err(MetaplexError::FractionManagerVaultMismatch.into());
+    }
+    if fraction_manager.accept_payment() != *accept_payment_info.key {
+        return Err(MetaplexError::AcceptPaymentMismatch.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_info.key.as_ref()],
+    )?;
+    let authority_seeds = &[PREFIX.as_bytes(), vault_info.key.as_ref(), &[bump_seed]];
+
+    // Authorities go back before anything is closed, while the manager can still sign for them
+    assert_owned_by(vault_info, &store.token_vault_program)?;
+    let vault = Vault::from_account_info(vault_info)?;
+    if vault.authority == *fraction_manager_info.key {
+        transfer_vault_authority(
+            token_vault_program_info.clone(),
+            vault_info.clone(),
+            fraction_manager_info.clone(),
+            authority_info.clone(),
+            authority_seeds,
+        )?;
+    }
+
+    let mut accounts_closed: u64 = 0;
+    let mut dependent_rent_reclaimed: u64 = 0;
+
+    // Every record was counted open as it was created, so closing them all brings the count to zero
+    while let Some(record_info) = account_info_iter.next() {
+        settle_record(
+            program_id,
+            record_info,
+            fraction_manager_info,
+            &fraction_manager,
+            token_metadata_program_info,
+            account_info_iter,
+            authority_seeds,
+        )?;
+
+        let rent = close_program_account(record_info, recipient_info)?;
+        fraction_manager.close_open_record()?;
+        fraction_manager.add_rent_reclaimed(rent)?;
+        accounts_closed = accounts_closed
+            .checked_add(1)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        dependent_rent_reclaimed = dependent_rent_reclaimed
+            .checked_add(rent)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+    }
+
+    if fraction_manager.open_records() != 0 {
+        return Err(MetaplexError::FractionRecordsStillOpen.into());
+    }
+
+    // Anything still in accept payment is dust nobody is owed, swept before the account closes
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+    if accept_payment.amount > 0 {
+        spl_token_transfer(
+            accept_payment_info.clone(),
+            leftover_destination_info.clone(),
+            accept_payment.amount,
+            fraction_manager_info.clone(),
+            authority_seeds,
+            token_program_info.clone(),
+        )?;
+    }
+    let accept_payment_rent = accept_payment_info.lamports();
+    spl_token_close_account(
+        accept_payment_info.clone(),
+        recipient_info.clone(),
+        fraction_manager_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+    fraction_manager.add_rent_reclaimed(accept_payment_rent)?;
+    accounts_closed = accounts_closed
+        .checked_add(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    dependent_rent_reclaimed = dependent_rent_reclaimed
+        .checked_add(accept_payment_rent)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    fraction_manager.set_status(FractionManagerStatus::Closed);
+
+    refund_prepaid_teardown_rent(
+        &mut fraction_manager,
+        fraction_manager_info,
+        teardown_rent_payer_info,
+    )?;
+
+    // Whatever is left once the payer's deposit is back with them
+    let manager_rent = fraction_manager_info.lamports();
+    fraction_manager.add_rent_reclaimed(manager_rent)?;
+
+    let closed = FractionManagerClosed {
+        manager: *fraction_manager_info.key,
+        vault: fraction_manager.vault(),
+        total_rent_reclaimed: fraction_manager.total_rent_reclaimed(),
+        total_fractions_ever_minted: fraction_manager.total_fractions_minted(),
+        accounts_closed,
+        dependent_rent_reclaimed,
+    };
+
+    close_program_account(fraction_manager_info, recipient_info)?;
+
+    let event = closed.try_to_vec()?;
+    sol_log_data(&[&event]);
+    set_return_data(&event);
+
+    Ok(())
+}
@@ -2,21 +2,26 @@ use {
     crate::{
         error::MetaplexError,
         state::{
-            FractionManagerStatus, FractionManagerV1, Key, Store, MAX_FRACTION_MANAGER_SIZE, PREFIX,
+            FractionManagerStatus, FractionManagerV1, FractionStoreConfig, FractionVaultClaim, Key,
+            Store, CLAIM, MAX_FRACTION_MANAGER_SIZE, MAX_FRACTION_VAULT_CLAIM_SIZE, PREFIX,
         },
         utils::{
-            assert_derivation, assert_initialized, assert_owned_by, create_or_allocate_account_raw,
+            assert_derivation, assert_initialized, assert_owned_by, assert_pricing_lookup_matches,
+            create_or_allocate_account_raw, load_fraction_store_config,
         },
     },
     borsh::BorshSerialize,
+    mpl_auction::processor::{AuctionData, AuctionState},
     mpl_token_vault::state::{ExternalPriceAccount, Vault, VaultState},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
+        program::invoke,
         program_error::ProgramError,
         program_option::COption,
         pubkey::Pubkey,
+        system_instruction,
     },
     spl_token::state::Account,
 };
@@ -82,6 +87,8 @@ pub fn assert_common_checks(
         return Err(MetaplexError::FractionManagerPriceAccountMintMismatch.into());
     }
 
+    assert_pricing_lookup_matches(&vault, external_price_account_info.key)?;
+
     if accept_payment.owner != *fraction_manager_info.key {
         return Err(MetaplexError::FractionAcceptPaymentOwnerMismatch.into());
     }
@@ -105,10 +112,109 @@ pub fn assert_common_checks(
     Ok((bump_seed, vault))
 }
 
+/// Rejects a vault that is already up for auction. The auction program derives its auction
+/// from the resource, so an auction that is not ended at that address conflicts.
+pub fn assert_vault_not_in_active_auction(
+    store: &Store,
+    vault_info: &AccountInfo,
+    auction_info: &AccountInfo,
+) -> ProgramResult {
+    assert_derivation(
+        &store.auction_program,
+        auction_info,
+        &[
+            mpl_auction::PREFIX.as_bytes(),
+            store.auction_program.as_ref(),
+            vault_info.key.as_ref(),
+        ],
+    )?;
+
+    // The vault was never auctioned
+    if auction_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(auction_info, &store.auction_program)?;
+
+    if AuctionData::get_state(auction_info)? != AuctionState::Ended {
+        return Err(MetaplexError::VaultInActiveAuction.into());
+    }
+
+    Ok(())
+}
+
+/// Counts a new fraction manager against the vault's lifetime allowance, creating the
+/// vault claim on the vault's first manager.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_vault_generation<'a>(
+    program_id: &Pubkey,
+    vault_info: &AccountInfo<'a>,
+    vault_claim_info: &AccountInfo<'a>,
+    fraction_store_config: &FractionStoreConfig,
+    payer_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let claim_bump = assert_derivation(
+        program_id,
+        vault_claim_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault_info.key.as_ref(),
+            CLAIM.as_bytes(),
+        ],
+    )?;
+
+    if vault_claim_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            vault_claim_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_FRACTION_VAULT_CLAIM_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                vault_info.key.as_ref(),
+                CLAIM.as_bytes(),
+                &[claim_bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(vault_claim_info, program_id)?;
+    }
+
+    let mut vault_claim = FractionVaultClaim::from_account_info_or_uninitialized(vault_claim_info)?;
+
+    if let Some(max_manager_generations) = fraction_store_config.max_manager_generations {
+        if vault_claim.manager_generations >= max_manager_generations {
+            return Err(MetaplexError::VaultFractionalizationLimitReached.into());
+        }
+    }
+
+    vault_claim.key = Key::FractionVaultClaimV1;
+    vault_claim.vault = *vault_info.key;
+    vault_claim.manager_generations = vault_claim
+        .manager_generations
+        .checked_add(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    vault_claim.serialize(&mut *vault_claim_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
 pub fn process_init_fraction_manager(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     orderbook_market_pool_size: u64,
+    creator_priority_until: Option<i64>,
+    min_redemption_price_per_share: u64,
+    prepaid_teardown_rent: u64,
+    check_vault_auction: bool,
+    fraction_mint: Option<Pubkey>,
 ) -> ProgramResult {
     msg!("DEBUG WORKS!");
     let account_info_iter = &mut accounts.iter();
@@ -123,8 +229,10 @@ pub fn process_init_fraction_manager(
     let store_info = next_account_info(account_info_iter)?;
     let system_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let vault_claim_info = next_account_info(account_info_iter)?;
+    let fraction_store_config_info = next_account_info(account_info_iter)?;
 
-    let (bump_seed, _vault) = assert_common_checks(
+    let (bump_seed, vault) = assert_common_checks(
         program_id,
         fraction_manager_info,
         vault_info,
@@ -135,11 +243,32 @@ pub fn process_init_fraction_manager(
         authority_info,
     )?;
 
-    let authority_seeds = &[
-        PREFIX.as_bytes(),
-        &fraction_manager_info.key.as_ref(),
-        &[bump_seed],
-    ];
+    if check_vault_auction {
+        let auction_info = next_account_info(account_info_iter)?;
+        let store = Store::from_account_info(store_info)?;
+        assert_vault_not_in_active_auction(&store, vault_info, auction_info)?;
+    }
+
+    let fraction_store_config =
+        load_fraction_store_config(program_id, store_info.key, fraction_store_config_info)?;
+
+    claim_vault_generation(
+        program_id,
+        vault_info,
+        vault_claim_info,
+        &fraction_store_config,
+        payer_info,
+        rent_info,
+        system_info,
+    )?;
+
+    if let Some(teardown_rent_deposit) = fraction_store_config.teardown_rent_deposit {
+        if prepaid_teardown_rent < teardown_rent_deposit {
+            return Err(MetaplexError::InsufficientTeardownRentPrepay.into());
+        }
+    }
+
+    let authority_seeds = &[PREFIX.as_bytes(), &vault_info.key.as_ref(), &[bump_seed]];
 
     create_or_allocate_account_raw(
         *program_id,
@@ -151,7 +280,24 @@ pub fn process_init_fraction_manager(
         authority_seeds,
     )?;
 
-    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    // On top of the manager's own rent, so it stays rent exempt until teardown refunds it
+    if prepaid_teardown_rent > 0 {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                fraction_manager_info.key,
+                prepaid_teardown_rent,
+            ),
+            &[
+                payer_info.clone(),
+                fraction_manager_info.clone(),
+                system_info.clone(),
+            ],
+        )?;
+    }
+
+    let mut fraction_manager =
+        FractionManagerV1::from_account_info_or_uninitialized(fraction_manager_info)?;
 
     fraction_manager.key = Key::FractionManagerV1;
     fraction_manager.store = *store_info.key;
@@ -164,8 +310,18 @@ pub fn process_init_fraction_manager(
     // todo - and set has participation to true if does have participation
     fraction_manager.state.token_pools_active = 0;
     fraction_manager.state.has_participation = false;
+    fraction_manager.state.redemption_paused = false;
+    fraction_manager.state.creator_priority_until = creator_priority_until;
+    fraction_manager.state.vault_token_type_count = vault.token_type_count;
+    fraction_manager.state.min_price = None;
+    fraction_manager.state.max_price = None;
+    fraction_manager.state.min_redemption_price_per_share = min_redemption_price_per_share;
+    fraction_manager.state.prepaid_teardown_rent = prepaid_teardown_rent;
+    fraction_manager.state.teardown_rent_payer = *payer_info.key;
+    fraction_manager.state.fraction_mint = fraction_mint;
 
     fraction_manager.token_mint = *token_mint_info.key;
+    fraction_manager.external_pricing = *external_price_account_info.key;
 
     fraction_manager.serialize(&mut *fraction_manager_info.data.borrow_mut())?;
 
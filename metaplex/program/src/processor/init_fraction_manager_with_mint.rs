@@ -0,0 +1,119 @@
+use {
+    crate::{
+        error::MetaplexError,
+        processor::init_fraction_manager::{
+            assert_vault_not_in_active_auction, process_init_fraction_manager,
+        },
+        state::{Store, FRACTION_MINT, PREFIX},
+        utils::{assert_derivation, create_or_allocate_account_raw},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_token::state::Mint,
+};
+
+pub fn process_init_fraction_manager_with_mint<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    orderbook_market_pool_size: u64,
+    creator_priority_until: Option<i64>,
+    min_redemption_price_per_share: u64,
+    prepaid_teardown_rent: u64,
+    decimals: u8,
+    check_vault_auction: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let _token_mint_info = next_account_info(account_info_iter)?;
+    let _external_price_account_info = next_account_info(account_info_iter)?;
+    let _authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _accept_payment_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let _vault_claim_info = next_account_info(account_info_iter)?;
+    let _fraction_store_config_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    if check_vault_auction {
+        let auction_info = next_account_info(account_info_iter)?;
+        let store = Store::from_account_info(store_info)?;
+        assert_vault_not_in_active_auction(&store, vault_info, auction_info)?;
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        fraction_mint_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            FRACTION_MINT.as_bytes(),
+        ],
+    )?;
+
+    // Only a never-created address is accepted, so no one can slip in a mint they control.
+    if !fraction_mint_info.data_is_empty() || *fraction_mint_info.owner != system_program::id() {
+        return Err(MetaplexError::FractionMintAlreadyExists.into());
+    }
+
+    // Set the manager up first so every init check passes before any lamports move to the mint.
+    process_init_fraction_manager(
+        program_id,
+        accounts,
+        orderbook_market_pool_size,
+        creator_priority_until,
+        min_redemption_price_per_share,
+        prepaid_teardown_rent,
+        false,
+        Some(*fraction_mint_info.key),
+    )?;
+
+    let store = Store::from_account_info(store_info)?;
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    create_or_allocate_account_raw(
+        store.token_program,
+        fraction_mint_info,
+        rent_info,
+        system_info,
+        payer_info,
+        Mint::LEN,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            FRACTION_MINT.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    invoke(
+        &spl_token::instruction::initialize_mint(
+            token_program_info.key,
+            fraction_mint_info.key,
+            fraction_manager_info.key,
+            Some(fraction_manager_info.key),
+            decimals,
+        )?,
+        &[
+            fraction_mint_info.clone(),
+            rent_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerLegacyV1, MAX_FRACTION_MANAGER_SIZE, PREFIX},
+        utils::{assert_authority_correct, assert_derivation, assert_owned_by, assert_signer},
+    },
+    mpl_token_vault::state::Vault,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        pubkey::Pubkey,
+        rent::Rent,
+        system_instruction,
+        sysvar::Sysvar,
+    },
+};
+
+pub fn process_migrate_fraction_manager<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_signer(payer_info)?;
+
+    let legacy = FractionManagerLegacyV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&legacy.authority, authority_info)?;
+
+    if legacy.vault != *vault_info.key {
+        return Err(MetaplexError::FractionManagerVaultMismatch.into());
+    }
+    assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_info.key.as_ref()],
+    )?;
+
+    let vault = Vault::from_account_info(vault_info)?;
+    let fraction_manager = legacy.migrate(&vault, payer_info.key);
+
+    // The payer covers the rent of the added space, so the manager stays rent exempt
+    let rent = Rent::from_account_info(rent_info)?;
+    let required_lamports = rent
+        .minimum_balance(MAX_FRACTION_MANAGER_SIZE)
+        .saturating_sub(fraction_manager_info.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                fraction_manager_info.key,
+                required_lamports,
+            ),
+            &[
+                payer_info.clone(),
+                fraction_manager_info.clone(),
+                system_info.clone(),
+            ],
+        )?;
+    }
+
+    fraction_manager_info.realloc(MAX_FRACTION_MANAGER_SIZE, false)?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    msg!(
+        "Fraction manager {} migrated to the current layout",
+        fraction_manager_info.key
+    );
+
+    Ok(())
+}
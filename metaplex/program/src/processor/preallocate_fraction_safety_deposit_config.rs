@@ -0,0 +1,117 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FractionManager, FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig,
+            FractionWinningConfigType, Key, Store, PREFIX,
+        },
+        utils::{
+            assert_authority_correct, assert_derivation, assert_owned_by, assert_rent_exempt,
+            assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    mpl_token_vault::state::SafetyDepositBox,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::{rent::Rent, Sysvar},
+    },
+};
+
+pub fn process_preallocate_fraction_safety_deposit_config<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    fraction_winning_config_type: FractionWinningConfigType,
+    metadata_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let safety_deposit_config_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    if fraction_manager.status() != FractionManagerStatus::Initialized {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    assert_owned_by(safety_deposit_info, &store.token_vault_program)?;
+    let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+    if safety_deposit.vault != fraction_manager.vault() {
+        return Err(MetaplexError::SafetyDepositBoxVaultMismatch.into());
+    }
+
+    // Pre-allocated or validated, a config that has been written is never pre-allocated again
+    if !safety_deposit_config_info.data_is_empty() {
+        assert_owned_by(safety_deposit_config_info, program_id)?;
+        if safety_deposit_config_info.data.borrow()[0] != Key::Uninitialized as u8 {
+            return Err(MetaplexError::AlreadyInitialized.into());
+        }
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        safety_deposit_config_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            safety_deposit_info.key.as_ref(),
+        ],
+    )?;
+
+    let safety_deposit_config = FractionSafetyDepositConfig {
+        key: Key::FractionSafetyDepositConfigPreallocatedV1,
+        fraction_manager: *fraction_manager_info.key,
+        order: safety_deposit.order as u64,
+        fraction_winning_config_type,
+        fixed_price: None,
+        immutable: false,
+        metadata_authority,
+    };
+
+    if safety_deposit_config_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            safety_deposit_config_info,
+            rent_info,
+            system_info,
+            payer_info,
+            safety_deposit_config.created_size(),
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager_info.key.as_ref(),
+                safety_deposit_info.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    let rent = &Rent::from_account_info(rent_info)?;
+    assert_rent_exempt(rent, safety_deposit_config_info)
+        .map_err(|_| MetaplexError::ConfigNotRentExempt)?;
+
+    safety_deposit_config.preallocate(safety_deposit_config_info, fraction_manager_info.key)?;
+
+    fraction_manager.add_open_record()?;
+    fraction_manager.save(fraction_manager_info)
+}
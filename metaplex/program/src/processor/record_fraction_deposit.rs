@@ -0,0 +1,103 @@
+use {
+    crate::{
+        error::MetaplexError,
+        processor::validate_fraction_safety_deposit_box::assert_depositor_owns_nft,
+        state::{
+            FractionDepositRecord, Key, Store, DEPOSIT, MAX_FRACTION_DEPOSIT_RECORD_SIZE, PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    mpl_token_vault::state::{Vault, VaultState},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_record_fraction_deposit<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let deposit_record_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let depositor_info = next_account_info(account_info_iter)?;
+    let depositor_token_account_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let store = Store::from_account_info(store_info)?;
+    assert_owned_by(vault_info, &store.token_vault_program)?;
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+
+    let vault = Vault::from_account_info(vault_info)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    // Boxes are only added while the vault is inactive, so the NFT cannot be in it yet.
+    if vault.state != VaultState::Inactive {
+        return Err(MetaplexError::DepositRecordedTooLate.into());
+    }
+
+    assert_depositor_owns_nft(
+        &store,
+        &metadata,
+        depositor_info,
+        depositor_token_account_info,
+    )?;
+
+    let record_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        vault_info.key.as_ref(),
+        metadata.mint.as_ref(),
+        DEPOSIT.as_bytes(),
+    ];
+    let record_bump = assert_derivation(program_id, deposit_record_info, record_seeds)?;
+
+    if deposit_record_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            deposit_record_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_FRACTION_DEPOSIT_RECORD_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                vault_info.key.as_ref(),
+                metadata.mint.as_ref(),
+                DEPOSIT.as_bytes(),
+                &[record_bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(deposit_record_info, program_id)?;
+    }
+
+    let mut deposit_record =
+        FractionDepositRecord::from_account_info_or_uninitialized(deposit_record_info)?;
+    // Written once, so a later holder cannot claim the deposit as theirs
+    if deposit_record.key != Key::Uninitialized {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+    deposit_record.key = Key::FractionDepositRecordV1;
+    deposit_record.vault = *vault_info.key;
+    deposit_record.mint = metadata.mint;
+    deposit_record.depositor = *depositor_info.key;
+
+    deposit_record.serialize(&mut *deposit_record_info.data.borrow_mut())?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerStatus, FractionManagerV1, Store, PREFIX},
+        utils::{
+            assert_derivation, assert_fraction_redemption_not_paused, assert_initialized,
+            assert_owned_by, assert_signer, spl_token_burn, spl_token_transfer,
+        },
+    },
+    mpl_token_vault::state::Vault,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+pub fn process_redeem_fraction_shares<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let holder_shares_info = next_account_info(account_info_iter)?;
+    let holder_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(holder_info)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    // Holders are only paid once a buyout has put their proceeds in accept payment
+    if fraction_manager.status() != FractionManagerStatus::Combined {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    assert_fraction_redemption_not_paused(&fraction_manager)?;
+
+    if fraction_manager.vault() != *vault_info.key {
+        return Err(MetaplexError::FractionManagerVaultMismatch.into());
+    }
+    assert_owned_by(vault_info, &store.token_vault_program)?;
+    let vault = Vault::from_account_info(vault_info)?;
+
+    if fraction_manager.fraction_mint(&vault) != *fraction_mint_info.key {
+        return Err(MetaplexError::FractionMintMismatch.into());
+    }
+
+    if fraction_manager.accept_payment() != *accept_payment_info.key {
+        return Err(MetaplexError::AcceptPaymentMismatch.into());
+    }
+
+    let holder_shares: Account = assert_initialized(holder_shares_info)?;
+    if holder_shares.mint != *fraction_mint_info.key {
+        return Err(MetaplexError::FractionMintMismatch.into());
+    }
+    if holder_shares.amount == 0 {
+        return Err(MetaplexError::NoFractionShares.into());
+    }
+
+    let payout = fraction_manager.redeem_buyout_shares(holder_shares.amount)?;
+
+    spl_token_burn(
+        holder_shares_info.clone(),
+        fraction_mint_info.clone(),
+        holder_shares.amount,
+        holder_info.clone(),
+        &[],
+        token_program_info.clone(),
+    )?;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_info.key.as_ref()],
+    )?;
+    let authority_seeds = &[PREFIX.as_bytes(), vault_info.key.as_ref(), &[bump_seed]];
+
+    spl_token_transfer(
+        accept_payment_info.clone(),
+        destination_info.clone(),
+        payout,
+        fraction_manager_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+
+    fraction_manager.save(fraction_manager_info)?;
+
+    msg!(
+        "Redeemed {} fraction shares of {} for {}",
+        holder_shares.amount,
+        holder_info.key,
+        payout
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionCreatorDrift, FractionCreatorSnapshot, Store},
+        utils::assert_owned_by,
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::set_return_data,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_report_fraction_creator_drift<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creator_snapshot_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(creator_snapshot_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let creator_snapshot = FractionCreatorSnapshot::from_account_info(creator_snapshot_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+
+    if creator_snapshot.metadata != *metadata_info.key {
+        return Err(MetaplexError::CreatorSnapshotMetadataMismatch.into());
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    set_return_data(
+        &FractionCreatorDrift {
+            metadata: *metadata_info.key,
+            drifted: creator_snapshot.creators_drifted(&metadata),
+        }
+        .try_to_vec()?,
+    );
+
+    Ok(())
+}
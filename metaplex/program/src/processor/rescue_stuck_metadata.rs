@@ -0,0 +1,204 @@
+use {
+    crate::{
+        error::MetaplexError,
+        processor::validate_fraction_safety_deposit_box::assert_return_to_is_wallet,
+        state::{
+            FractionManager, FractionManagerStatus, FractionManagerV1, FractionMetadataRescue, Key,
+            Store, MAX_FRACTION_METADATA_RESCUE_SIZE, PREFIX, RESCUE,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            transfer_metadata_ownership,
+        },
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+pub fn process_rescue_stuck_metadata<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let rescue_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let original_authority_lookup_info = next_account_info(account_info_iter)?;
+    let claimed_original_authority_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    if !rescue_info.data_is_empty() {
+        assert_owned_by(rescue_info, program_id)?;
+    }
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    let store = Store::from_account_info(store_info)?;
+    if *token_metadata_program_info.key != store.token_metadata_program {
+        return Err(MetaplexError::AuctionManagerTokenMetadataProgramMismatch.into());
+    }
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    // Combined is where teardown would hand the authority back had the lookup survived. Before
+    // that the manager still needs the authority for the fractions it backs.
+    if fraction_manager.status() != FractionManagerStatus::Combined {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    let vault_key = fraction_manager.vault();
+    let fraction_manager_bump = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_key.as_ref()],
+    )?;
+
+    assert_owned_by(metadata_info, token_metadata_program_info.key)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.update_authority != *fraction_manager_info.key {
+        return Err(MetaplexError::MetadataNotHeldByFractionManager.into());
+    }
+
+    // Only a lost lookup justifies bypassing the recorded original authority
+    let (lookup_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            vault_key.as_ref(),
+            metadata_info.key.as_ref(),
+        ],
+        program_id,
+    );
+    if lookup_key != *original_authority_lookup_info.key {
+        return Err(MetaplexError::FractionOriginalAuthorityLookupKeyMismatch.into());
+    }
+    if !original_authority_lookup_info.data_is_empty() {
+        return Err(MetaplexError::OriginalAuthorityLookupNotLost.into());
+    }
+
+    assert_return_to_is_wallet(claimed_original_authority_info.key)?;
+
+    let rescue_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata_info.key.as_ref(),
+        RESCUE.as_bytes(),
+    ];
+    let rescue_bump = assert_derivation(program_id, rescue_info, rescue_seeds)?;
+
+    let clock = Clock::from_account_info(clock_info)?;
+
+    // The first call only records the claim and starts the dispute window
+    if rescue_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            rescue_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_FRACTION_METADATA_RESCUE_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata_info.key.as_ref(),
+                RESCUE.as_bytes(),
+                &[rescue_bump],
+            ],
+        )?;
+    }
+
+    let mut rescue = FractionMetadataRescue::from_account_info_or_uninitialized(rescue_info)?;
+
+    if rescue.key == Key::Uninitialized {
+        rescue.key = Key::FractionMetadataRescueV1;
+        rescue.fraction_manager = *fraction_manager_info.key;
+        rescue.metadata = *metadata_info.key;
+        rescue.claimed_original_authority = *claimed_original_authority_info.key;
+        rescue.admin = *admin_wallet_info.key;
+        rescue.requested_at = clock.unix_timestamp;
+        rescue.executed_at = None;
+
+        msg!(
+            "Metadata rescue requested by store admin {} for metadata {}, returning to {} after {}",
+            rescue.admin,
+            rescue.metadata,
+            rescue.claimed_original_authority,
+            rescue.dispute_window_ends()?
+        );
+
+        rescue.serialize(&mut *rescue_info.data.borrow_mut())?;
+        fraction_manager.add_open_record()?;
+        fraction_manager.save(fraction_manager_info)?;
+        return Ok(());
+    }
+
+    if rescue.executed_at.is_some() {
+        return Err(MetaplexError::RescueAlreadyExecuted.into());
+    }
+
+    if rescue.claimed_original_authority != *claimed_original_authority_info.key {
+        return Err(MetaplexError::RescueClaimMismatch.into());
+    }
+
+    if clock.unix_timestamp < rescue.dispute_window_ends()? {
+        return Err(MetaplexError::RescueDisputeWindowOpen.into());
+    }
+
+    transfer_metadata_ownership(
+        token_metadata_program_info.clone(),
+        metadata_info.clone(),
+        fraction_manager_info.clone(),
+        claimed_original_authority_info.clone(),
+        &[
+            PREFIX.as_bytes(),
+            vault_key.as_ref(),
+            &[fraction_manager_bump],
+        ],
+    )?;
+
+    rescue.executed_at = Some(clock.unix_timestamp);
+
+    msg!(
+        "Metadata rescue executed by store admin {} for metadata {}, returned to {}",
+        admin_wallet_info.key,
+        rescue.metadata,
+        rescue.claimed_original_authority
+    );
+
+    rescue.serialize(&mut *rescue_info.data.borrow_mut())?;
+
+    // The lost lookup can never be closed, so the executed rescue stands in for it
+    fraction_manager.close_open_record()?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerV1, MAX_FRACTION_MANAGER_URI_LENGTH},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_fraction_manager_metadata_uri<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if fraction_manager.uri_immutable() {
+        return Err(MetaplexError::ManagerUriImmutable.into());
+    }
+
+    if uri.len() > MAX_FRACTION_MANAGER_URI_LENGTH {
+        return Err(MetaplexError::ManagerUriTooLong.into());
+    }
+
+    fraction_manager.set_metadata_uri(uri);
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerStatus, FractionManagerV1},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_fraction_manager_metadata_uri_immutable<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    // Until every box is validated the uri may still need to describe what ends up in the vault.
+    if fraction_manager.status() == FractionManagerStatus::Initialized {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    fraction_manager.set_uri_immutable();
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FractionStoreConfig, Key, FRACTION_CONFIG, MAX_APPROVED_AUTHORITY_PROGRAMS,
+            MAX_APPROVED_METADATA_PROGRAMS, MAX_FRACTION_STORE_CONFIG_SIZE, PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_fraction_store_config<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    max_manager_generations: Option<u64>,
+    approved_authority_programs: Vec<Pubkey>,
+    buyout_fee_bps: u16,
+    teardown_rent_deposit: Option<u64>,
+    approved_metadata_programs: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_store_config_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    if approved_authority_programs.len() > MAX_APPROVED_AUTHORITY_PROGRAMS {
+        return Err(MetaplexError::TooManyApprovedAuthorityPrograms.into());
+    }
+
+    if approved_metadata_programs.len() > MAX_APPROVED_METADATA_PROGRAMS {
+        return Err(MetaplexError::TooManyApprovedMetadataPrograms.into());
+    }
+
+    if buyout_fee_bps > 10000 {
+        return Err(MetaplexError::InvalidBuyoutFeeBps.into());
+    }
+
+    assert_signer(payer_info)?;
+    assert_signer(admin_wallet_info)?;
+    if !fraction_store_config_info.data_is_empty() {
+        assert_owned_by(fraction_store_config_info, program_id)?;
+    }
+    assert_owned_by(store_info, program_id)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    let config_bump = assert_derivation(
+        program_id,
+        fraction_store_config_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+    )?;
+
+    if fraction_store_config_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            fraction_store_config_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_FRACTION_STORE_CONFIG_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store_info.key.as_ref(),
+                FRACTION_CONFIG.as_bytes(),
+                &[config_bump],
+            ],
+        )?;
+    }
+
+    let mut fraction_store_config =
+        FractionStoreConfig::from_account_info_or_uninitialized(fraction_store_config_info)?;
+    fraction_store_config.key = Key::FractionStoreConfigV1;
+    fraction_store_config.store = *store_info.key;
+    fraction_store_config.max_manager_generations = max_manager_generations;
+    fraction_store_config.approved_authority_programs = approved_authority_programs;
+    fraction_store_config.buyout_fee_bps = buyout_fee_bps;
+    fraction_store_config.teardown_rent_deposit = teardown_rent_deposit;
+    fraction_store_config.approved_metadata_programs = approved_metadata_programs;
+
+    fraction_store_config.serialize(&mut *fraction_store_config_info.data.borrow_mut())?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use {
+    crate::{
+        state::{FractionManager, FractionManagerV1},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_fraction_supply_caps<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    max_fraction_supply: Option<u64>,
+    max_fraction_shares_per_wallet: Option<u64>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    // Caps only bind later mints, shares already out stay where they are.
+    fraction_manager.set_fraction_supply_caps(max_fraction_supply, max_fraction_shares_per_wallet);
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use {
+    crate::{
+        state::{Key, MintDenylist, DENYLIST, MAX_MINT_DENYLIST_SIZE, PREFIX},
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_mint_denylisted<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    denylisted: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let mint_denylist_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_signer(admin_wallet_info)?;
+    if !mint_denylist_info.data_is_empty() {
+        assert_owned_by(mint_denylist_info, program_id)?;
+    }
+    assert_owned_by(store_info, program_id)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    let denylist_bump = assert_derivation(
+        program_id,
+        mint_denylist_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            DENYLIST.as_bytes(),
+        ],
+    )?;
+
+    if mint_denylist_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            mint_denylist_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_MINT_DENYLIST_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store_info.key.as_ref(),
+                DENYLIST.as_bytes(),
+                &[denylist_bump],
+            ],
+        )?;
+    }
+
+    let mut mint_denylist = MintDenylist::from_account_info(mint_denylist_info)?;
+    mint_denylist.key = Key::MintDenylistV1;
+    mint_denylist.store = *store_info.key;
+    mint_denylist.set_denylisted(*mint_info.key, denylisted)?;
+
+    mint_denylist.serialize(&mut *mint_denylist_info.data.borrow_mut())?;
+    Ok(())
+}
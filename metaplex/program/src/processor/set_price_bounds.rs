@@ -0,0 +1,41 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerV1},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_price_bounds<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if let (Some(min_price), Some(max_price)) = (min_price, max_price) {
+        if min_price > max_price {
+            return Err(MetaplexError::InvalidPriceBounds.into());
+        }
+    }
+
+    fraction_manager.set_price_bounds(min_price, max_price);
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
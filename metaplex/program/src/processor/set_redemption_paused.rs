@@ -0,0 +1,39 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerStatus, FractionManagerV1},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_redemption_paused<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    paused: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    // Pausing only makes sense once redemption has actually been opened.
+    if fraction_manager.status() != FractionManagerStatus::Redeemable {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    fraction_manager.set_redemption_paused(paused);
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
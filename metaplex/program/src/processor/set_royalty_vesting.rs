@@ -0,0 +1,43 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FractionManager, FractionManagerV1, MAX_ROYALTY_VESTING_SECONDS},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_royalty_vesting<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    vesting_seconds: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    // Creators may already have withdrawn against the running schedule.
+    if fraction_manager.royalty_vesting().0.is_some() {
+        return Err(MetaplexError::RoyaltyVestingAlreadySet.into());
+    }
+
+    if vesting_seconds > MAX_ROYALTY_VESTING_SECONDS {
+        return Err(MetaplexError::RoyaltyVestingTooLong.into());
+    }
+
+    fraction_manager.set_royalty_vesting_seconds(vesting_seconds);
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            CreatorSnapshot, FractionCreatorSnapshot, FractionManager, FractionManagerStatus,
+            FractionManagerV1, Key, Store, CREATORS, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, PREFIX,
+        },
+        utils::{
+            assert_authority_correct, assert_derivation, assert_owned_by, assert_signer,
+            create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    mpl_token_metadata::state::Metadata,
+    mpl_token_vault::state::SafetyDepositBox,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_snapshot_fraction_creators<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creator_snapshot_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    if !creator_snapshot_info.data_is_empty() {
+        assert_owned_by(creator_snapshot_info, program_id)?;
+    }
+
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    // Once redemption opens the creators are what holders were sold on.
+    match fraction_manager.status() {
+        FractionManagerStatus::Initialized | FractionManagerStatus::Validated => (),
+        _ => return Err(MetaplexError::InvalidStatus.into()),
+    }
+
+    assert_owned_by(safety_deposit_info, &store.token_vault_program)?;
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+
+    let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if safety_deposit.vault != fraction_manager.vault() {
+        return Err(MetaplexError::SafetyDepositBoxVaultMismatch.into());
+    }
+
+    if safety_deposit.token_mint != metadata.mint {
+        return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
+    }
+
+    let snapshot_bump = assert_derivation(
+        program_id,
+        creator_snapshot_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            metadata_info.key.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+    )?;
+
+    if creator_snapshot_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            creator_snapshot_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager_info.key.as_ref(),
+                metadata_info.key.as_ref(),
+                CREATORS.as_bytes(),
+                &[snapshot_bump],
+            ],
+        )?;
+    }
+
+    let mut creator_snapshot =
+        FractionCreatorSnapshot::from_account_info_or_uninitialized(creator_snapshot_info)?;
+    // Taken once, so the creators can't be rewritten before redemption opens
+    if creator_snapshot.key != Key::Uninitialized {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+    creator_snapshot.key = Key::FractionCreatorSnapshotV1;
+    creator_snapshot.fraction_manager = *fraction_manager_info.key;
+    creator_snapshot.metadata = *metadata_info.key;
+    creator_snapshot.creators = match &metadata.data.creators {
+        Some(creators) => creators
+            .iter()
+            .map(|creator| CreatorSnapshot {
+                address: creator.address,
+                verified: creator.verified,
+                share: creator.share,
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    creator_snapshot.serialize(&mut *creator_snapshot_info.data.borrow_mut())?;
+
+    fraction_manager.mark_creators_snapshotted();
+    fraction_manager.add_open_record()?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::UpdateFractionSafetyDepositConfigArgs,
+        state::{
+            FractionManager, FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig,
+            PREFIX,
+        },
+        utils::{assert_authority_correct, assert_derivation, assert_owned_by},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_update_fraction_safety_deposit_config<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateFractionSafetyDepositConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let safety_deposit_config_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(safety_deposit_config_info, program_id)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+
+    let fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+
+    assert_authority_correct(&fraction_manager.authority(), authority_info)?;
+
+    assert_derivation(
+        program_id,
+        safety_deposit_config_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            safety_deposit_info.key.as_ref(),
+        ],
+    )?;
+
+    let mut safety_deposit_config =
+        FractionSafetyDepositConfig::from_account_info(safety_deposit_config_info)?;
+
+    if safety_deposit_config.immutable {
+        return Err(MetaplexError::ConfigImmutable.into());
+    }
+
+    // Once the manager is activated the configs are what holders were sold on.
+    match fraction_manager.status() {
+        FractionManagerStatus::Initialized | FractionManagerStatus::Validated => (),
+        _ => return Err(MetaplexError::InvalidStatus.into()),
+    }
+
+    safety_deposit_config.fixed_price = args.fixed_price;
+    safety_deposit_config.immutable = args.immutable;
+    safety_deposit_config.save(safety_deposit_config_info)?;
+
+    Ok(())
+}
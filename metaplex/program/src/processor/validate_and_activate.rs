@@ -0,0 +1,117 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::FractionOrderRange,
+        instruction::PdaAuthority,
+        processor::validate_fraction_safety_deposit_box::process_validate_fraction_safety_deposit_box,
+        state::{
+            FractionManager, FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig,
+        },
+        utils::{assert_redemption_price_floor, remaining_compute_units, report_compute_units},
+    },
+    mpl_token_vault::state::Vault,
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+        program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+    },
+};
+
+// Positions in the ValidateFractionSafetyDepositBox accounts that activation reads as well
+const FRACTION_MANAGER_ACCOUNT: usize = 1;
+const VAULT_ACCOUNT: usize = 10;
+const CLOCK_ACCOUNT: usize = 18;
+
+fn validation_account<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    position: usize,
+) -> Result<&'a AccountInfo<'a>, ProgramError> {
+    accounts
+        .get(position)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+/// Checks made before validating, so a vault that can't be activated in one step fails cheaply.
+pub fn assert_can_validate_and_activate(
+    vault: &Vault,
+    safety_deposit_config: &FractionSafetyDepositConfig,
+    allow_multiple_boxes: bool,
+) -> ProgramResult {
+    if vault.token_type_count > 1 && !allow_multiple_boxes {
+        return Err(MetaplexError::ActivationRequiresSingleBox.into());
+    }
+
+    if safety_deposit_config.fixed_price.is_none() {
+        return Err(MetaplexError::ActivationPriceMissing.into());
+    }
+
+    Ok(())
+}
+
+/// Opens redemption at `price_per_share` on a manager whose last box was just validated,
+/// starting creator royalty vesting at `now`.
+pub fn activate_redemption(
+    fraction_manager: &mut dyn FractionManager,
+    price_per_share: u64,
+    now: i64,
+) -> ProgramResult {
+    if fraction_manager.status() != FractionManagerStatus::Validated {
+        return Err(MetaplexError::ActivationBeforeAllBoxesValidated.into());
+    }
+
+    assert_redemption_price_floor(fraction_manager, price_per_share)?;
+
+    fraction_manager.set_fixed_price_per_share(Some(price_per_share));
+    fraction_manager.set_status(FractionManagerStatus::Redeemable);
+    fraction_manager.start_royalty_vesting(now);
+
+    Ok(())
+}
+
+pub fn process_validate_and_activate<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    safety_deposit_config: FractionSafetyDepositConfig,
+    max_slot: Option<u64>,
+    allow_multiple_boxes: bool,
+    pda_authority: Option<PdaAuthority>,
+    report_cu: bool,
+    return_to: Option<Pubkey>,
+    require_depositor_custody: bool,
+    order_range: Option<FractionOrderRange>,
+) -> ProgramResult {
+    let compute_start = if report_cu {
+        remaining_compute_units()
+    } else {
+        None
+    };
+
+    let vault = Vault::from_account_info(validation_account(accounts, VAULT_ACCOUNT)?)?;
+    assert_can_validate_and_activate(&vault, &safety_deposit_config, allow_multiple_boxes)?;
+    let price_per_share = safety_deposit_config
+        .fixed_price
+        .ok_or(MetaplexError::ActivationPriceMissing)?;
+
+    process_validate_fraction_safety_deposit_box(
+        program_id,
+        accounts,
+        safety_deposit_config,
+        max_slot,
+        pda_authority,
+        false,
+        return_to,
+        require_depositor_custody,
+        order_range,
+    )?;
+
+    let fraction_manager_info = validation_account(accounts, FRACTION_MANAGER_ACCOUNT)?;
+    let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let clock = Clock::from_account_info(validation_account(accounts, CLOCK_ACCOUNT)?)?;
+    activate_redemption(&mut fraction_manager, price_per_share, clock.unix_timestamp)?;
+    fraction_manager.save(fraction_manager_info)?;
+
+    if report_cu {
+        report_compute_units(compute_start)?;
+    }
+
+    Ok(())
+}
@@ -1,15 +1,20 @@
 use {
     crate::{
         error::MetaplexError,
+        instruction::{FractionOrderRange, PdaAuthority},
         state::{
-            FractionManager, FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig,
-            FractionWinningConfigType, Key, OriginalAuthorityLookup, Store,
-            MAX_AUTHORITY_LOOKUP_SIZE, PREFIX,
+            FractionDepositRecord, FractionManager, FractionManagerStatus, FractionManagerV1,
+            FractionOriginalAuthorityLookup, FractionSafetyDepositConfig, FractionStoreConfig,
+            FractionWinningConfigType, Key, Store, DEPOSIT, MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+            PREFIX,
         },
         utils::{
             assert_at_least_one_fraction_creator_matches_or_store_public_and_all_verified,
-            assert_authority_correct, assert_derivation, assert_initialized, assert_owned_by,
+            assert_authority_correct, assert_derivation, assert_fraction_manager_status_consistent,
+            assert_initialized, assert_is_genuine_nft, assert_mint_not_denylisted, assert_owned_by,
+            assert_pricing_lookup_matches, assert_rent_exempt, assert_signer, assert_slot_not_past,
             assert_store_safety_vault_manager_match, create_or_allocate_account_raw,
+            load_fraction_store_config, remaining_compute_units, report_compute_units,
             transfer_metadata_ownership,
         },
     },
@@ -23,11 +28,13 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         pubkey::Pubkey,
+        sysvar::{rent::Rent, Sysvar},
     },
     spl_token::state::{Account, Mint},
 };
 pub fn make_fraction_safety_deposit_config<'a>(
     program_id: &Pubkey,
+    fraction_manager: &dyn FractionManager,
     fraction_manager_info: &AccountInfo<'a>,
     safety_deposit_info: &AccountInfo<'a>,
     safety_deposit_config_info: &AccountInfo<'a>,
@@ -36,38 +43,95 @@ pub fn make_fraction_safety_deposit_config<'a>(
     system_info: &AccountInfo<'a>,
     safety_deposit_config: &FractionSafetyDepositConfig,
 ) -> ProgramResult {
-    let bump = assert_derivation(
+    let (config_key, bump) = fraction_manager.config_address(
         program_id,
-        safety_deposit_config_info,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            fraction_manager_info.key.as_ref(),
-            safety_deposit_info.key.as_ref(),
-        ],
-    )?;
+        fraction_manager_info.key,
+        safety_deposit_info.key,
+    );
+    if config_key != *safety_deposit_config_info.key {
+        return Err(MetaplexError::DerivedKeyInvalid.into());
+    }
 
-    create_or_allocate_account_raw(
-        *program_id,
-        safety_deposit_config_info,
-        rent_info,
-        system_info,
-        payer_info,
-        safety_deposit_config.created_size(),
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            fraction_manager_info.key.as_ref(),
-            safety_deposit_info.key.as_ref(),
-            &[bump],
-        ],
-    )?;
+    // A pre-allocated config already has its space
+    if safety_deposit_config_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            safety_deposit_config_info,
+            rent_info,
+            system_info,
+            payer_info,
+            safety_deposit_config.created_size(),
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager_info.key.as_ref(),
+                safety_deposit_info.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    // Guard against a partial allocation leaving the config unable to survive rent collection.
+    let rent = &Rent::from_account_info(rent_info)?;
+    assert_rent_exempt(rent, safety_deposit_config_info)
+        .map_err(|_| MetaplexError::ConfigNotRentExempt)?;
 
     safety_deposit_config.create(safety_deposit_config_info, fraction_manager_info.key)?;
 
     Ok(())
 }
 
+/// Validation only proceeds on a fresh config, or one pre-allocated for the same type.
+pub fn assert_config_allocation(
+    program_id: &Pubkey,
+    safety_deposit_config_info: &AccountInfo,
+    winning_config_type: FractionWinningConfigType,
+) -> ProgramResult {
+    if safety_deposit_config_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(safety_deposit_config_info, program_id)?;
+
+    match FractionSafetyDepositConfig::get_preallocated_type(safety_deposit_config_info)? {
+        Some(declared) if declared == winning_config_type => Ok(()),
+        Some(_) => Err(MetaplexError::PreallocatedTypeMismatch.into()),
+        None => Err(MetaplexError::AlreadyValidated.into()),
+    }
+}
+
+/// Only the manager authority names an expected metadata authority, when pre-allocating, and
+/// the stored one binds validation. When set, that authority has to sign.
+pub fn assert_expected_metadata_authority(
+    safety_deposit_config_info: &AccountInfo,
+    safety_deposit_config: &FractionSafetyDepositConfig,
+    metadata_authority_info: &AccountInfo,
+) -> ProgramResult {
+    let expected = if safety_deposit_config_info.data_is_empty() {
+        // Otherwise whoever validates would pick who has to sign for it
+        if safety_deposit_config.metadata_authority.is_some() {
+            return Err(MetaplexError::MetadataAuthorityRequiresPreallocation.into());
+        }
+        None
+    } else {
+        let stored =
+            FractionSafetyDepositConfig::get_metadata_authority(safety_deposit_config_info);
+        if safety_deposit_config.metadata_authority != stored {
+            return Err(MetaplexError::PreallocatedConfigMismatch.into());
+        }
+        stored
+    };
+
+    match expected {
+        Some(expected)
+            if expected != *metadata_authority_info.key || !metadata_authority_info.is_signer =>
+        {
+            Err(MetaplexError::UnexpectedMetadataAuthority.into())
+        }
+        _ => Ok(()),
+    }
+}
+
 pub struct CommonCheckArgs<'a, 'b> {
     pub program_id: &'a Pubkey,
     pub fraction_manager_info: &'a AccountInfo<'a>,
@@ -82,6 +146,7 @@ pub struct CommonCheckArgs<'a, 'b> {
     pub token_metadata_program_info: &'a AccountInfo<'a>,
     pub fraction_manager_store_info: &'a AccountInfo<'a>,
     pub authority_info: &'a AccountInfo<'a>,
+    pub mint_denylist_info: &'a AccountInfo<'a>,
     pub store: &'b Store,
     pub fraction_manager: &'b dyn FractionManager,
     pub metadata: &'b Metadata,
@@ -105,6 +170,7 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
         token_metadata_program_info,
         fraction_manager_store_info,
         authority_info,
+        mint_denylist_info,
         store,
         fraction_manager,
         metadata,
@@ -120,6 +186,14 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
         return Err(MetaplexError::VaultAuthorityMismatch.into());
     }
 
+    assert_pricing_lookup_matches(vault, &fraction_manager.external_pricing())?;
+
+    // A reopened vault can gain boxes the manager never accounted for, which would let it
+    // reach Validated with boxes left unchecked.
+    if vault.token_type_count > fraction_manager.vault_token_type_count() {
+        return Err(MetaplexError::VaultTokenTypesAdded.into());
+    }
+
     assert_owned_by(fraction_manager_info, program_id)?;
     assert_owned_by(metadata_info, &store.token_metadata_program)?;
     if !original_authority_lookup_info.data_is_empty() {
@@ -128,9 +202,11 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
 
     if *whitelisted_creator_info.key != solana_program::system_program::id() {
         if whitelisted_creator_info.data_is_empty() {
-            return Err(MetaplexError::Uninitialized.into());
+            return Err(MetaplexError::WhitelistedCreatorUninitialized.into());
+        }
+        if whitelisted_creator_info.owner != program_id {
+            return Err(MetaplexError::WhitelistedCreatorWrongOwner.into());
         }
-        assert_owned_by(whitelisted_creator_info, program_id)?;
     }
 
     assert_owned_by(fraction_manager_store_info, program_id)?;
@@ -138,8 +214,28 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
     assert_owned_by(safety_deposit_token_store_info, &store.token_program)?;
     assert_owned_by(mint_info, &store.token_program)?;
 
-    if *winning_config_type != FractionWinningConfigType::FractionToken {
-        assert_owned_by(edition_info, &store.token_metadata_program)?;
+    // The vault program only accepts stores held by its own vault PDA, so any other authority
+    // means the store was never set up through the vault.
+    let safety_deposit_token_store: Account = assert_initialized(safety_deposit_token_store_info)?;
+    let (vault_authority, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_vault::state::PREFIX.as_bytes(),
+            store.token_vault_program.as_ref(),
+            vault_info.key.as_ref(),
+        ],
+        &store.token_vault_program,
+    );
+    if safety_deposit_token_store.owner != vault_authority {
+        return Err(MetaplexError::StoreNotVaultDerived.into());
+    }
+
+    // Edition ownership is checked with the supply logic, against the store's approved programs
+    if *winning_config_type == FractionWinningConfigType::FractionToken
+        && *edition_info.key != solana_program::system_program::id()
+    {
+        // Token boxes have no edition, so anything else is most likely a master edition
+        // NFT mistakenly configured as a token.
+        return Err(MetaplexError::UnexpectedEditionForTokenBox.into());
     }
     assert_owned_by(vault_info, &store.token_vault_program)?;
 
@@ -170,6 +266,14 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
         return Err(MetaplexError::SafetyDepositBoxMintMismatch.into());
     }
 
+    // Denylisting applies on top of the whitelist, so a whitelisted creator's mint can still be refused.
+    assert_mint_not_denylisted(
+        program_id,
+        fraction_manager_store_info.key,
+        mint_denylist_info,
+        mint_info.key,
+    )?;
+
     if *token_metadata_program_info.key != store.token_metadata_program {
         return Err(MetaplexError::FractionManagerTokenMetadataProgramMismatch.into());
     }
@@ -184,6 +288,185 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
     Ok(())
 }
 
+/// Checks the metadata update authority is the PDA the seeds derive under a program the store
+/// approved. Such an authority can't co-sign a transfer, so it is recorded instead.
+pub fn assert_pda_authority_approved(
+    fraction_store_config: &FractionStoreConfig,
+    metadata: &Metadata,
+    metadata_authority_info: &AccountInfo,
+    pda_authority: &PdaAuthority,
+) -> ProgramResult {
+    if !fraction_store_config.is_approved_authority_program(&pda_authority.program) {
+        return Err(MetaplexError::AuthorityProgramNotApproved.into());
+    }
+
+    let seeds: Vec<&[u8]> = pda_authority.seeds.iter().map(|s| s.as_slice()).collect();
+    let derived = Pubkey::create_program_address(&seeds, &pda_authority.program)
+        .map_err(|_| MetaplexError::DerivedKeyInvalid)?;
+    if derived != metadata.update_authority || derived != *metadata_authority_info.key {
+        return Err(MetaplexError::DerivedKeyInvalid.into());
+    }
+
+    Ok(())
+}
+
+/// A PDA or the default key can never sign for the metadata again, so handing the
+/// authority to one on unwind would strand the NFT.
+pub fn assert_return_to_is_wallet(return_to: &Pubkey) -> ProgramResult {
+    if *return_to == Pubkey::default() || !return_to.is_on_curve() {
+        return Err(MetaplexError::InvalidReturnToAddress.into());
+    }
+
+    Ok(())
+}
+
+/// The edition may live on any metadata program the store approved, as long as it sits at the
+/// edition address that program derives for the metadata's mint.
+pub fn assert_edition_program_consistent(
+    store: &Store,
+    fraction_store_config: &FractionStoreConfig,
+    metadata: &Metadata,
+    edition_info: &AccountInfo,
+) -> ProgramResult {
+    let edition_program = edition_info.owner;
+    if !fraction_store_config.is_approved_metadata_program(store, edition_program) {
+        return Err(MetaplexError::EditionProgramNotApproved.into());
+    }
+
+    let (edition_key, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            edition_program.as_ref(),
+            metadata.mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        edition_program,
+    );
+    if edition_key != *edition_info.key {
+        return Err(MetaplexError::InvalidEditionAddress.into());
+    }
+
+    Ok(())
+}
+
+/// Recording a deposit requires the signing depositor to still hold the NFT itself, in a token
+/// account of its own with a balance of exactly one.
+pub fn assert_depositor_owns_nft(
+    store: &Store,
+    metadata: &Metadata,
+    depositor_info: &AccountInfo,
+    depositor_token_account_info: &AccountInfo,
+) -> ProgramResult {
+    assert_signer(depositor_info)?;
+    assert_owned_by(depositor_token_account_info, &store.token_program)?;
+
+    let depositor_token_account: Account = assert_initialized(depositor_token_account_info)?;
+    if depositor_token_account.mint != metadata.mint
+        || depositor_token_account.owner != *depositor_info.key
+        || depositor_token_account.amount != 1
+    {
+        return Err(MetaplexError::DepositorDoesNotOwnNft.into());
+    }
+
+    Ok(())
+}
+
+/// For custody and pre-vault flows the NFT has moved into the vault by validation time, so the
+/// signing depositor proves ownership with the record written while it still held the NFT.
+pub fn assert_deposit_recorded(
+    program_id: &Pubkey,
+    vault_key: &Pubkey,
+    metadata: &Metadata,
+    depositor_info: &AccountInfo,
+    deposit_record_info: &AccountInfo,
+) -> ProgramResult {
+    assert_signer(depositor_info)?;
+    assert_derivation(
+        program_id,
+        deposit_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault_key.as_ref(),
+            metadata.mint.as_ref(),
+            DEPOSIT.as_bytes(),
+        ],
+    )?;
+    if deposit_record_info.data_is_empty() {
+        return Err(MetaplexError::DepositorDoesNotOwnNft.into());
+    }
+    assert_owned_by(deposit_record_info, program_id)?;
+
+    // Anything but a written record means the deposit was never recorded
+    let deposit_record = FractionDepositRecord::from_account_info(deposit_record_info)
+        .map_err(|_| MetaplexError::DepositorDoesNotOwnNft)?;
+    if deposit_record.vault != *vault_key
+        || deposit_record.mint != metadata.mint
+        || deposit_record.depositor != *depositor_info.key
+    {
+        return Err(MetaplexError::DepositRecordMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Fraction tokens left in the treasury by an aborted earlier attempt were never accounted for
+/// by this manager, so it won't finish validating on top of them.
+pub fn assert_vault_treasury_empty(
+    vault: &Vault,
+    fraction_treasury_info: &AccountInfo,
+) -> ProgramResult {
+    if vault.fraction_treasury != *fraction_treasury_info.key {
+        return Err(MetaplexError::VaultTreasuryMismatch.into());
+    }
+
+    let fraction_treasury: Account = assert_initialized(fraction_treasury_info)?;
+    if fraction_treasury.amount != 0 {
+        return Err(MetaplexError::VaultTreasuryNotEmpty.into());
+    }
+
+    Ok(())
+}
+
+/// Counts a box towards the manager. Transactions validating disjoint order ranges may land in
+/// any order; each builds on the manager the last one wrote, and the bitmap keeps a box from
+/// ever counting twice. Whatever the order, the manager moves from Initialized to Validated
+/// exactly once, on the box that brings the count to the token type count snapshotted at init,
+/// and only while the vault's fraction treasury is empty.
+pub fn record_box_validated(
+    fraction_manager: &mut FractionManagerV1,
+    order: u8,
+    order_range: Option<FractionOrderRange>,
+    vault: &Vault,
+    fraction_treasury_info: &AccountInfo,
+) -> ProgramResult {
+    if fraction_manager.status() != FractionManagerStatus::Initialized {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    if let Some(order_range) = order_range {
+        if !order_range.contains(order as u64) {
+            return Err(MetaplexError::SafetyDepositOrderOutsideRange.into());
+        }
+    }
+
+    fraction_manager.mark_order_validated(order)?;
+    fraction_manager.state.safety_config_items_validated = fraction_manager
+        .state
+        .safety_config_items_validated
+        .checked_add(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    if fraction_manager.state.safety_config_items_validated
+        == fraction_manager.vault_token_type_count() as u64
+    {
+        assert_vault_treasury_empty(vault, fraction_treasury_info)?;
+        fraction_manager.state.status = FractionManagerStatus::Validated
+    }
+
+    Ok(())
+}
+
 pub struct SupplyLogicCheckArgs<'a, 'b> {
     pub program_id: &'a Pubkey,
     pub fraction_manager_info: &'a AccountInfo<'a>,
@@ -196,11 +479,15 @@ pub struct SupplyLogicCheckArgs<'a, 'b> {
     pub payer_info: &'a AccountInfo<'a>,
     pub token_metadata_program_info: &'a AccountInfo<'a>,
     pub safety_deposit_token_store_info: &'a AccountInfo<'a>,
+    pub mint_info: &'a AccountInfo<'a>,
     pub fraction_manager: &'b dyn FractionManager,
     pub winning_config_type: &'b FractionWinningConfigType,
     pub metadata: &'b Metadata,
     pub safety_deposit: &'b SafetyDepositBox,
     pub store: &'b Store,
+    pub fraction_store_config: &'b FractionStoreConfig,
+    pub pda_authority: Option<&'b PdaAuthority>,
+    pub return_to: Option<Pubkey>,
 }
 
 pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
@@ -221,20 +508,14 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
         safety_deposit,
         store,
         safety_deposit_token_store_info,
+        mint_info,
+        fraction_store_config,
+        pda_authority,
+        return_to,
     } = args;
 
     let safety_deposit_token_store: Account = assert_initialized(safety_deposit_token_store_info)?;
 
-    let edition_seeds = &[
-        mpl_token_metadata::state::PREFIX.as_bytes(),
-        store.token_metadata_program.as_ref(),
-        &metadata.mint.as_ref(),
-        mpl_token_metadata::state::EDITION.as_bytes(),
-    ];
-
-    let (edition_key, _) =
-        Pubkey::find_program_address(edition_seeds, &store.token_metadata_program);
-
     // HERE IS A POINT IT CAN BREAK
     // remember, seeds are used as a definition of what is correct.
     // when the transaction is signed, it is verified that the supplied signers is equal to something this generates??
@@ -248,19 +529,32 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
     // Supply logic check
     match winning_config_type {
         FractionWinningConfigType::FractionMasterEditionV2 => {
-            // Asserts current wallet owner is the correct metadata owner
-            assert_update_authority_is_correct(&metadata, metadata_authority_info)?;
+            match pda_authority {
+                Some(pda_authority) => assert_pda_authority_approved(
+                    fraction_store_config,
+                    metadata,
+                    metadata_authority_info,
+                    pda_authority,
+                )?,
+                // Asserts current wallet owner is the correct metadata owner
+                None => assert_update_authority_is_correct(&metadata, metadata_authority_info)?,
+            }
+            if let Some(return_to) = return_to {
+                assert_return_to_is_wallet(&return_to)?;
+            }
 
             if safety_deposit.token_mint != metadata.mint {
                 return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
             }
-            if edition_key != *edition_info.key {
-                return Err(MetaplexError::InvalidEditionAddress.into());
-            }
+            assert_edition_program_consistent(
+                store,
+                fraction_store_config,
+                metadata,
+                edition_info,
+            )?;
 
-            if safety_deposit_token_store.amount != 1 {
-                return Err(MetaplexError::StoreIsEmpty.into());
-            }
+            let mint: Mint = assert_initialized(mint_info)?;
+            assert_is_genuine_nft(&mint, &safety_deposit_token_store)?;
 
             // TODO - IS THIS NEEDED!!!!!!!!
             // if total_amount_requested != 1 {
@@ -303,25 +597,31 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
                 rent_info,
                 system_info,
                 payer_info,
-                MAX_AUTHORITY_LOOKUP_SIZE,
+                MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
                 original_authority_seeds,
             )?;
 
             let mut original_authority_lookup =
-                OriginalAuthorityLookup::from_account_info(original_authority_lookup_info)?;
-            original_authority_lookup.key = Key::OriginalAuthorityLookupV1;
+                FractionOriginalAuthorityLookup::from_account_info_or_uninitialized(
+                    original_authority_lookup_info,
+                )?;
+            original_authority_lookup.key = Key::FractionOriginalAuthorityLookupV1;
 
             original_authority_lookup.original_authority = *metadata_authority_info.key;
+            original_authority_lookup.pda_authority = pda_authority.is_some();
+            original_authority_lookup.return_to = return_to;
 
             // Transfers the ownership of the metadata (for the picture I believe)
             // from the current authority (the connected wallet!) -> to the fraction manager while this is fractionalised
-            transfer_metadata_ownership(
-                token_metadata_program_info.clone(),
-                metadata_info.clone(),
-                metadata_authority_info.clone(),
-                fraction_manager_info.clone(),
-                authority_seeds,
-            )?;
+            if pda_authority.is_none() {
+                transfer_metadata_ownership(
+                    token_metadata_program_info.clone(),
+                    metadata_info.clone(),
+                    metadata_authority_info.clone(),
+                    fraction_manager_info.clone(),
+                    authority_seeds,
+                )?;
+            }
 
             original_authority_lookup
                 .serialize(&mut *original_authority_lookup_info.data.borrow_mut())?;
@@ -344,7 +644,18 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
     safety_deposit_config: FractionSafetyDepositConfig,
+    max_slot: Option<u64>,
+    pda_authority: Option<PdaAuthority>,
+    report_cu: bool,
+    return_to: Option<Pubkey>,
+    require_depositor_custody: bool,
+    order_range: Option<FractionOrderRange>,
 ) -> ProgramResult {
+    let compute_start = if report_cu {
+        remaining_compute_units()
+    } else {
+        None
+    };
     let account_info_iter = &mut accounts.iter();
     let safety_deposit_config_info = next_account_info(account_info_iter)?;
     let mut fraction_manager_info = next_account_info(account_info_iter)?;
@@ -365,18 +676,37 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
     let token_metadata_program_info = next_account_info(account_info_iter)?;
     let system_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let mint_denylist_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let fraction_store_config_info = next_account_info(account_info_iter)?;
+    let fraction_treasury_info = next_account_info(account_info_iter)?;
 
-    if !safety_deposit_config_info.data_is_empty() {
-        return Err(MetaplexError::AlreadyValidated.into());
-    }
+    assert_slot_not_past(clock_info, max_slot)?;
+
+    assert_config_allocation(
+        program_id,
+        safety_deposit_config_info,
+        safety_deposit_config.fraction_winning_config_type,
+    )?;
+    assert_expected_metadata_authority(
+        safety_deposit_config_info,
+        &safety_deposit_config,
+        metadata_authority_info,
+    )?;
 
     // get fraction manager from account info
     let mut fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    assert_fraction_manager_status_consistent(&fraction_manager)?;
     let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
     let metadata = Metadata::from_account_info(metadata_info)?;
     let store = Store::from_account_info(fraction_manager_store_info)?;
     // Is it a real vault?
     let vault = Vault::from_account_info(vault_info)?;
+    let fraction_store_config = load_fraction_store_config(
+        program_id,
+        fraction_manager_store_info.key,
+        fraction_store_config_info,
+    )?;
 
     assert_common_checks(CommonCheckArgs {
         program_id,
@@ -392,6 +722,7 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
         token_metadata_program_info,
         fraction_manager_store_info,
         authority_info,
+        mint_denylist_info,
         store: &store,
         fraction_manager: &fraction_manager,
         metadata: &metadata,
@@ -400,6 +731,18 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
         winning_config_type: &safety_deposit_config.fraction_winning_config_type,
     })?;
 
+    if require_depositor_custody {
+        let depositor_info = next_account_info(account_info_iter)?;
+        let deposit_record_info = next_account_info(account_info_iter)?;
+        assert_deposit_recorded(
+            program_id,
+            vault_info.key,
+            &metadata,
+            depositor_info,
+            deposit_record_info,
+        )?;
+    }
+
     assert_supply_logic_check(SupplyLogicCheckArgs {
         program_id,
         fraction_manager_info,
@@ -417,26 +760,37 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
         safety_deposit: &safety_deposit,
         store: &store,
         safety_deposit_token_store_info,
+        mint_info,
+        fraction_store_config: &fraction_store_config,
+        pda_authority: pda_authority.as_ref(),
+        return_to,
     })?;
 
     if safety_deposit_config.order != safety_deposit.order as u64 {
         return Err(MetaplexError::SafetyDepositConfigOrderMismatch.into());
     }
 
-    fraction_manager.state.safety_config_items_validated = fraction_manager
-        .state
-        .safety_config_items_validated
-        .checked_add(1)
-        .ok_or(MetaplexError::NumericalOverflowError)?;
-
-    if fraction_manager.state.safety_config_items_validated == vault.token_type_count as u64 {
-        fraction_manager.state.status = FractionManagerStatus::Validated
+    // A pre-allocated config was counted when it was allocated
+    if FractionSafetyDepositConfig::get_preallocated_type(safety_deposit_config_info)?.is_none() {
+        fraction_manager.add_open_record()?;
+    }
+    if !original_authority_lookup_info.data_is_empty() {
+        fraction_manager.add_open_record()?;
     }
 
+    record_box_validated(
+        &mut fraction_manager,
+        safety_deposit.order,
+        order_range,
+        &vault,
+        fraction_treasury_info,
+    )?;
+
     fraction_manager.save(&mut fraction_manager_info)?;
 
     make_fraction_safety_deposit_config(
         program_id,
+        &fraction_manager,
         fraction_manager_info,
         safety_deposit_info,
         safety_deposit_config_info,
@@ -445,5 +799,10 @@ pub fn process_validate_fraction_safety_deposit_box<'a>(
         system_info,
         &safety_deposit_config,
     )?;
+
+    if report_cu {
+        report_compute_units(compute_start)?;
+    }
+
     Ok(())
 }
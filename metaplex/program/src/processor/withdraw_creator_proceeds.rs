@@ -0,0 +1,128 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FractionCreatorProceeds, FractionCreatorSnapshot, FractionManager, FractionManagerV1,
+            Store, CREATORS, PREFIX, PROCEEDS,
+        },
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+            spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+};
+
+pub fn process_withdraw_creator_proceeds<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creator_proceeds_info = next_account_info(account_info_iter)?;
+    let fraction_manager_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let creator_snapshot_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+    assert_owned_by(creator_proceeds_info, program_id)?;
+    assert_owned_by(fraction_manager_info, program_id)?;
+    assert_owned_by(creator_snapshot_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let fraction_manager = FractionManagerV1::from_account_info(fraction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if fraction_manager.store() != *store_info.key {
+        return Err(MetaplexError::FractionManagerStoreMismatch.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    if fraction_manager.accept_payment() != *accept_payment_info.key {
+        return Err(MetaplexError::AcceptPaymentMismatch.into());
+    }
+
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+    let destination: Account = assert_initialized(destination_info)?;
+    if destination.mint != accept_payment.mint {
+        return Err(MetaplexError::DestinationMintMismatch.into());
+    }
+
+    assert_derivation(
+        program_id,
+        creator_proceeds_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            creator_info.key.as_ref(),
+            PROCEEDS.as_bytes(),
+        ],
+    )?;
+    let mut creator_proceeds = FractionCreatorProceeds::from_account_info(creator_proceeds_info)?;
+    if creator_proceeds.fraction_manager != *fraction_manager_info.key
+        || creator_proceeds.creator != *creator_info.key
+    {
+        return Err(MetaplexError::CreatorProceedsMismatch.into());
+    }
+
+    let creator_snapshot = FractionCreatorSnapshot::from_account_info(creator_snapshot_info)?;
+    assert_derivation(
+        program_id,
+        creator_snapshot_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager_info.key.as_ref(),
+            creator_snapshot.metadata.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+    )?;
+    creator_snapshot.assert_payout_verified(creator_info.key)?;
+
+    let clock = Clock::from_account_info(clock_info)?;
+    let amount = creator_proceeds.withdrawable(&fraction_manager, clock.unix_timestamp)?;
+    if amount == 0 {
+        return Err(MetaplexError::NoVestedCreatorProceeds.into());
+    }
+
+    let vault_key = fraction_manager.vault();
+    let bump = assert_derivation(
+        program_id,
+        fraction_manager_info,
+        &[PREFIX.as_bytes(), vault_key.as_ref()],
+    )?;
+
+    spl_token_transfer(
+        accept_payment_info.clone(),
+        destination_info.clone(),
+        amount,
+        fraction_manager_info.clone(),
+        &[PREFIX.as_bytes(), vault_key.as_ref(), &[bump]],
+        token_program_info.clone(),
+    )?;
+
+    creator_proceeds.withdrawn = creator_proceeds
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    creator_proceeds.serialize(&mut *creator_proceeds_info.data.borrow_mut())?;
+
+    Ok(())
+}
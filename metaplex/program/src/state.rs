@@ -1,17 +1,23 @@
 use {
     crate::{
-        deprecated_state::AuctionManagerV1, error::MetaplexError, utils::try_from_slice_checked,
+        deprecated_state::AuctionManagerV1,
+        error::MetaplexError,
+        utils::{try_from_slice_checked, try_from_slice_initialized},
     },
     arrayref::{array_mut_ref, array_ref, mut_array_refs},
     borsh::{BorshDeserialize, BorshSerialize},
     mpl_auction::processor::AuctionData,
-    mpl_token_metadata::state::Metadata,
-    mpl_token_vault::state::SafetyDepositBox,
+    mpl_token_metadata::state::{Metadata, MAX_CREATOR_LIMIT},
+    mpl_token_vault::state::{ExternalPriceAccount, SafetyDepositBox, Vault},
     solana_program::{
         account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
         pubkey::Pubkey,
     },
-    std::cell::{Ref, RefMut},
+    spl_token::state::Mint,
+    std::{
+        cell::{Ref, RefMut},
+        convert::TryInto,
+    },
 };
 /// prefix used for PDAs to avoid certain collision attacks (https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack)
 pub const PREFIX: &str = "metaplex";
@@ -19,6 +25,20 @@ pub const TOTALS: &str = "totals";
 pub const INDEX: &str = "index";
 pub const CACHE: &str = "cache";
 pub const CONFIG: &str = "config";
+pub const DENYLIST: &str = "denylist";
+pub const FRACTION_MINT: &str = "fraction_mint";
+pub const FRACTION_CONFIG: &str = "fraction_config";
+pub const CLAIM: &str = "claim";
+pub const CREATORS: &str = "creators";
+pub const RESCUE: &str = "rescue";
+pub const PROCEEDS: &str = "proceeds";
+pub const DEPOSIT: &str = "deposit";
+/// How long a metadata rescue must stand before update authority moves, one week
+pub const RESCUE_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60;
+/// Longest creator royalties may take to vest, four years
+pub const MAX_ROYALTY_VESTING_SECONDS: u64 = 4 * 365 * 24 * 60 * 60;
+/// Recipients one batch mint goes through, so it always ends within the compute limit
+pub const MAX_BATCH_MINT_RECIPIENTS: usize = 20;
 pub const BASE_TRACKER_SIZE: usize = 1 + 1 + 1 + 4;
 
 pub const MAX_INDEXED_ELEMENTS: usize = 100;
@@ -28,6 +48,64 @@ pub const MAX_STORE_INDEXER_SIZE: usize = 1 + //key
 4 + // how many elements are in the vec
 32*MAX_INDEXED_ELEMENTS; // size of indexed auction keys
 
+pub const MAX_DENYLISTED_MINTS: usize = 100;
+pub const MAX_MINT_DENYLIST_SIZE: usize = 1 + //key
+32 + //store
+4 + // how many elements are in the vec
+32*MAX_DENYLISTED_MINTS; // sorted denylisted mints
+
+pub const MAX_APPROVED_AUTHORITY_PROGRAMS: usize = 10;
+pub const MAX_APPROVED_METADATA_PROGRAMS: usize = 5;
+pub const MAX_FRACTION_MANAGER_URI_LENGTH: usize = 200;
+pub const MAX_FRACTION_STORE_CONFIG_SIZE: usize = 1 + //key
+32 + //store
+9 + // max manager generations
+4 + // how many approved authority programs are in the vec
+32*MAX_APPROVED_AUTHORITY_PROGRAMS + // approved authority programs
+2 + // buyout fee bps
+9 + // teardown rent deposit
+4 + // how many approved metadata programs are in the vec
+32*MAX_APPROVED_METADATA_PROGRAMS + // approved metadata programs
+200; // padding
+
+pub const MAX_FRACTION_VAULT_CLAIM_SIZE: usize = 1 + //key
+32 + //vault
+8 + // manager generations
+50; // padding
+
+pub const MAX_FRACTION_CREATOR_SNAPSHOT_SIZE: usize = 1 + //key
+32 + //fraction manager
+32 + //metadata
+4 + // how many creators are in the vec
+CREATOR_SNAPSHOT_SIZE*MAX_CREATOR_LIMIT + // creators
+50; // padding
+
+pub const MAX_FRACTION_METADATA_RESCUE_SIZE: usize = 1 + //key
+32 + //fraction manager
+32 + //metadata
+32 + //claimed original authority
+32 + //store admin
+8 + //requested at
+9 + //executed at
+50; // padding
+
+pub const MAX_FRACTION_CREATOR_PROCEEDS_SIZE: usize = 1 + //key
+32 + //fraction manager
+32 + //creator
+8 + //total owed
+8 + //withdrawn
+50; // padding
+
+pub const MAX_FRACTION_DEPOSIT_RECORD_SIZE: usize = 1 + //key
+32 + //vault
+32 + //mint
+32 + //depositor
+50; // padding
+
+pub const CREATOR_SNAPSHOT_SIZE: usize = 32 + // address
+1 + // verified
+1; // share
+
 pub const MAX_METADATA_PER_CACHE: usize = 10;
 pub const MAX_AUCTION_CACHE_SIZE: usize = 1 + //key
 32 + //store
@@ -60,7 +138,53 @@ pub const MAX_FRACTION_MANAGER_SIZE: usize = 1 + //key
 1 + //status
 8 + // winning configs validated
 8 + // token_pools_active
+1 + // redemption paused
+9 + // creator priority until
+1 + // vault token type count
+32 + // external pricing
+9 + // min price
+9 + // max price
+8 + // min redemption price per share
+8 + // prepaid teardown rent
+9 + // royalty vesting start
+8 + // royalty vesting seconds
+8 + // total rent reclaimed
+8 + // total fractions minted
+4 + MAX_FRACTION_MANAGER_URI_LENGTH + // metadata uri
+1 + // uri immutable
+9 + // max fraction supply
+9 + // max fraction shares per wallet
+32 + // validated orders
+32 + // teardown rent payer
+33 + // fraction mint
+9 + // fixed price per share
+1 + // creators snapshotted
+8 + // buyout holder proceeds
+8 + // buyout shares
+8 + // shares redeemed
+8 + // holder proceeds paid
+8 + // open records
+200; // padding
+
+/// Size fraction managers were created at before the state was extended, which
+/// MigrateFractionManager grows to MAX_FRACTION_MANAGER_SIZE
+pub const LEGACY_FRACTION_MANAGER_SIZE: usize = 1 + //key
+32 + // store
+32 + // authority
+32 + // vault
+32 + // token_mint
+32 + // accept_payment
+1 + // has participation
+1 + //status
+8 + // winning configs validated
+8 + // token_pools_active
 200; // padding
+
+pub const FRACTION_MANAGER_SUMMARY_SIZE: usize = 1 + // status
+32 + // vault
+32 + // fraction mint
+8 + // fraction shares
+8; // price per share
 pub const MAX_STORE_SIZE: usize = 2 + // Store Version Key 
 32 + // Auction Program Key
 32 + // Token Vault Program Key
@@ -74,6 +198,11 @@ pub const MAX_WHITELISTED_CREATOR_SIZE: usize = 2 + 32 + 10;
 pub const MAX_PAYOUT_TICKET_SIZE: usize = 1 + 32 + 8;
 pub const MAX_BID_REDEMPTION_TICKET_SIZE: usize = 3;
 pub const MAX_AUTHORITY_LOOKUP_SIZE: usize = 33;
+pub const MAX_FRACTION_AUTHORITY_LOOKUP_SIZE: usize = 1 + // key
+32 + // original authority
+1 + // pda authority
+33 + // return to
+50; // padding
 pub const MAX_PRIZE_TRACKING_TICKET_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 50;
 pub const BASE_SAFETY_CONFIG_SIZE: usize = 1 +// Key
  32 + // auction manager lookup
@@ -96,6 +225,8 @@ pub const FRACTION_BASE_SAFETY_CONFIG_SIZE: usize = 1 +// Key
  8 + // order
  1 + // fraction winning config type
  9 + // fixed price + option of it
+ 1 + // immutable
+ 33 + // expected metadata authority + option of it
  20; // padding
 
 #[repr(C)]
@@ -119,6 +250,15 @@ pub enum Key {
     StoreConfigV1,
     FractionManagerV1,
     FractionSafetyDepositConfigV1,
+    MintDenylistV1,
+    FractionStoreConfigV1,
+    FractionVaultClaimV1,
+    FractionCreatorSnapshotV1,
+    FractionOriginalAuthorityLookupV1,
+    FractionMetadataRescueV1,
+    FractionSafetyDepositConfigPreallocatedV1,
+    FractionCreatorProceedsV1,
+    FractionDepositRecordV1,
 }
 
 pub struct CommonWinningIndexChecks<'a> {
@@ -156,10 +296,63 @@ pub trait FractionManager {
     fn authority(&self) -> Pubkey;
     fn vault(&self) -> Pubkey;
     fn accept_payment(&self) -> Pubkey;
+    fn external_pricing(&self) -> Pubkey;
     fn status(&self) -> FractionManagerStatus;
     fn set_status(&mut self, status: FractionManagerStatus);
     fn configs_validated(&self) -> u64;
     fn set_configs_validated(&mut self, new_configs_validated: u64);
+    fn redemption_paused(&self) -> bool;
+    fn set_redemption_paused(&mut self, paused: bool);
+    fn creator_priority_until(&self) -> Option<i64>;
+    fn vault_token_type_count(&self) -> u8;
+    fn price_bounds(&self) -> (Option<u64>, Option<u64>);
+    fn set_price_bounds(&mut self, min_price: Option<u64>, max_price: Option<u64>);
+    fn min_redemption_price_per_share(&self) -> u64;
+    fn prepaid_teardown_rent(&self) -> u64;
+    fn set_prepaid_teardown_rent(&mut self, prepaid_teardown_rent: u64);
+    fn teardown_rent_payer(&self) -> Pubkey;
+    fn fraction_mint(&self, vault: &Vault) -> Pubkey;
+    fn royalty_vesting(&self) -> (Option<i64>, u64);
+    fn set_royalty_vesting_seconds(&mut self, vesting_seconds: u64);
+    fn start_royalty_vesting(&mut self, start: i64);
+    fn total_rent_reclaimed(&self) -> u64;
+    fn add_rent_reclaimed(&mut self, lamports: u64) -> ProgramResult;
+    fn total_fractions_minted(&self) -> u64;
+    fn add_fractions_minted(&mut self, amount: u64) -> ProgramResult;
+    fn metadata_uri(&self) -> &str;
+    fn set_metadata_uri(&mut self, uri: String);
+    fn uri_immutable(&self) -> bool;
+    fn set_uri_immutable(&mut self);
+    fn order_validated(&self, order: u8) -> bool;
+    fn mark_order_validated(&mut self, order: u8) -> ProgramResult;
+    fn fraction_supply_caps(&self) -> (Option<u64>, Option<u64>);
+    fn set_fraction_supply_caps(
+        &mut self,
+        max_fraction_supply: Option<u64>,
+        max_fraction_shares_per_wallet: Option<u64>,
+    );
+    fn fixed_price_per_share(&self) -> Option<u64>;
+    fn set_fixed_price_per_share(&mut self, price_per_share: Option<u64>);
+    fn creators_snapshotted(&self) -> bool;
+    fn mark_creators_snapshotted(&mut self);
+    fn record_buyout(&mut self, holder_proceeds: u64, shares: u64);
+    fn redeem_buyout_shares(&mut self, shares: u64) -> Result<u64, ProgramError>;
+    fn holder_proceeds_outstanding(&self) -> bool;
+    fn open_records(&self) -> u64;
+    fn add_open_record(&mut self) -> ProgramResult;
+    fn close_open_record(&mut self) -> ProgramResult;
+    fn config_address(
+        &self,
+        program_id: &Pubkey,
+        fraction_manager: &Pubkey,
+        safety_deposit: &Pubkey,
+    ) -> (Pubkey, u8);
+    fn summary(
+        &self,
+        vault: &Vault,
+        fraction_mint: &Mint,
+        external_price_account: &ExternalPriceAccount,
+    ) -> FractionManagerSummary;
     fn save(&self, account: &AccountInfo) -> ProgramResult;
     fn fast_save(
         &self,
@@ -350,6 +543,7 @@ pub enum FractionManagerStatus {
     Active,
     Redeemable,
     Combined,
+    Closed,
 }
 
 #[repr(C)]
@@ -376,6 +570,107 @@ pub struct FractionManagerState {
     pub token_pools_active: u64,
 
     pub has_participation: bool,
+    /// Set by the authority to temporarily halt redemption and buyout once the
+    /// manager is Redeemable, e.g. while a price dispute is resolved.
+    pub redemption_paused: bool,
+    /// Until this unix timestamp only verified creators of the fractionalized NFT may redeem
+    pub creator_priority_until: Option<i64>,
+    /// Vault token type count when the manager was initialized
+    pub vault_token_type_count: u8,
+    /// Redemption and buyout revert if the external price per share falls outside these
+    pub min_price: Option<u64>,
+    pub max_price: Option<u64>,
+    /// Floor on the price per share of any redemption or buyout, so holders are never
+    /// redeemed out at zero. Unlike the price bounds it also binds fixed prices.
+    pub min_redemption_price_per_share: u64,
+    /// Lamports the payer deposited at init so teardown is paid for, refunded once Closed
+    pub prepaid_teardown_rent: u64,
+    /// Creator royalties vest linearly over royalty_vesting_seconds from this unix timestamp,
+    /// the moment redemption opened
+    pub royalty_vesting_start: Option<i64>,
+    pub royalty_vesting_seconds: u64,
+    /// Lamports handed back so far from accounts closed during teardown
+    pub total_rent_reclaimed: u64,
+    /// Fraction shares minted through this manager over its lifetime, burns aside
+    pub total_fractions_minted: u64,
+    /// Descriptive URI of the fractionalized asset, for marketplaces to display
+    pub metadata_uri: String,
+    /// Once set the metadata uri can never change again
+    pub uri_immutable: bool,
+    /// Batch mints never take the fraction mint's supply past this
+    pub max_fraction_supply: Option<u64>,
+    /// Batch mints never leave a wallet holding more than this across the batch's accounts
+    pub max_fraction_shares_per_wallet: Option<u64>,
+    /// Bit per box order, set once that box is validated. Lets disjoint order ranges be
+    /// validated by independent transactions without ever counting a box twice.
+    pub validated_orders: [u8; 32],
+    /// Payer of the manager at init, who prepaid_teardown_rent is refunded to
+    pub teardown_rent_payer: Pubkey,
+    /// Fraction mint the manager created at init, which its shares are minted from in place of
+    /// the vault's own
+    pub fraction_mint: Option<Pubkey>,
+    /// Price per share set when activating straight from validation, which buyout settles at
+    /// in place of the external price
+    pub fixed_price_per_share: Option<u64>,
+    /// Set once the creators are snapshotted, after which every buyout credits them royalties
+    pub creators_snapshotted: bool,
+    /// Holders' part of the buyout payment, which they redeem their shares for out of
+    /// accept payment
+    pub buyout_holder_proceeds: u64,
+    /// Fraction supply at buyout, which the holder proceeds are split across
+    pub buyout_shares: u64,
+    pub shares_redeemed: u64,
+    pub holder_proceeds_paid: u64,
+    /// Configs, lookups, snapshots, proceeds and rescues created for the manager and not yet
+    /// closed, all of which teardown must close before the manager can be
+    pub open_records: u64,
+}
+
+/// Fixed size snapshot of a fractionalized asset for other programs to read.
+/// Fields are only ever appended so existing readers keep deserializing.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FractionManagerSummary {
+    pub status: FractionManagerStatus,
+    pub vault: Pubkey,
+    /// Mint producing the vault's fractional shares
+    pub fraction_mint: Pubkey,
+    /// Current supply of the fraction mint
+    pub fraction_shares: u64,
+    /// Per share price from the vault's external price account
+    pub price_per_share: u64,
+}
+
+/// Logged and set as return data once a closed fraction manager is finalized, so indexers see
+/// the manager's totals after its account is gone.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FractionManagerClosed {
+    pub manager: Pubkey,
+    pub vault: Pubkey,
+    /// Lamports reclaimed over teardown, the manager's own included
+    pub total_rent_reclaimed: u64,
+    pub total_fractions_ever_minted: u64,
+    /// Records and the accept payment account closed by teardown, and their combined rent
+    pub accounts_closed: u64,
+    pub dependent_rent_reclaimed: u64,
+}
+
+/// Return data of a batch mint. A batch longer than MAX_BATCH_MINT_RECIPIENTS minted to the
+/// first recipients_minted recipients only, and the rest should be sent again.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FractionShareBatchMinted {
+    pub recipients_minted: u64,
+    pub amount_minted: u64,
+}
+
+/// Return data of a validation asked to report its compute cost
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ComputeUnitReport {
+    /// None when the runtime can't tell how much compute is left
+    pub consumed: Option<u64>,
 }
 
 #[repr(C)]
@@ -412,6 +707,99 @@ pub struct FractionManagerV1 {
     pub accept_payment: Pubkey,
 
     pub state: FractionManagerState,
+
+    /// External price account the vault was priced against when the manager was initialized
+    pub external_pricing: Pubkey,
+}
+
+/// Fraction manager state as created before it was extended, kept only to migrate from
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionManagerLegacyState {
+    pub status: FractionManagerStatus,
+    pub safety_config_items_validated: u64,
+    pub token_pools_active: u64,
+    pub has_participation: bool,
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionManagerLegacyV1 {
+    pub key: Key,
+
+    pub store: Pubkey,
+
+    pub authority: Pubkey,
+
+    pub vault: Pubkey,
+
+    pub token_mint: Pubkey,
+
+    pub accept_payment: Pubkey,
+
+    pub state: FractionManagerLegacyState,
+}
+
+impl FractionManagerLegacyV1 {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionManagerLegacyV1, ProgramError> {
+        if a.data_len() != LEGACY_FRACTION_MANAGER_SIZE {
+            return Err(MetaplexError::FractionManagerNotLegacy.into());
+        }
+
+        let fm: FractionManagerLegacyV1 = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionManagerV1,
+            LEGACY_FRACTION_MANAGER_SIZE,
+        )?;
+
+        Ok(fm)
+    }
+
+    /// Carries the manager over to the current layout. Fields the original layout had no
+    /// room for start as a new manager's would, except the ones read back off the vault,
+    /// and every box validated so far left a config for teardown to close.
+    pub fn migrate(self, vault: &Vault, teardown_rent_payer: &Pubkey) -> FractionManagerV1 {
+        FractionManagerV1 {
+            key: Key::FractionManagerV1,
+            store: self.store,
+            authority: self.authority,
+            vault: self.vault,
+            token_mint: self.token_mint,
+            accept_payment: self.accept_payment,
+            state: FractionManagerState {
+                status: self.state.status,
+                safety_config_items_validated: self.state.safety_config_items_validated,
+                token_pools_active: self.state.token_pools_active,
+                has_participation: self.state.has_participation,
+                redemption_paused: false,
+                creator_priority_until: None,
+                vault_token_type_count: vault.token_type_count,
+                min_price: None,
+                max_price: None,
+                min_redemption_price_per_share: 0,
+                prepaid_teardown_rent: 0,
+                royalty_vesting_start: None,
+                royalty_vesting_seconds: 0,
+                total_rent_reclaimed: 0,
+                total_fractions_minted: 0,
+                metadata_uri: String::new(),
+                uri_immutable: false,
+                max_fraction_supply: None,
+                max_fraction_shares_per_wallet: None,
+                validated_orders: [0; 32],
+                teardown_rent_payer: *teardown_rent_payer,
+                fraction_mint: None,
+                fixed_price_per_share: None,
+                creators_snapshotted: false,
+                buyout_holder_proceeds: 0,
+                buyout_shares: 0,
+                shares_redeemed: 0,
+                holder_proceeds_paid: 0,
+                open_records: self.state.safety_config_items_validated,
+            },
+            external_pricing: vault.pricing_lookup_address,
+        }
+    }
 }
 
 impl FractionManager for FractionManagerV1 {
@@ -437,6 +825,10 @@ impl FractionManager for FractionManagerV1 {
         self.accept_payment
     }
 
+    fn external_pricing(&self) -> Pubkey {
+        self.external_pricing
+    }
+
     fn status(&self) -> FractionManagerStatus {
         self.state.status
     }
@@ -453,6 +845,253 @@ impl FractionManager for FractionManagerV1 {
         self.state.safety_config_items_validated = new_configs_validated
     }
 
+    fn redemption_paused(&self) -> bool {
+        self.state.redemption_paused
+    }
+
+    fn set_redemption_paused(&mut self, paused: bool) {
+        self.state.redemption_paused = paused
+    }
+
+    fn creator_priority_until(&self) -> Option<i64> {
+        self.state.creator_priority_until
+    }
+
+    fn vault_token_type_count(&self) -> u8 {
+        self.state.vault_token_type_count
+    }
+
+    fn price_bounds(&self) -> (Option<u64>, Option<u64>) {
+        (self.state.min_price, self.state.max_price)
+    }
+
+    fn set_price_bounds(&mut self, min_price: Option<u64>, max_price: Option<u64>) {
+        self.state.min_price = min_price;
+        self.state.max_price = max_price;
+    }
+
+    fn min_redemption_price_per_share(&self) -> u64 {
+        self.state.min_redemption_price_per_share
+    }
+
+    fn prepaid_teardown_rent(&self) -> u64 {
+        self.state.prepaid_teardown_rent
+    }
+
+    fn set_prepaid_teardown_rent(&mut self, prepaid_teardown_rent: u64) {
+        self.state.prepaid_teardown_rent = prepaid_teardown_rent;
+    }
+
+    fn teardown_rent_payer(&self) -> Pubkey {
+        self.state.teardown_rent_payer
+    }
+
+    fn fraction_mint(&self, vault: &Vault) -> Pubkey {
+        self.state.fraction_mint.unwrap_or(vault.fraction_mint)
+    }
+
+    fn royalty_vesting(&self) -> (Option<i64>, u64) {
+        (
+            self.state.royalty_vesting_start,
+            self.state.royalty_vesting_seconds,
+        )
+    }
+
+    fn set_royalty_vesting_seconds(&mut self, vesting_seconds: u64) {
+        self.state.royalty_vesting_seconds = vesting_seconds;
+    }
+
+    fn start_royalty_vesting(&mut self, start: i64) {
+        self.state.royalty_vesting_start = Some(start);
+    }
+
+    fn total_rent_reclaimed(&self) -> u64 {
+        self.state.total_rent_reclaimed
+    }
+
+    fn add_rent_reclaimed(&mut self, lamports: u64) -> ProgramResult {
+        self.state.total_rent_reclaimed = self
+            .state
+            .total_rent_reclaimed
+            .checked_add(lamports)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    fn total_fractions_minted(&self) -> u64 {
+        self.state.total_fractions_minted
+    }
+
+    fn add_fractions_minted(&mut self, amount: u64) -> ProgramResult {
+        self.state.total_fractions_minted = self
+            .state
+            .total_fractions_minted
+            .checked_add(amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    fn metadata_uri(&self) -> &str {
+        &self.state.metadata_uri
+    }
+
+    fn set_metadata_uri(&mut self, uri: String) {
+        self.state.metadata_uri = uri;
+    }
+
+    fn uri_immutable(&self) -> bool {
+        self.state.uri_immutable
+    }
+
+    fn set_uri_immutable(&mut self) {
+        self.state.uri_immutable = true;
+    }
+
+    fn order_validated(&self, order: u8) -> bool {
+        self.state.validated_orders[order as usize / 8] & (1 << (order % 8)) != 0
+    }
+
+    fn mark_order_validated(&mut self, order: u8) -> ProgramResult {
+        if self.order_validated(order) {
+            return Err(MetaplexError::AlreadyValidated.into());
+        }
+
+        self.state.validated_orders[order as usize / 8] |= 1 << (order % 8);
+        Ok(())
+    }
+
+    fn fraction_supply_caps(&self) -> (Option<u64>, Option<u64>) {
+        (
+            self.state.max_fraction_supply,
+            self.state.max_fraction_shares_per_wallet,
+        )
+    }
+
+    fn set_fraction_supply_caps(
+        &mut self,
+        max_fraction_supply: Option<u64>,
+        max_fraction_shares_per_wallet: Option<u64>,
+    ) {
+        self.state.max_fraction_supply = max_fraction_supply;
+        self.state.max_fraction_shares_per_wallet = max_fraction_shares_per_wallet;
+    }
+
+    fn fixed_price_per_share(&self) -> Option<u64> {
+        self.state.fixed_price_per_share
+    }
+
+    fn set_fixed_price_per_share(&mut self, price_per_share: Option<u64>) {
+        self.state.fixed_price_per_share = price_per_share;
+    }
+
+    fn creators_snapshotted(&self) -> bool {
+        self.state.creators_snapshotted
+    }
+
+    fn mark_creators_snapshotted(&mut self) {
+        self.state.creators_snapshotted = true;
+    }
+
+    fn record_buyout(&mut self, holder_proceeds: u64, shares: u64) {
+        self.state.buyout_holder_proceeds = holder_proceeds;
+        self.state.buyout_shares = shares;
+    }
+
+    /// Pays `shares` their pro rata part of the holder proceeds. Each payout rounds down and
+    /// the last shares redeemed take whatever is left, so the proceeds are paid out exactly.
+    fn redeem_buyout_shares(&mut self, shares: u64) -> Result<u64, ProgramError> {
+        let shares_redeemed = self
+            .state
+            .shares_redeemed
+            .checked_add(shares)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        if shares_redeemed > self.state.buyout_shares {
+            return Err(MetaplexError::NumericalOverflowError.into());
+        }
+
+        let payout = if shares_redeemed == self.state.buyout_shares {
+            self.state
+                .buyout_holder_proceeds
+                .checked_sub(self.state.holder_proceeds_paid)
+                .ok_or(MetaplexError::NumericalOverflowError)?
+        } else {
+            (self.state.buyout_holder_proceeds as u128)
+                .checked_mul(shares as u128)
+                .ok_or(MetaplexError::NumericalOverflowError)?
+                .checked_div(self.state.buyout_shares as u128)
+                .ok_or(MetaplexError::NumericalOverflowError)?
+                .try_into()
+                .map_err(|_| MetaplexError::NumericalOverflowError)?
+        };
+
+        self.state.shares_redeemed = shares_redeemed;
+        self.state.holder_proceeds_paid = self
+            .state
+            .holder_proceeds_paid
+            .checked_add(payout)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+
+        Ok(payout)
+    }
+
+    fn holder_proceeds_outstanding(&self) -> bool {
+        self.state.holder_proceeds_paid < self.state.buyout_holder_proceeds
+    }
+
+    fn open_records(&self) -> u64 {
+        self.state.open_records
+    }
+
+    fn add_open_record(&mut self) -> ProgramResult {
+        self.state.open_records = self
+            .state
+            .open_records
+            .checked_add(1)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    fn close_open_record(&mut self) -> ProgramResult {
+        self.state.open_records = self
+            .state
+            .open_records
+            .checked_sub(1)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+        Ok(())
+    }
+
+    fn summary(
+        &self,
+        vault: &Vault,
+        fraction_mint: &Mint,
+        external_price_account: &ExternalPriceAccount,
+    ) -> FractionManagerSummary {
+        FractionManagerSummary {
+            status: self.state.status,
+            vault: self.vault,
+            fraction_mint: self.fraction_mint(vault),
+            fraction_shares: fraction_mint.supply,
+            price_per_share: external_price_account.price_per_share,
+        }
+    }
+
+    fn config_address(
+        &self,
+        program_id: &Pubkey,
+        fraction_manager: &Pubkey,
+        safety_deposit: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.as_ref(),
+                safety_deposit.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
     fn fast_save(
         &self,
         account: &AccountInfo,
@@ -464,7 +1103,10 @@ impl FractionManager for FractionManagerV1 {
     }
 
     fn save(&self, account: &AccountInfo) -> ProgramResult {
-        self.serialize(&mut *account.data.borrow_mut())?;
+        // Through a copy of the data slice, so the manager can be read again in the same
+        // instruction instead of from a slice shortened by the write
+        let mut data = account.data.borrow_mut();
+        self.serialize(&mut &mut data[..])?;
         Ok(())
     }
 
@@ -484,6 +1126,19 @@ impl FractionManager for FractionManagerV1 {
 
 impl FractionManagerV1 {
     pub fn from_account_info(a: &AccountInfo) -> Result<FractionManagerV1, ProgramError> {
+        let fm: FractionManagerV1 = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionManagerV1,
+            MAX_FRACTION_MANAGER_SIZE,
+        )?;
+
+        Ok(fm)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionManagerV1, ProgramError> {
         let fm: FractionManagerV1 = try_from_slice_checked(
             &a.data.borrow_mut(),
             Key::FractionManagerV1,
@@ -938,6 +1593,50 @@ impl OriginalAuthorityLookup {
     }
 }
 
+/// Fraction counterpart of OriginalAuthorityLookup. Metadata whose update authority is a
+/// PDA of an approved program is never transferred to the manager, which pda_authority records.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, Debug)]
+pub struct FractionOriginalAuthorityLookup {
+    pub key: Key,
+    pub original_authority: Pubkey,
+    pub pda_authority: bool,
+    /// Wallet teardown hands the metadata authority to instead of the original authority
+    pub return_to: Option<Pubkey>,
+}
+
+impl FractionOriginalAuthorityLookup {
+    pub fn from_account_info(
+        a: &AccountInfo,
+    ) -> Result<FractionOriginalAuthorityLookup, ProgramError> {
+        let lookup: FractionOriginalAuthorityLookup = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionOriginalAuthorityLookupV1,
+            MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+        )?;
+
+        Ok(lookup)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionOriginalAuthorityLookup, ProgramError> {
+        let lookup: FractionOriginalAuthorityLookup = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionOriginalAuthorityLookupV1,
+            MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+        )?;
+
+        Ok(lookup)
+    }
+
+    /// Who FinalizeFractionManagerTeardown hands the metadata authority back to
+    pub fn return_authority(&self) -> Pubkey {
+        self.return_to.unwrap_or(self.original_authority)
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Copy)]
 pub struct PayoutTicket {
@@ -1039,6 +1738,394 @@ impl StoreConfig {
     }
 }
 
+/// Mints a store refuses to fractionalize, e.g. NFTs flagged as stolen.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct MintDenylist {
+    pub key: Key,
+    pub store: Pubkey,
+    /// Kept sorted so lookups can binary search
+    pub mints: Vec<Pubkey>,
+}
+
+impl MintDenylist {
+    pub fn from_account_info(a: &AccountInfo) -> Result<MintDenylist, ProgramError> {
+        let denylist: MintDenylist = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::MintDenylistV1,
+            MAX_MINT_DENYLIST_SIZE,
+        )?;
+
+        Ok(denylist)
+    }
+
+    pub fn is_denylisted(&self, mint: &Pubkey) -> bool {
+        self.mints.binary_search(mint).is_ok()
+    }
+
+    pub fn set_denylisted(&mut self, mint: Pubkey, denylisted: bool) -> ProgramResult {
+        match (self.mints.binary_search(&mint), denylisted) {
+            (Err(index), true) => {
+                if self.mints.len() >= MAX_DENYLISTED_MINTS {
+                    return Err(MetaplexError::MaxMintDenylistSizeReached.into());
+                }
+                self.mints.insert(index, mint);
+            }
+            (Ok(index), false) => {
+                self.mints.remove(index);
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Store admin settings that apply to every fraction manager in the store.
+/// Stores that never set one get the defaults.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionStoreConfig {
+    pub key: Key,
+    pub store: Pubkey,
+    /// How many managers a single vault may ever have, None for no cap
+    pub max_manager_generations: Option<u64>,
+    /// Programs whose PDAs may stay update authority of a box's metadata during validation
+    pub approved_authority_programs: Vec<Pubkey>,
+    /// Cut of every buyout, in basis points, paid to the store's fee account
+    pub buyout_fee_bps: u16,
+    /// Lamports every new manager's payer must deposit towards teardown, None for no deposit
+    pub teardown_rent_deposit: Option<u64>,
+    /// Metadata programs besides the store's own that a box's edition may live on
+    pub approved_metadata_programs: Vec<Pubkey>,
+}
+
+impl FractionStoreConfig {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionStoreConfig, ProgramError> {
+        let config: FractionStoreConfig = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionStoreConfigV1,
+            MAX_FRACTION_STORE_CONFIG_SIZE,
+        )?;
+
+        Ok(config)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionStoreConfig, ProgramError> {
+        let config: FractionStoreConfig = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionStoreConfigV1,
+            MAX_FRACTION_STORE_CONFIG_SIZE,
+        )?;
+
+        Ok(config)
+    }
+
+    /// Settings for a store that has never set any
+    pub fn new(store: Pubkey) -> FractionStoreConfig {
+        FractionStoreConfig {
+            key: Key::FractionStoreConfigV1,
+            store,
+            max_manager_generations: None,
+            approved_authority_programs: vec![],
+            buyout_fee_bps: 0,
+            teardown_rent_deposit: None,
+            approved_metadata_programs: vec![],
+        }
+    }
+
+    pub fn is_approved_authority_program(&self, program: &Pubkey) -> bool {
+        self.approved_authority_programs.contains(program)
+    }
+
+    /// The store's own metadata program is always approved
+    pub fn is_approved_metadata_program(&self, store: &Store, program: &Pubkey) -> bool {
+        store.token_metadata_program == *program
+            || self.approved_metadata_programs.contains(program)
+    }
+}
+
+/// Outlives the fraction managers of a vault so their number can be capped.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionVaultClaim {
+    pub key: Key,
+    pub vault: Pubkey,
+    /// Fraction managers ever created for the vault
+    pub manager_generations: u64,
+}
+
+impl FractionVaultClaim {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionVaultClaim, ProgramError> {
+        let claim: FractionVaultClaim = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionVaultClaimV1,
+            MAX_FRACTION_VAULT_CLAIM_SIZE,
+        )?;
+
+        Ok(claim)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionVaultClaim, ProgramError> {
+        let claim: FractionVaultClaim = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionVaultClaimV1,
+            MAX_FRACTION_VAULT_CLAIM_SIZE,
+        )?;
+
+        Ok(claim)
+    }
+}
+
+/// Audit record of a store admin returning metadata whose original authority lookup was lost.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionMetadataRescue {
+    pub key: Key,
+    pub fraction_manager: Pubkey,
+    pub metadata: Pubkey,
+    /// Wallet the admin claims was the update authority before fractionalization
+    pub claimed_original_authority: Pubkey,
+    /// Store admin that requested the rescue
+    pub admin: Pubkey,
+    /// Unix timestamp the rescue was requested at, the dispute window runs from here
+    pub requested_at: i64,
+    /// Unix timestamp update authority was returned at
+    pub executed_at: Option<i64>,
+}
+
+impl FractionMetadataRescue {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionMetadataRescue, ProgramError> {
+        let rescue: FractionMetadataRescue = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionMetadataRescueV1,
+            MAX_FRACTION_METADATA_RESCUE_SIZE,
+        )?;
+
+        Ok(rescue)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionMetadataRescue, ProgramError> {
+        let rescue: FractionMetadataRescue = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionMetadataRescueV1,
+            MAX_FRACTION_METADATA_RESCUE_SIZE,
+        )?;
+
+        Ok(rescue)
+    }
+
+    pub fn dispute_window_ends(&self) -> Result<i64, ProgramError> {
+        self.requested_at
+            .checked_add(RESCUE_DISPUTE_WINDOW)
+            .ok_or_else(|| MetaplexError::NumericalOverflowError.into())
+    }
+}
+
+/// What a creator is owed in royalties by a fraction manager, and has withdrawn so far.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionCreatorProceeds {
+    pub key: Key,
+    pub fraction_manager: Pubkey,
+    pub creator: Pubkey,
+    /// Royalties credited to the creator by buyout
+    pub total_owed: u64,
+    pub withdrawn: u64,
+}
+
+impl FractionCreatorProceeds {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionCreatorProceeds, ProgramError> {
+        let proceeds: FractionCreatorProceeds = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionCreatorProceedsV1,
+            MAX_FRACTION_CREATOR_PROCEEDS_SIZE,
+        )?;
+
+        Ok(proceeds)
+    }
+
+    /// Also accepts a freshly allocated account, for the first credit to a creator
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionCreatorProceeds, ProgramError> {
+        let proceeds: FractionCreatorProceeds = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionCreatorProceedsV1,
+            MAX_FRACTION_CREATOR_PROCEEDS_SIZE,
+        )?;
+
+        Ok(proceeds)
+    }
+
+    /// Portion of total_owed vested under the manager's schedule at `now`
+    pub fn vested(
+        &self,
+        fraction_manager: &dyn FractionManager,
+        now: i64,
+    ) -> Result<u64, ProgramError> {
+        let (start, vesting_seconds) = match fraction_manager.royalty_vesting() {
+            (Some(start), vesting_seconds) if vesting_seconds > 0 => (start, vesting_seconds),
+            _ => return Ok(self.total_owed),
+        };
+
+        let elapsed = now.saturating_sub(start).max(0) as u64;
+        if elapsed >= vesting_seconds {
+            return Ok(self.total_owed);
+        }
+
+        let vested = (self.total_owed as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(MetaplexError::NumericalOverflowError)?
+            .checked_div(vesting_seconds as u128)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+
+        Ok(vested as u64)
+    }
+
+    /// Vested royalties not yet withdrawn
+    pub fn withdrawable(
+        &self,
+        fraction_manager: &dyn FractionManager,
+        now: i64,
+    ) -> Result<u64, ProgramError> {
+        Ok(self
+            .vested(fraction_manager, now)?
+            .saturating_sub(self.withdrawn))
+    }
+}
+
+/// Proof that a depositor held an NFT in its own wallet before adding it to a vault, written
+/// once while the depositor still held it.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionDepositRecord {
+    pub key: Key,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+}
+
+impl FractionDepositRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionDepositRecord, ProgramError> {
+        let record: FractionDepositRecord = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionDepositRecordV1,
+            MAX_FRACTION_DEPOSIT_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionDepositRecord, ProgramError> {
+        let record: FractionDepositRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionDepositRecordV1,
+            MAX_FRACTION_DEPOSIT_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct CreatorSnapshot {
+    pub address: Pubkey,
+    /// Whether the creator had signed the metadata when the snapshot was taken
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// The creators of a fraction manager's NFT as they stood when it was fractionalized,
+/// so royalties follow the metadata holders were sold rather than its later edits.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct FractionCreatorSnapshot {
+    pub key: Key,
+    pub fraction_manager: Pubkey,
+    pub metadata: Pubkey,
+    pub creators: Vec<CreatorSnapshot>,
+}
+
+impl FractionCreatorSnapshot {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FractionCreatorSnapshot, ProgramError> {
+        let snapshot: FractionCreatorSnapshot = try_from_slice_initialized(
+            &a.data.borrow_mut(),
+            Key::FractionCreatorSnapshotV1,
+            MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+        )?;
+
+        Ok(snapshot)
+    }
+
+    /// Also accepts a freshly allocated account, for the instruction initializing it
+    pub fn from_account_info_or_uninitialized(
+        a: &AccountInfo,
+    ) -> Result<FractionCreatorSnapshot, ProgramError> {
+        let snapshot: FractionCreatorSnapshot = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FractionCreatorSnapshotV1,
+            MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+        )?;
+
+        Ok(snapshot)
+    }
+
+    /// Whether `address` was a verified creator when the snapshot was taken
+    pub fn is_verified_creator(&self, address: &Pubkey) -> bool {
+        self.creators
+            .iter()
+            .any(|snapshot| snapshot.verified && snapshot.address == *address)
+    }
+
+    /// Only creators verified at snapshot time may be paid, anyone else is a spoof.
+    pub fn assert_payout_verified(&self, creator: &Pubkey) -> ProgramResult {
+        if self.is_verified_creator(creator) {
+            Ok(())
+        } else {
+            Err(MetaplexError::UnverifiedCreatorPayout.into())
+        }
+    }
+
+    /// Whether the metadata's creators, their order, verification or shares, have changed
+    /// since the snapshot was taken.
+    pub fn creators_drifted(&self, live_metadata: &Metadata) -> bool {
+        let live_creators = live_metadata.data.creators.as_deref().unwrap_or(&[]);
+
+        self.creators.len() != live_creators.len()
+            || self
+                .creators
+                .iter()
+                .zip(live_creators)
+                .any(|(snapshot, live)| {
+                    snapshot.address != live.address
+                        || snapshot.verified != live.verified
+                        || snapshot.share != live.share
+                })
+    }
+}
+
+/// Return data of a creator drift report
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FractionCreatorDrift {
+    pub metadata: Pubkey,
+    pub drifted: bool,
+}
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Copy)]
 pub struct WhitelistedCreator {
@@ -1137,6 +2224,12 @@ pub struct FractionSafetyDepositConfig {
     /// safety deposit order
     pub order: u64,
     pub fraction_winning_config_type: FractionWinningConfigType,
+    /// Price the authority asks for this box, if any
+    pub fixed_price: Option<u64>,
+    /// Opt-in at creation. Immutable configs can never be updated.
+    pub immutable: bool,
+    /// When set, only this metadata update authority may validate the box, and it must sign
+    pub metadata_authority: Option<Pubkey>,
 }
 
 pub struct AmountCumulativeReturn {
@@ -1157,6 +2250,9 @@ const AMOUNT_RANGE_FIRST_EL_POSITION: usize = 48;
 const FRACTION_ORDER_POSITION: usize = 33;
 const FRACTION_MANAGER_POSITION: usize = 1;
 const FRACTION_WINNING_CONFIG_POSITION: usize = 41;
+const FRACTION_FIXED_PRICE_POSITION: usize = 42;
+const FRACTION_IMMUTABLE_POSITION: usize = 51;
+const FRACTION_METADATA_AUTHORITY_POSITION: usize = 52;
 
 fn get_number_from_data(data: &Ref<&mut [u8]>, data_type: TupleNumericType, offset: usize) -> u64 {
     return match data_type {
@@ -1590,6 +2686,34 @@ impl FractionSafetyDepositConfig {
         })
     }
 
+    pub fn get_fixed_price(a: &AccountInfo) -> Option<u64> {
+        let data = a.data.borrow();
+        match data[FRACTION_FIXED_PRICE_POSITION] {
+            0 => None,
+            _ => Some(u64::from_le_bytes(*array_ref![
+                data,
+                FRACTION_FIXED_PRICE_POSITION + 1,
+                8
+            ])),
+        }
+    }
+
+    pub fn get_immutable(a: &AccountInfo) -> bool {
+        a.data.borrow()[FRACTION_IMMUTABLE_POSITION] == 1
+    }
+
+    pub fn get_metadata_authority(a: &AccountInfo) -> Option<Pubkey> {
+        let data = a.data.borrow();
+        match data[FRACTION_METADATA_AUTHORITY_POSITION] {
+            0 => None,
+            _ => Some(Pubkey::new_from_array(*array_ref![
+                data,
+                FRACTION_METADATA_AUTHORITY_POSITION + 1,
+                32
+            ])),
+        }
+    }
+
     pub fn from_account_info(a: &AccountInfo) -> Result<FractionSafetyDepositConfig, ProgramError> {
         let data = &mut a.data.borrow();
         if a.data_len() < FRACTION_BASE_SAFETY_CONFIG_SIZE {
@@ -1607,18 +2731,35 @@ impl FractionSafetyDepositConfig {
         let fraction_winning_config_type =
             FractionSafetyDepositConfig::get_fraction_winning_config_type(a)?;
 
+        let fixed_price = FractionSafetyDepositConfig::get_fixed_price(a);
+
+        let immutable = FractionSafetyDepositConfig::get_immutable(a);
+
+        let metadata_authority = FractionSafetyDepositConfig::get_metadata_authority(a);
+
         Ok(FractionSafetyDepositConfig {
             key: Key::FractionSafetyDepositConfigV1,
             fraction_manager,
             order,
             fraction_winning_config_type,
+            fixed_price,
+            immutable,
+            metadata_authority,
         })
     }
 
     pub fn create(&self, a: &AccountInfo, auction_manager_key: &Pubkey) -> ProgramResult {
+        // Whatever path writes it, a frozen config is never rewritten
+        if a.data_len() >= FRACTION_BASE_SAFETY_CONFIG_SIZE
+            && a.data.borrow()[0] != Key::Uninitialized as u8
+            && FractionSafetyDepositConfig::get_immutable(a)
+        {
+            return Err(MetaplexError::ConfigImmutable.into());
+        }
+
         let mut data = a.data.borrow_mut();
 
-        data[0] = Key::SafetyDepositConfigV1 as u8;
+        data[0] = Key::FractionSafetyDepositConfigV1 as u8;
         // for whatever reason, copy_from_slice doesnt do jack here.
         let as_bytes = auction_manager_key.as_ref();
         for n in 0..32 {
@@ -1626,9 +2767,58 @@ impl FractionSafetyDepositConfig {
         }
         *array_mut_ref![data, FRACTION_ORDER_POSITION, 8] = self.order.to_le_bytes();
         data[FRACTION_WINNING_CONFIG_POSITION] = self.fraction_winning_config_type as u8;
+        match self.fixed_price {
+            Some(price) => {
+                data[FRACTION_FIXED_PRICE_POSITION] = 1;
+                *array_mut_ref![data, FRACTION_FIXED_PRICE_POSITION + 1, 8] = price.to_le_bytes();
+            }
+            None => {
+                data[FRACTION_FIXED_PRICE_POSITION] = 0;
+                *array_mut_ref![data, FRACTION_FIXED_PRICE_POSITION + 1, 8] = [0; 8];
+            }
+        }
+        data[FRACTION_IMMUTABLE_POSITION] = self.immutable as u8;
+        match self.metadata_authority {
+            Some(metadata_authority) => {
+                data[FRACTION_METADATA_AUTHORITY_POSITION] = 1;
+                *array_mut_ref![data, FRACTION_METADATA_AUTHORITY_POSITION + 1, 32] =
+                    metadata_authority.to_bytes();
+            }
+            None => {
+                data[FRACTION_METADATA_AUTHORITY_POSITION] = 0;
+                *array_mut_ref![data, FRACTION_METADATA_AUTHORITY_POSITION + 1, 32] = [0; 32];
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        self.create(a, &self.fraction_manager)
+    }
+
+    /// Writes the config as pre-allocated, validation then overwrites it with the real one
+    pub fn preallocate(&self, a: &AccountInfo, fraction_manager_key: &Pubkey) -> ProgramResult {
+        self.create(a, fraction_manager_key)?;
+        a.data.borrow_mut()[0] = Key::FractionSafetyDepositConfigPreallocatedV1 as u8;
 
         Ok(())
     }
+
+    /// Type declared when the config was pre-allocated, None if it never was
+    pub fn get_preallocated_type(
+        a: &AccountInfo,
+    ) -> Result<Option<FractionWinningConfigType>, ProgramError> {
+        if a.data_len() < FRACTION_BASE_SAFETY_CONFIG_SIZE
+            || a.data.borrow()[0] != Key::FractionSafetyDepositConfigPreallocatedV1 as u8
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            FractionSafetyDepositConfig::get_fraction_winning_config_type(a)?,
+        ))
+    }
 }
 
 #[repr(C)]
@@ -2,11 +2,13 @@ use crate::{
     error::MetaplexError,
     state::{
         get_auction_manager, AuctionManager, AuctionManagerStatus, BidRedemptionTicket,
-        FractionManager, Key, OriginalAuthorityLookup, Store, WhitelistedCreator, PREFIX,
+        ComputeUnitReport, FractionCreatorSnapshot, FractionManager, FractionManagerStatus,
+        FractionStoreConfig, Key, MintDenylist, OriginalAuthorityLookup, Store, WhitelistedCreator,
+        DENYLIST, FRACTION_CONFIG, PREFIX,
     },
 };
 use arrayref::array_ref;
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_auction::{
     instruction::end_auction_instruction,
     processor::{
@@ -17,24 +19,30 @@ use mpl_token_metadata::{
     instruction::update_metadata_accounts,
     state::{Metadata, EDITION},
 };
-use mpl_token_vault::{instruction::create_withdraw_tokens_instruction, state::Vault};
+use mpl_token_vault::{
+    instruction::{
+        create_combine_vault_instruction, create_set_authority_instruction,
+        create_withdraw_tokens_instruction,
+    },
+    state::{ExternalPriceAccount, Vault},
+};
 use solana_program::{
     account_info::AccountInfo,
     borsh::try_from_slice_unchecked,
     entrypoint::ProgramResult,
     log::sol_log_compute_units,
     msg,
-    program::{invoke, invoke_signed},
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
     system_instruction,
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::{
     instruction::{set_authority, AuthorityType},
-    state::Account as SplAccount,
+    state::{Account as SplAccount, Mint},
 };
 use std::convert::TryInto;
 
@@ -219,6 +227,63 @@ pub fn assert_at_least_one_fraction_creator_matches_or_store_public_and_all_veri
     Err(MetaplexError::InvalidWhitelistedCreator.into())
 }
 
+pub fn assert_mint_not_denylisted(
+    program_id: &Pubkey,
+    store_key: &Pubkey,
+    mint_denylist_info: &AccountInfo,
+    mint: &Pubkey,
+) -> ProgramResult {
+    assert_derivation(
+        program_id,
+        mint_denylist_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_key.as_ref(),
+            DENYLIST.as_bytes(),
+        ],
+    )?;
+
+    // Stores that never set up a denylist have nothing to check against.
+    if mint_denylist_info.data_is_empty() {
+        return Ok(());
+    }
+
+    assert_owned_by(mint_denylist_info, program_id)?;
+    let mint_denylist = MintDenylist::from_account_info(mint_denylist_info)?;
+    if mint_denylist.is_denylisted(mint) {
+        return Err(MetaplexError::MintDenylisted.into());
+    }
+
+    Ok(())
+}
+
+/// Loads the store's fraction config, which may be an empty account if the store never set one.
+pub fn load_fraction_store_config(
+    program_id: &Pubkey,
+    store_key: &Pubkey,
+    fraction_store_config_info: &AccountInfo,
+) -> Result<FractionStoreConfig, ProgramError> {
+    assert_derivation(
+        program_id,
+        fraction_store_config_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_key.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+    )?;
+
+    if fraction_store_config_info.data_is_empty() {
+        return Ok(FractionStoreConfig::new(*store_key));
+    }
+
+    assert_owned_by(fraction_store_config_info, program_id)?;
+
+    FractionStoreConfig::from_account_info(fraction_store_config_info)
+}
+
 pub fn assert_authority_correct(
     auction_manager_authority: &Pubkey,
     authority_info: &AccountInfo,
@@ -232,6 +297,192 @@ pub fn assert_authority_correct(
     Ok(())
 }
 
+/// A manager is only Validated once every box the vault had at init is. Anything else means
+/// state was corrupted, and validating on top of it would only corrupt it further.
+pub fn assert_fraction_manager_status_consistent(
+    fraction_manager: &dyn FractionManager,
+) -> ProgramResult {
+    if fraction_manager.status() == FractionManagerStatus::Validated
+        && fraction_manager.configs_validated() != fraction_manager.vault_token_type_count() as u64
+    {
+        return Err(MetaplexError::ManagerInvariantViolated.into());
+    }
+
+    Ok(())
+}
+
+/// Redeem and buyout paths on a fraction manager must call this before moving any funds.
+pub fn assert_fraction_redemption_not_paused(
+    fraction_manager: &dyn FractionManager,
+) -> ProgramResult {
+    if fraction_manager.redemption_paused() {
+        return Err(MetaplexError::RedemptionPaused.into());
+    }
+
+    Ok(())
+}
+
+/// Redeem and buyout paths must call this with the external price they are about to use, so
+/// participants are protected from sudden moves in it.
+pub fn assert_price_within_bounds(
+    fraction_manager: &dyn FractionManager,
+    external_price_account: &ExternalPriceAccount,
+) -> ProgramResult {
+    let price = external_price_account.price_per_share;
+    let (min_price, max_price) = fraction_manager.price_bounds();
+    if min_price.map_or(false, |min_price| price < min_price)
+        || max_price.map_or(false, |max_price| price > max_price)
+    {
+        return Err(MetaplexError::PriceOutOfBounds.into());
+    }
+
+    Ok(())
+}
+
+/// Redeem and buyout paths must call this with the price per share they settle at, whether it
+/// came from the external price account or a config's fixed price.
+pub fn assert_redemption_price_floor(
+    fraction_manager: &dyn FractionManager,
+    price_per_share: u64,
+) -> ProgramResult {
+    if price_per_share < fraction_manager.min_redemption_price_per_share() {
+        return Err(MetaplexError::PriceBelowFloor.into());
+    }
+
+    Ok(())
+}
+
+/// Teardown calls this once it has moved the manager to Closed to hand the deposit back to whoever paid it
+/// at init. The manager is owned by this program, so the lamports move without a system
+/// transfer. Saving the manager is left to the caller, which usually closes it right after.
+pub fn refund_prepaid_teardown_rent(
+    fraction_manager: &mut dyn FractionManager,
+    fraction_manager_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+) -> ProgramResult {
+    if fraction_manager.status() != FractionManagerStatus::Closed {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    if *recipient_info.key != fraction_manager.teardown_rent_payer() {
+        return Err(MetaplexError::TeardownRentPayerMismatch.into());
+    }
+
+    let refund = fraction_manager.prepaid_teardown_rent();
+    **fraction_manager_info.lamports.borrow_mut() = fraction_manager_info
+        .lamports()
+        .checked_sub(refund)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    **recipient_info.lamports.borrow_mut() = recipient_info
+        .lamports()
+        .checked_add(refund)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    fraction_manager.set_prepaid_teardown_rent(0);
+    fraction_manager.add_rent_reclaimed(refund)?;
+
+    Ok(())
+}
+
+/// Splits buyout proceeds into the store's fee and what is left for fraction holders. Buyout
+/// paths must pay the fee to the store's fee account before distributing the rest. The fee is
+/// rounded up so dust goes to the store rather than being lost between holders.
+pub fn split_buyout_proceeds(
+    fraction_store_config: &FractionStoreConfig,
+    amount: u64,
+) -> Result<(u64, u64), ProgramError> {
+    let fee = (amount as u128)
+        .checked_mul(fraction_store_config.buyout_fee_bps as u128)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_add(9999)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_div(10000)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    let fee: u64 = fee
+        .try_into()
+        .map_err(|_| MetaplexError::NumericalOverflowError)?;
+    let remaining = amount
+        .checked_sub(fee)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    Ok((fee, remaining))
+}
+
+/// Redeem paths must call this with the redeeming wallet. While the manager's creator priority
+/// window is open only creators verified in the fractionalized NFT's creator snapshot may
+/// redeem, so creators can't be added to the live metadata to jump the queue.
+pub fn assert_creator_priority_window(
+    fraction_manager: &dyn FractionManager,
+    creator_snapshot: &FractionCreatorSnapshot,
+    redeemer: &Pubkey,
+    clock_info: &AccountInfo,
+) -> ProgramResult {
+    if let Some(creator_priority_until) = fraction_manager.creator_priority_until() {
+        let clock = Clock::from_account_info(clock_info)?;
+        if clock.unix_timestamp >= creator_priority_until {
+            return Ok(());
+        }
+
+        if !creator_snapshot.is_verified_creator(redeemer) {
+            return Err(MetaplexError::CreatorPriorityWindowActive.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_pricing_lookup_matches(vault: &Vault, external_pricing: &Pubkey) -> ProgramResult {
+    if vault.pricing_lookup_address != *external_pricing {
+        return Err(MetaplexError::PricingLookupMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Compute units left in the transaction, only known on runtimes with the syscall.
+pub fn remaining_compute_units() -> Option<u64> {
+    #[cfg(all(target_arch = "bpf", feature = "remaining-compute-units"))]
+    {
+        extern "C" {
+            fn sol_remaining_compute_units() -> u64;
+        }
+
+        Some(unsafe { sol_remaining_compute_units() })
+    }
+
+    #[cfg(not(all(target_arch = "bpf", feature = "remaining-compute-units")))]
+    None
+}
+
+/// Sets return data to the compute consumed since `start`, a prior `remaining_compute_units`.
+pub fn report_compute_units(start: Option<u64>) -> ProgramResult {
+    report_compute_units_between(start, remaining_compute_units())
+}
+
+/// Sets return data to the compute consumed between two `remaining_compute_units` readings.
+pub fn report_compute_units_between(start: Option<u64>, end: Option<u64>) -> ProgramResult {
+    let consumed = match (start, end) {
+        (Some(start), Some(end)) => start.checked_sub(end),
+        _ => None,
+    };
+
+    set_return_data(&ComputeUnitReport { consumed }.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Lets clients put an expiry on an instruction by naming the last slot it may land in.
+pub fn assert_slot_not_past(clock_info: &AccountInfo, max_slot: Option<u64>) -> ProgramResult {
+    if let Some(max_slot) = max_slot {
+        let clock = Clock::from_account_info(clock_info)?;
+        if clock.slot > max_slot {
+            return Err(MetaplexError::TransactionTooOld.into());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn assert_auction_is_ended_or_valid_instant_sale(
     auction_info: &AccountInfo,
     auction_extended_info: Option<&AccountInfo>,
@@ -355,6 +606,83 @@ pub fn transfer_safety_deposit_box_items<'a>(
     Ok(())
 }
 
+/// Combines the vault as its authority, handing the vault to `new_authority`. Whatever the
+/// vault's circulating shares are worth at the external price is paid by `payment`, and the
+/// shares in `outstanding_shares` are burned, both on the say of `transfer_authority`.
+#[allow(clippy::too_many_arguments)]
+pub fn combine_vault<'a>(
+    token_vault_program: AccountInfo<'a>,
+    vault: AccountInfo<'a>,
+    outstanding_shares: AccountInfo<'a>,
+    payment: AccountInfo<'a>,
+    fraction_mint: AccountInfo<'a>,
+    fraction_treasury: AccountInfo<'a>,
+    redeem_treasury: AccountInfo<'a>,
+    new_authority: AccountInfo<'a>,
+    vault_authority: AccountInfo<'a>,
+    transfer_authority: AccountInfo<'a>,
+    burn_authority: AccountInfo<'a>,
+    external_pricing: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &create_combine_vault_instruction(
+            *token_vault_program.key,
+            *vault.key,
+            *outstanding_shares.key,
+            *payment.key,
+            *fraction_mint.key,
+            *fraction_treasury.key,
+            *redeem_treasury.key,
+            *new_authority.key,
+            *vault_authority.key,
+            *transfer_authority.key,
+            *burn_authority.key,
+            *external_pricing.key,
+        ),
+        &[
+            token_vault_program,
+            vault,
+            outstanding_shares,
+            payment,
+            fraction_mint,
+            fraction_treasury,
+            redeem_treasury,
+            new_authority,
+            vault_authority,
+            transfer_authority,
+            burn_authority,
+            external_pricing,
+            token_program,
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn transfer_vault_authority<'a>(
+    token_vault_program: AccountInfo<'a>,
+    vault: AccountInfo<'a>,
+    current_authority: AccountInfo<'a>,
+    new_authority: AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &create_set_authority_instruction(
+            *token_vault_program.key,
+            *vault.key,
+            *current_authority.key,
+            *new_authority.key,
+        ),
+        &[token_vault_program, vault, current_authority, new_authority],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn transfer_metadata_ownership<'a>(
     token_metadata_program: AccountInfo<'a>,
     metadata_info: AccountInfo<'a>,
@@ -901,6 +1229,16 @@ pub fn spl_token_transfer<'a: 'b, 'b>(
     result.map_err(|_| MetaplexError::TokenTransferFailed.into())
 }
 
+/// One check for everything that must hold a single NFT: a mint with no decimals and a supply
+/// of one, all of which sits in the token store.
+pub fn assert_is_genuine_nft(mint: &Mint, token_store: &SplAccount) -> ProgramResult {
+    if mint.decimals != 0 || mint.supply != 1 || token_store.amount != 1 {
+        return Err(MetaplexError::NotAGenuineNft.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_edition_valid(
     program_id: &Pubkey,
     mint: &Pubkey,
@@ -945,6 +1283,67 @@ pub fn spl_token_mint_to<'a: 'b, 'b>(
     result.map_err(|_| MetaplexError::TokenMintToFailed.into())
 }
 
+pub fn spl_token_burn<'a: 'b, 'b>(
+    source: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    amount: u64,
+    authority: AccountInfo<'a>,
+    authority_signer_seeds: &'b [&'b [u8]],
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    let result = invoke_signed(
+        &spl_token::instruction::burn(
+            token_program.key,
+            source.key,
+            mint.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[source, mint, authority, token_program],
+        &[authority_signer_seeds],
+    );
+    result.map_err(|_| MetaplexError::TokenBurnFailed.into())
+}
+
+pub fn spl_token_close_account<'a: 'b, 'b>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    authority_signer_seeds: &'b [&'b [u8]],
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    let result = invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[account, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+    result.map_err(|_| MetaplexError::CloseAccountFailed.into())
+}
+
+/// Closes an account owned by this program, moving its lamports to `recipient` and zeroing its
+/// data so nothing reads it again before the runtime collects it. Returns the lamports moved.
+pub fn close_program_account(
+    account_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let lamports = account_info.lamports();
+    **account_info.lamports.borrow_mut() = 0;
+    **recipient_info.lamports.borrow_mut() = recipient_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    account_info.data.borrow_mut().fill(0);
+
+    Ok(lamports)
+}
+
 pub fn assert_derivation(
     program_id: &Pubkey,
     account: &AccountInfo,
@@ -973,6 +1372,19 @@ pub fn try_from_slice_checked<T: BorshDeserialize>(
     Ok(result)
 }
 
+/// Like try_from_slice_checked, but also rejects a buffer that was never initialized
+pub fn try_from_slice_initialized<T: BorshDeserialize>(
+    data: &[u8],
+    data_type: Key,
+    data_size: usize,
+) -> Result<T, ProgramError> {
+    if data.is_empty() || data[0] != data_type as u8 {
+        return Err(MetaplexError::DataTypeMismatch.into());
+    }
+
+    try_from_slice_checked(data, data_type, data_size)
+}
+
 pub fn end_auction<'a: 'b, 'b>(
     resource: Pubkey,
     auction: AccountInfo<'a>,
@@ -0,0 +1,398 @@
+mod utils;
+
+use borsh::BorshDeserialize;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    instruction::FractionShareAllocation,
+    processor::{
+        batch_mint_fraction_shares::process_batch_mint_fraction_shares,
+        set_fraction_supply_caps::process_set_fraction_supply_caps,
+    },
+    state::{
+        FractionManager, FractionManagerStatus, FractionManagerV1, FractionShareBatchMinted, Key,
+        Store, FRACTION_MINT, MAX_BATCH_MINT_RECIPIENTS, MAX_STORE_SIZE, PREFIX,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::get_return_data,
+    program_option::COption, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use utils::*;
+
+const INITIAL_SUPPLY: u64 = 100;
+
+struct BatchFixture {
+    program_id: Pubkey,
+    fraction_manager: TestAccount,
+    authority: TestAccount,
+    fraction_mint: TestAccount,
+    store: TestAccount,
+}
+
+impl BatchFixture {
+    /// A validated manager whose fraction mint has INITIAL_SUPPLY shares out
+    fn new() -> BatchFixture {
+        let program_id = id();
+        let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+
+        let mut store = TestAccount::new(
+            program_id,
+            account_data(
+                &Store {
+                    key: Key::StoreV1,
+                    public: true,
+                    auction_program: Pubkey::new_unique(),
+                    token_vault_program: mpl_token_vault::id(),
+                    token_metadata_program: mpl_token_metadata::id(),
+                    token_program: spl_token::id(),
+                },
+                MAX_STORE_SIZE,
+            ),
+        );
+        store.key = Pubkey::new_unique();
+
+        let mut fraction_manager_state =
+            make_fraction_manager(authority.key, FractionManagerStatus::Validated);
+        fraction_manager_state.store = store.key;
+        let mut fraction_manager =
+            TestAccount::new(program_id, fraction_manager_data(&fraction_manager_state));
+        fraction_manager.key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), fraction_manager_state.vault.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let mut fraction_mint = TestAccount::new(
+            spl_token::id(),
+            packed_data(Mint {
+                mint_authority: COption::Some(fraction_manager.key),
+                supply: INITIAL_SUPPLY,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: COption::Some(fraction_manager.key),
+            }),
+        );
+        fraction_mint.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.key.as_ref(),
+                FRACTION_MINT.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+        fraction_manager_state.state.fraction_mint = Some(fraction_mint.key);
+        fraction_manager.data = fraction_manager_data(&fraction_manager_state);
+
+        BatchFixture {
+            program_id,
+            fraction_manager,
+            authority,
+            fraction_mint,
+            store,
+        }
+    }
+
+    /// The associated token account of the fraction mint held by `owner`
+    fn token_account(&self, owner: Pubkey, amount: u64) -> TestAccount {
+        TestAccount {
+            key: get_associated_token_address(&owner, &self.fraction_mint.key),
+            ..self.other_token_account(owner, amount)
+        }
+    }
+
+    /// A token account of the fraction mint held by `owner` that isn't its associated one
+    fn other_token_account(&self, owner: Pubkey, amount: u64) -> TestAccount {
+        TestAccount::new(
+            spl_token::id(),
+            packed_data(TokenAccount {
+                mint: self.fraction_mint.key,
+                owner,
+                amount,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            }),
+        )
+    }
+
+    fn set_caps(
+        &mut self,
+        max_fraction_supply: Option<u64>,
+        max_fraction_shares_per_wallet: Option<u64>,
+    ) {
+        process_set_fraction_supply_caps(
+            &self.program_id,
+            &[self.fraction_manager.info(), self.authority.info()],
+            max_fraction_supply,
+            max_fraction_shares_per_wallet,
+        )
+        .unwrap();
+    }
+
+    /// Mints `amount` to the wallet owning each token account
+    fn batch_mint(&mut self, recipients: &mut [(&mut TestAccount, u64)]) -> ProgramResult {
+        let mut token_program = TestAccount::wallet(spl_token::id(), false);
+        let allocations = recipients
+            .iter()
+            .map(|(token_account, amount)| FractionShareAllocation {
+                recipient: token_owner(token_account),
+                amount: *amount,
+            })
+            .collect();
+
+        let mut infos: Vec<AccountInfo> = vec![
+            self.fraction_manager.info(),
+            self.authority.info(),
+            self.fraction_mint.info(),
+            self.store.info(),
+            token_program.info(),
+        ];
+        infos.extend(
+            recipients
+                .iter_mut()
+                .map(|(token_account, _)| token_account.info()),
+        );
+
+        process_batch_mint_fraction_shares(&self.program_id, &infos, allocations)
+    }
+
+    fn total_fractions_minted(&self) -> u64 {
+        self.fraction_manager
+            .read::<FractionManagerV1>()
+            .total_fractions_minted()
+    }
+}
+
+fn token_owner(token_account: &TestAccount) -> Pubkey {
+    TokenAccount::unpack(&token_account.data).unwrap().owner
+}
+
+#[test]
+fn test_distributes_to_many_recipients() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(Some(INITIAL_SUPPLY + 60), Some(30));
+    let mut first = fixture.token_account(Pubkey::new_unique(), 0);
+    let mut second = fixture.token_account(Pubkey::new_unique(), 10);
+    let mut third = fixture.token_account(Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut first, 30), (&mut second, 20), (&mut third, 10)]),
+        Ok(())
+    );
+    assert_eq!(fixture.total_fractions_minted(), 60);
+}
+
+#[test]
+fn test_uncapped() {
+    let mut fixture = BatchFixture::new();
+    let mut first = fixture.token_account(Pubkey::new_unique(), 0);
+    let mut second = fixture.token_account(Pubkey::new_unique(), u64::MAX / 2);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut first, 1_000), (&mut second, 1_000)]),
+        Ok(())
+    );
+    assert_eq!(fixture.total_fractions_minted(), 2_000);
+}
+
+#[test]
+fn test_wallet_cap_violated_mid_batch() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(None, Some(30));
+    let wallet = Pubkey::new_unique();
+    let mut first = fixture.token_account(wallet, 5);
+    let mut other = fixture.token_account(Pubkey::new_unique(), 0);
+    let mut second = fixture.token_account(wallet, 5);
+
+    // The wallet ends up holding 5 + 20 + 10
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut first, 20), (&mut other, 30), (&mut second, 10)]),
+        Err(MetaplexError::FractionWalletCapExceeded.into())
+    );
+    assert_eq!(fixture.total_fractions_minted(), 0);
+}
+
+#[test]
+fn test_wallet_cap_counts_earlier_batches() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(None, Some(30));
+    let wallet = Pubkey::new_unique();
+
+    // What earlier batches minted is still in the wallet's associated token account
+    let mut token_account = fixture.token_account(wallet, 25);
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 6)]),
+        Err(MetaplexError::FractionWalletCapExceeded.into())
+    );
+    assert_eq!(fixture.batch_mint(&mut [(&mut token_account, 5)]), Ok(()));
+}
+
+#[test]
+fn test_wallet_cap_not_split_across_token_accounts() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(None, Some(30));
+    let wallet = Pubkey::new_unique();
+    let mut token_account = fixture.token_account(wallet, 30);
+    let mut fresh = fixture.other_token_account(wallet, 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut fresh, 30)]),
+        Err(MetaplexError::FractionShareRecipientMismatch.into())
+    );
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 1)]),
+        Err(MetaplexError::FractionWalletCapExceeded.into())
+    );
+}
+
+#[test]
+fn test_wallet_cap_counts_repeat_allocations() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(None, Some(30));
+    let wallet = Pubkey::new_unique();
+    let mut token_account = fixture.token_account(wallet, 10);
+    let mut again = TestAccount {
+        key: token_account.key,
+        ..fixture.token_account(wallet, 10)
+    };
+
+    // The existing balance of an account only counts once
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 10), (&mut again, 10)]),
+        Ok(())
+    );
+
+    let mut token_account = fixture.token_account(wallet, 10);
+    let mut again = TestAccount {
+        key: token_account.key,
+        ..fixture.token_account(wallet, 10)
+    };
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 10), (&mut again, 11)]),
+        Err(MetaplexError::FractionWalletCapExceeded.into())
+    );
+}
+
+#[test]
+fn test_supply_cap_violated_mid_batch() {
+    let mut fixture = BatchFixture::new();
+    fixture.set_caps(Some(INITIAL_SUPPLY + 50), None);
+    let mut first = fixture.token_account(Pubkey::new_unique(), 0);
+    let mut second = fixture.token_account(Pubkey::new_unique(), 0);
+    let mut third = fixture.token_account(Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut first, 25), (&mut second, 25), (&mut third, 1)]),
+        Err(MetaplexError::FractionSupplyCapExceeded.into())
+    );
+    assert_eq!(fixture.total_fractions_minted(), 0);
+}
+
+#[test]
+fn test_recipient_must_own_token_account() {
+    let mut fixture = BatchFixture::new();
+    let recipient = Pubkey::new_unique();
+    let mut token_account = TestAccount {
+        key: get_associated_token_address(&recipient, &fixture.fraction_mint.key),
+        ..fixture.token_account(Pubkey::new_unique(), 0)
+    };
+    let mut token_program = TestAccount::wallet(spl_token::id(), false);
+
+    assert_eq!(
+        process_batch_mint_fraction_shares(
+            &fixture.program_id,
+            &[
+                fixture.fraction_manager.info(),
+                fixture.authority.info(),
+                fixture.fraction_mint.info(),
+                fixture.store.info(),
+                token_program.info(),
+                token_account.info(),
+            ],
+            vec![FractionShareAllocation {
+                recipient,
+                amount: 1,
+            }],
+        ),
+        Err(MetaplexError::FractionShareRecipientMismatch.into())
+    );
+}
+
+#[test]
+fn test_mint_must_be_managers() {
+    let mut fixture = BatchFixture::new();
+    let mut state: FractionManagerV1 = fixture.fraction_manager.read();
+    state.state.fraction_mint = None;
+    fixture.fraction_manager.data = fraction_manager_data(&state);
+    let mut token_account = fixture.token_account(Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 1)]),
+        Err(MetaplexError::FractionMintMismatch.into())
+    );
+}
+
+#[test]
+fn test_requires_validated_manager() {
+    let mut fixture = BatchFixture::new();
+    let mut state: FractionManagerV1 = fixture.fraction_manager.read();
+    state.state.status = FractionManagerStatus::Initialized;
+    fixture.fraction_manager.data = fraction_manager_data(&state);
+    let mut token_account = fixture.token_account(Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 1)]),
+        Err(MetaplexError::InvalidStatus.into())
+    );
+}
+
+#[test]
+fn test_rejects_redeemable_manager() {
+    let mut fixture = BatchFixture::new();
+    let mut state: FractionManagerV1 = fixture.fraction_manager.read();
+    state.state.status = FractionManagerStatus::Redeemable;
+    fixture.fraction_manager.data = fraction_manager_data(&state);
+    let mut token_account = fixture.token_account(Pubkey::new_unique(), 0);
+
+    assert_eq!(
+        fixture.batch_mint(&mut [(&mut token_account, 1)]),
+        Err(MetaplexError::InvalidStatus.into())
+    );
+    assert_eq!(fixture.total_fractions_minted(), 0);
+}
+
+#[test]
+fn test_batch_ends_at_recipient_cap() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BatchFixture::new();
+    let mut token_accounts: Vec<TestAccount> = (0..MAX_BATCH_MINT_RECIPIENTS + 2)
+        .map(|_| fixture.token_account(Pubkey::new_unique(), 0))
+        .collect();
+    let mut recipients: Vec<(&mut TestAccount, u64)> = token_accounts
+        .iter_mut()
+        .map(|token_account| (token_account, 10))
+        .collect();
+
+    assert_eq!(fixture.batch_mint(&mut recipients), Ok(()));
+
+    let (_, data) = get_return_data().unwrap();
+    assert_eq!(
+        FractionShareBatchMinted::try_from_slice(&data).unwrap(),
+        FractionShareBatchMinted {
+            recipients_minted: MAX_BATCH_MINT_RECIPIENTS as u64,
+            amount_minted: 10 * MAX_BATCH_MINT_RECIPIENTS as u64,
+        }
+    );
+    assert_eq!(
+        fixture.total_fractions_minted(),
+        10 * MAX_BATCH_MINT_RECIPIENTS as u64
+    );
+}
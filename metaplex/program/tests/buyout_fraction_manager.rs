@@ -0,0 +1,603 @@
+mod utils;
+
+use borsh::BorshSerialize;
+use mpl_metaplex::{
+    error::MetaplexError,
+    processor::{
+        set_price_bounds::process_set_price_bounds,
+        set_redemption_paused::process_set_redemption_paused,
+        withdraw_creator_proceeds::process_withdraw_creator_proceeds,
+    },
+    state::{
+        CreatorSnapshot, FractionCreatorProceeds, FractionManagerStatus, FractionManagerV1,
+        FractionStoreConfig, CREATORS, MAX_FRACTION_STORE_CONFIG_SIZE, PREFIX,
+    },
+};
+use mpl_token_vault::instruction::VaultInstruction;
+use solana_program::{
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    sysvar::{self, clock::Clock},
+};
+use spl_token::instruction::TokenInstruction;
+use spl_token::state::{Account as TokenAccount, AccountState};
+use utils::*;
+
+const FLOOR: u64 = 1_000;
+const WINDOW_CLOSES: i64 = 1_000;
+
+/// Toggles the pause through SetRedemptionPaused, as the authority
+fn set_paused(fixture: &mut BuyoutFixture, paused: bool) -> ProgramResult {
+    fixture.sync();
+    let init = &mut fixture.init;
+    process_set_redemption_paused(
+        &init.program_id,
+        &[init.fraction_manager.info(), init.authority.info()],
+        paused,
+    )?;
+    fixture.fraction_manager_state = init.fraction_manager.read::<FractionManagerV1>();
+
+    Ok(())
+}
+
+/// Bounds the external price through SetPriceBounds, as the authority
+fn set_price_bounds(
+    fixture: &mut BuyoutFixture,
+    min_price: Option<u64>,
+    max_price: Option<u64>,
+) -> ProgramResult {
+    fixture.sync();
+    let init = &mut fixture.init;
+    process_set_price_bounds(
+        &init.program_id,
+        &[init.fraction_manager.info(), init.authority.info()],
+        min_price,
+        max_price,
+    )?;
+    fixture.fraction_manager_state = init.fraction_manager.read::<FractionManagerV1>();
+
+    Ok(())
+}
+
+/// Buys out a single share of a manager bounded to `bounds` at `price_per_share`
+fn buyout_priced_at(bounds: (Option<u64>, Option<u64>), price_per_share: u64) -> ProgramResult {
+    let mut fixture = BuyoutFixture::new();
+    set_price_bounds(&mut fixture, bounds.0, bounds.1)?;
+    fixture.init.external_price_account_state.price_per_share = price_per_share;
+    fixture.fraction_mint_state.supply = 1;
+
+    fixture.process_buyout().map(|_| ())
+}
+
+/// Buys out a single share at `price_per_share` from a manager with a redemption floor of FLOOR
+fn buyout_floored_at(price_per_share: u64, bounds: (Option<u64>, Option<u64>)) -> ProgramResult {
+    let mut fixture = BuyoutFixture::new();
+    fixture
+        .fraction_manager_state
+        .state
+        .min_redemption_price_per_share = FLOOR;
+    set_price_bounds(&mut fixture, bounds.0, bounds.1)?;
+    fixture.init.external_price_account_state.price_per_share = price_per_share;
+    fixture.fraction_mint_state.supply = 1;
+
+    fixture.process_buyout().map(|_| ())
+}
+
+/// Charges `buyout_fee_bps` on the fixture's buyouts through the store's fraction config
+fn charge_buyout_fee(fixture: &mut BuyoutFixture, buyout_fee_bps: u16) {
+    let mut config = FractionStoreConfig::new(fixture.init.store.key);
+    config.buyout_fee_bps = buyout_fee_bps;
+    fixture.init.fraction_store_config.owner = fixture.init.program_id;
+    fixture.init.fraction_store_config.data = account_data(&config, MAX_FRACTION_STORE_CONFIG_SIZE);
+}
+
+/// Buys out at `now` as `buyer`, with creators getting priority until WINDOW_CLOSES
+fn buyout_in_window(fixture: &mut BuyoutFixture, buyer: Pubkey, now: i64) -> ProgramResult {
+    fixture.fraction_manager_state.state.creator_priority_until = Some(WINDOW_CLOSES);
+    fixture.buyer.key = buyer;
+    fixture.now = now;
+
+    fixture.process_buyout().map(|_| ())
+}
+
+/// Amount and destination of every token transfer invoked, in order
+fn transfers(stubs: &ProgramStubs) -> Vec<(u64, Pubkey)> {
+    stubs
+        .invoked()
+        .iter()
+        .filter(|instruction| instruction.program_id == spl_token::id())
+        .map(
+            |instruction| match TokenInstruction::unpack(&instruction.data).unwrap() {
+                TokenInstruction::Transfer { amount } => (amount, instruction.accounts[1].pubkey),
+                other => panic!("unexpected token instruction {:?}", other),
+            },
+        )
+        .collect()
+}
+
+#[test]
+fn test_buyout() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+
+    let manager = fixture.process_buyout().unwrap();
+
+    assert_eq!(manager.state.status, FractionManagerStatus::Combined);
+    assert_eq!(
+        transfers(&installed.stubs),
+        vec![(fixture.price(), fixture.init.accept_payment.key)]
+    );
+    // No royalties at the fixture's seller fee, so all of it is the holders'
+    assert_eq!(manager.state.buyout_holder_proceeds, fixture.price());
+    assert_eq!(
+        manager.state.buyout_shares,
+        fixture.fraction_mint_state.supply
+    );
+}
+
+#[test]
+fn test_buyout_combines_vault_to_buyer() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+
+    fixture.process_buyout().unwrap();
+
+    let combines: Vec<_> = installed
+        .stubs
+        .invoked()
+        .into_iter()
+        .filter(|instruction| instruction.program_id == mpl_token_vault::id())
+        .collect();
+    assert_eq!(combines.len(), 1);
+    let combine = &combines[0];
+    assert_eq!(
+        combine.data,
+        VaultInstruction::CombineVault.try_to_vec().unwrap()
+    );
+    assert_eq!(combine.accounts[0].pubkey, fixture.init.vault.key);
+    assert_eq!(combine.accounts[2].pubkey, fixture.buyer_token_account.key);
+    // The buyer is the vault's new authority, on the say of the manager
+    assert_eq!(combine.accounts[6].pubkey, fixture.buyer.key);
+    assert_eq!(
+        combine.accounts[7].pubkey,
+        fixture.init.fraction_manager.key
+    );
+    assert!(combine.accounts[7].is_signer);
+    assert_eq!(
+        combine.accounts[9].pubkey,
+        vault_authority(&fixture.init.vault.key)
+    );
+}
+
+#[test]
+fn test_buyout_needs_vault_authority() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.init.vault_state.authority = fixture.init.authority.key;
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::FractionVaultAuthorityMismatch.into())
+    );
+    assert_eq!(installed.stubs.invoked(), vec![]);
+}
+
+#[test]
+fn test_buyout_of_vault_fraction_mint() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_manager_state.state.fraction_mint = None;
+    fixture.fraction_mint.key = fixture.init.vault_state.fraction_mint;
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::BuyoutRequiresManagerFractionMint.into())
+    );
+}
+
+#[test]
+fn test_buyout_of_no_shares() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_mint_state.supply = 0;
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::NoFractionShares.into())
+    );
+    assert_eq!(installed.stubs.invoked(), vec![]);
+}
+
+#[test]
+fn test_buyout_requires_redeemable() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_manager_state.state.status = FractionManagerStatus::Validated;
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::InvalidStatus.into())
+    );
+}
+
+#[test]
+fn test_paused_buyout_reverts_until_resumed() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+
+    set_paused(&mut fixture, true).unwrap();
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::RedemptionPaused.into())
+    );
+    assert_eq!(transfers(&installed.stubs), vec![]);
+
+    set_paused(&mut fixture, false).unwrap();
+    let manager = fixture.process_buyout().unwrap();
+    assert_eq!(manager.state.status, FractionManagerStatus::Combined);
+}
+
+#[test]
+fn test_buyout_of_other_fraction_mint() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_manager_state.state.fraction_mint = Some(Pubkey::new_unique());
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::FractionMintMismatch.into())
+    );
+}
+
+#[test]
+fn test_buyout_in_price_bounds() {
+    let _stubs = ProgramStubs::install();
+
+    for price in [100, 150, 200] {
+        assert_eq!(buyout_priced_at((Some(100), Some(200)), price), Ok(()));
+    }
+}
+
+#[test]
+fn test_buyout_out_of_price_bounds() {
+    let installed = ProgramStubs::install();
+
+    for price in [0, 99, 201, u64::MAX] {
+        assert_eq!(
+            buyout_priced_at((Some(100), Some(200)), price),
+            Err(MetaplexError::PriceOutOfBounds.into())
+        );
+    }
+    assert_eq!(transfers(&installed.stubs), vec![]);
+}
+
+#[test]
+fn test_buyout_one_sided_and_unbounded() {
+    let _stubs = ProgramStubs::install();
+
+    assert_eq!(buyout_priced_at((None, None), 0), Ok(()));
+    assert_eq!(
+        buyout_priced_at((Some(100), None), 99),
+        Err(MetaplexError::PriceOutOfBounds.into())
+    );
+    assert_eq!(buyout_priced_at((Some(100), None), u64::MAX), Ok(()));
+}
+
+#[test]
+fn test_buyout_at_activation_price() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    set_price_bounds(&mut fixture, Some(100), Some(200)).unwrap();
+    fixture.fraction_manager_state.state.fixed_price_per_share = Some(50);
+    fixture.init.external_price_account_state.price_per_share = 1_000;
+    fixture.fraction_mint_state.supply = 2;
+
+    // The activation price stands in for the external price and its bounds
+    fixture.process_buyout().unwrap();
+    assert_eq!(
+        transfers(&installed.stubs),
+        vec![(100, fixture.init.accept_payment.key)]
+    );
+}
+
+#[test]
+fn test_buyout_at_floor() {
+    let _stubs = ProgramStubs::install();
+
+    assert_eq!(buyout_floored_at(FLOOR, (None, None)), Ok(()));
+}
+
+#[test]
+fn test_buyout_below_floor() {
+    let installed = ProgramStubs::install();
+
+    for price in [0, FLOOR - 1] {
+        assert_eq!(
+            buyout_floored_at(price, (None, None)),
+            Err(MetaplexError::PriceBelowFloor.into())
+        );
+    }
+    assert_eq!(transfers(&installed.stubs), vec![]);
+}
+
+#[test]
+fn test_buyout_above_floor() {
+    let _stubs = ProgramStubs::install();
+
+    assert_eq!(buyout_floored_at(FLOOR + 1, (None, None)), Ok(()));
+}
+
+#[test]
+fn test_floor_binds_inside_price_bounds() {
+    let _stubs = ProgramStubs::install();
+
+    // Bounds wide enough to admit a zero price don't lower the floor
+    assert_eq!(
+        buyout_floored_at(0, (Some(0), None)),
+        Err(MetaplexError::PriceBelowFloor.into())
+    );
+}
+
+#[test]
+fn test_buyout_fee_to_store() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    charge_buyout_fee(&mut fixture, 250);
+    fixture.init.external_price_account_state.price_per_share = 10;
+    fixture.fraction_mint_state.supply = 200_000;
+
+    fixture.process_buyout().unwrap();
+
+    assert_eq!(
+        transfers(&installed.stubs),
+        vec![
+            (50_000, fixture.store_fee_account.key),
+            (1_950_000, fixture.init.accept_payment.key),
+        ]
+    );
+}
+
+#[test]
+fn test_buyout_fee_rounds_to_store() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    charge_buyout_fee(&mut fixture, 250);
+    // 2.5% of 1_000_001 is 25_000.025
+    fixture.init.external_price_account_state.price_per_share = 1;
+    fixture.fraction_mint_state.supply = 1_000_001;
+
+    fixture.process_buyout().unwrap();
+
+    assert_eq!(
+        transfers(&installed.stubs),
+        vec![
+            (25_001, fixture.store_fee_account.key),
+            (975_000, fixture.init.accept_payment.key),
+        ]
+    );
+}
+
+#[test]
+fn test_buyout_fee_only_to_store_admin() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    charge_buyout_fee(&mut fixture, 250);
+    fixture.store_admin = TestAccount::wallet(Pubkey::new_unique(), false);
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::DerivedKeyInvalid.into())
+    );
+
+    // The admin is right but the fee account is someone else's
+    let mut fixture = BuyoutFixture::new();
+    charge_buyout_fee(&mut fixture, 250);
+    fixture.store_fee_account.data = fixture.buyer_token_account.data.clone();
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(MetaplexError::StoreFeeAccountMismatch.into())
+    );
+    assert_eq!(transfers(&installed.stubs), vec![]);
+}
+
+#[test]
+fn test_creator_buys_out_early() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    let creator = fixture.creator;
+
+    assert_eq!(buyout_in_window(&mut fixture, creator, 0), Ok(()));
+}
+
+#[test]
+fn test_non_creator_blocked_then_allowed() {
+    let _stubs = ProgramStubs::install();
+    let holder = Pubkey::new_unique();
+
+    let mut fixture = BuyoutFixture::new();
+    assert_eq!(
+        buyout_in_window(&mut fixture, holder, WINDOW_CLOSES - 1),
+        Err(MetaplexError::CreatorPriorityWindowActive.into())
+    );
+
+    let mut fixture = BuyoutFixture::new();
+    assert_eq!(
+        buyout_in_window(&mut fixture, holder, WINDOW_CLOSES),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_unverified_creator_blocked() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.creator_snapshot_state.creators[0].verified = false;
+    let creator = fixture.creator;
+
+    assert_eq!(
+        buyout_in_window(&mut fixture, creator, 0),
+        Err(MetaplexError::CreatorPriorityWindowActive.into())
+    );
+}
+
+#[test]
+fn test_priority_follows_snapshot() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    // Snapshotted for another manager, so it can't vouch for this one's creators
+    let other_manager = Pubkey::new_unique();
+    fixture.creator_snapshot_state.fraction_manager = other_manager;
+    fixture.creator_snapshot.key = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            fixture.init.program_id.as_ref(),
+            other_manager.as_ref(),
+            fixture.creator_snapshot_state.metadata.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+        &fixture.init.program_id,
+    )
+    .0;
+    let creator = fixture.creator;
+
+    assert_eq!(
+        buyout_in_window(&mut fixture, creator, 0),
+        Err(MetaplexError::DerivedKeyInvalid.into())
+    );
+}
+
+#[test]
+fn test_no_window() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+
+    assert!(fixture.process_buyout().is_ok());
+}
+
+#[test]
+fn test_no_window_or_snapshot_needs_no_creators() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_manager_state.state.creators_snapshotted = false;
+    fixture.metadata_state.data.seller_fee_basis_points = 1_000;
+    // Never read, so it needn't be a snapshot at all
+    fixture.creator_snapshot.owner = Pubkey::new_unique();
+
+    let manager = fixture.process_buyout().unwrap();
+    assert_eq!(manager.state.buyout_holder_proceeds, fixture.price());
+    assert_eq!(fixture.royalties_owed(), vec![0]);
+}
+
+#[test]
+fn test_window_needs_snapshot() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.fraction_manager_state.state.creators_snapshotted = false;
+    fixture.creator_snapshot.owner = Pubkey::new_unique();
+    let creator = fixture.creator;
+
+    assert_eq!(
+        buyout_in_window(&mut fixture, creator, 0),
+        Err(MetaplexError::IncorrectOwner.into())
+    );
+}
+
+#[test]
+fn test_buyout_credits_verified_creators() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    charge_buyout_fee(&mut fixture, 250);
+    fixture.metadata_state.data.seller_fee_basis_points = 500;
+    fixture.set_creators(vec![
+        CreatorSnapshot {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 70,
+        },
+        CreatorSnapshot {
+            address: Pubkey::new_unique(),
+            verified: false,
+            share: 20,
+        },
+        CreatorSnapshot {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 10,
+        },
+    ]);
+    fixture.init.external_price_account_state.price_per_share = 10;
+    fixture.fraction_mint_state.supply = 200_000;
+
+    fixture.process_buyout().unwrap();
+
+    // 5% of the 1_950_000 left after the store's fee, split by share. The unverified
+    // creator's share stays with holders.
+    assert_eq!(fixture.royalties_owed(), vec![68_250, 9_750]);
+    let proceeds: FractionCreatorProceeds = fixture.creator_proceeds[0].read();
+    assert_eq!(proceeds.fraction_manager, fixture.init.fraction_manager.key);
+    assert_eq!(
+        proceeds.creator,
+        fixture.creator_snapshot_state.creators[0].address
+    );
+    assert_eq!(proceeds.withdrawn, 0);
+}
+
+#[test]
+fn test_buyout_needs_every_verified_creators_proceeds() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.creator_proceeds.clear();
+
+    assert_eq!(
+        fixture.process_buyout().map(|_| ()),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+}
+
+#[test]
+fn test_creator_withdraws_royalties_from_buyout() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    fixture.metadata_state.data.seller_fee_basis_points = 1_000;
+    fixture.process_buyout().unwrap();
+    assert_eq!(fixture.royalties_owed(), vec![fixture.price() / 10]);
+
+    let init = &mut fixture.init;
+    let mut destination = TestAccount::new(
+        spl_token::id(),
+        packed_data(TokenAccount {
+            mint: init.token_mint.key,
+            owner: fixture.creator,
+            amount: 0,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }),
+    );
+    let mut creator = TestAccount::wallet(fixture.creator, true);
+    let mut token_program = TestAccount::wallet(spl_token::id(), false);
+    let mut clock = TestAccount::sysvar(sysvar::clock::id(), &Clock::default());
+
+    assert_eq!(
+        process_withdraw_creator_proceeds(
+            &init.program_id,
+            &[
+                fixture.creator_proceeds[0].info(),
+                init.fraction_manager.info(),
+                init.accept_payment.info(),
+                destination.info(),
+                creator.info(),
+                fixture.creator_snapshot.info(),
+                init.store.info(),
+                token_program.info(),
+                clock.info(),
+            ],
+        ),
+        Ok(())
+    );
+    let proceeds: FractionCreatorProceeds = fixture.creator_proceeds[0].read();
+    assert_eq!(proceeds.withdrawn, proceeds.total_owed);
+}
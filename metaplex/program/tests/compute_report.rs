@@ -0,0 +1,78 @@
+mod utils;
+
+use borsh::BorshDeserialize;
+use mpl_metaplex::{
+    state::ComputeUnitReport,
+    utils::{remaining_compute_units, report_compute_units, report_compute_units_between},
+};
+use solana_program::{entrypoint::ProgramResult, program::get_return_data};
+use utils::*;
+
+fn reported(report: impl FnOnce() -> ProgramResult) -> ComputeUnitReport {
+    let _stubs = ProgramStubs::install();
+    report().unwrap();
+
+    let (program_id, data) = get_return_data().unwrap();
+    assert_eq!(program_id, mpl_metaplex::id());
+    ComputeUnitReport::try_from_slice(&data).unwrap()
+}
+
+#[test]
+fn test_report_read_back() {
+    // Off-chain there is no compute meter, so the estimate is reported as unknown
+    assert_eq!(remaining_compute_units(), None);
+    assert_eq!(
+        reported(|| report_compute_units(Some(200_000))),
+        ComputeUnitReport { consumed: None }
+    );
+    assert_eq!(
+        reported(|| report_compute_units(None)),
+        ComputeUnitReport { consumed: None }
+    );
+}
+
+#[test]
+fn test_report_units_consumed() {
+    assert_eq!(
+        reported(|| report_compute_units_between(Some(200_000), Some(185_500))),
+        ComputeUnitReport {
+            consumed: Some(14_500)
+        }
+    );
+    // A meter that went up can't be trusted
+    assert_eq!(
+        reported(|| report_compute_units_between(Some(185_500), Some(200_000))),
+        ComputeUnitReport { consumed: None }
+    );
+    assert_eq!(
+        reported(|| report_compute_units_between(Some(200_000), None)),
+        ComputeUnitReport { consumed: None }
+    );
+}
+
+#[test]
+fn test_validation_reports_compute() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+    fixture.report_cu = true;
+
+    fixture.process_validate(None).unwrap();
+
+    let (program_id, data) = get_return_data().unwrap();
+    assert_eq!(program_id, mpl_metaplex::id());
+    // Off-chain there is no compute meter to read, on-chain this is the units validation used
+    assert_eq!(
+        ComputeUnitReport::try_from_slice(&data).unwrap(),
+        ComputeUnitReport { consumed: None }
+    );
+}
+
+#[test]
+fn test_validation_reports_nothing_unasked() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+
+    fixture.process_validate(None).unwrap();
+
+    assert_eq!(get_return_data(), None);
+}
@@ -0,0 +1,602 @@
+mod utils;
+
+use borsh::BorshDeserialize;
+use mpl_metaplex::{
+    error::MetaplexError,
+    processor::{
+        finalize_fraction_manager_teardown::process_finalize_fraction_manager_teardown,
+        init_fraction_manager::process_init_fraction_manager,
+    },
+    state::{
+        FractionCreatorProceeds, FractionCreatorSnapshot, FractionManager, FractionManagerClosed,
+        FractionManagerStatus, FractionManagerV1, FractionMetadataRescue,
+        FractionOriginalAuthorityLookup, Key, MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+        MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, MAX_FRACTION_MANAGER_SIZE,
+        MAX_FRACTION_METADATA_RESCUE_SIZE, PREFIX,
+    },
+};
+use mpl_token_metadata::{instruction::update_metadata_accounts, state::MAX_METADATA_LEN};
+use mpl_token_vault::instruction::create_set_authority_instruction;
+use solana_program::{
+    entrypoint::ProgramResult,
+    program::get_return_data,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, rent::Rent},
+};
+use utils::*;
+
+const MANAGER_RENT: u64 = 1_000_000_000;
+const PREPAID_TEARDOWN_RENT: u64 = 5_000;
+const ACCEPT_PAYMENT_RENT: u64 = 2_039_280;
+const RECORD_RENT: u64 = 1_500_000;
+
+fn init(fixture: &mut InitFixture, payer: &mut TestAccount) -> ProgramResult {
+    fixture.sync();
+    // Account creation and the prepay transfer are no-ops off-chain
+    fixture.fraction_manager.data = vec![0; MAX_FRACTION_MANAGER_SIZE];
+    fixture.fraction_manager.lamports = MANAGER_RENT + PREPAID_TEARDOWN_RENT;
+    fixture.accept_payment.lamports = ACCEPT_PAYMENT_RENT;
+    let mut system = TestAccount::wallet(system_program::id(), false);
+    let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+    process_init_fraction_manager(
+        &fixture.program_id,
+        &[
+            fixture.fraction_manager.info(),
+            fixture.vault.info(),
+            fixture.token_mint.info(),
+            fixture.external_price_account.info(),
+            fixture.authority.info(),
+            payer.info(),
+            fixture.accept_payment.info(),
+            fixture.store.info(),
+            system.info(),
+            rent.info(),
+            fixture.vault_claim.info(),
+            fixture.fraction_store_config.info(),
+        ],
+        0,
+        None,
+        0,
+        PREPAID_TEARDOWN_RENT,
+        false,
+        None,
+    )
+}
+
+/// Tears the manager down, closing `records` after its fixed accounts
+fn finalize_with(
+    fixture: &mut InitFixture,
+    recipient: &mut TestAccount,
+    payer: &mut TestAccount,
+    records: &mut [TestAccount],
+) -> ProgramResult {
+    let mut leftover_destination = TestAccount::new(spl_token::id(), vec![]);
+    let mut token_program = TestAccount::wallet(spl_token::id(), false);
+    let mut token_vault_program = TestAccount::wallet(mpl_token_vault::id(), false);
+    let mut token_metadata_program = TestAccount::wallet(mpl_token_metadata::id(), false);
+
+    let mut accounts = vec![
+        fixture.fraction_manager.info(),
+        fixture.authority.info(),
+        recipient.info(),
+        payer.info(),
+        fixture.vault.info(),
+        fixture.accept_payment.info(),
+        leftover_destination.info(),
+        fixture.store.info(),
+        token_program.info(),
+        token_vault_program.info(),
+        token_metadata_program.info(),
+    ];
+    accounts.extend(records.iter_mut().map(|record| record.info()));
+
+    process_finalize_fraction_manager_teardown(&fixture.program_id, &accounts)
+}
+
+fn finalize(
+    fixture: &mut InitFixture,
+    recipient: &mut TestAccount,
+    payer: &mut TestAccount,
+) -> ProgramResult {
+    finalize_with(fixture, recipient, payer, &mut [])
+}
+
+fn new_payer() -> TestAccount {
+    let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+    payer.is_writable = true;
+    payer
+}
+
+fn new_recipient() -> TestAccount {
+    let mut recipient = TestAccount::wallet(Pubkey::new_unique(), false);
+    recipient.is_writable = true;
+    recipient
+}
+
+fn update_manager(fixture: &mut InitFixture, update: impl FnOnce(&mut FractionManagerV1)) {
+    let mut manager: FractionManagerV1 = fixture.fraction_manager.read();
+    update(&mut manager);
+    fixture.fraction_manager.data = fraction_manager_data(&manager);
+}
+
+/// Stands in for minting and the buyout that takes the manager to Combined
+fn combine_after_minting(fixture: &mut InitFixture, fractions_minted: u64) {
+    update_manager(fixture, |manager| {
+        manager.add_fractions_minted(fractions_minted).unwrap();
+        manager.state.status = FractionManagerStatus::Combined;
+    });
+}
+
+/// A program account holding `record`, counted open on the manager as it would have been when made
+fn open_record(
+    fixture: &mut InitFixture,
+    record: &impl borsh::BorshSerialize,
+    size: usize,
+) -> TestAccount {
+    update_manager(fixture, |manager| manager.add_open_record().unwrap());
+    let mut account = TestAccount::new(fixture.program_id, account_data(record, size));
+    account.lamports = RECORD_RENT;
+    account
+}
+
+fn snapshot_record(fixture: &mut InitFixture, fraction_manager: Pubkey) -> TestAccount {
+    open_record(
+        fixture,
+        &FractionCreatorSnapshot {
+            key: Key::FractionCreatorSnapshotV1,
+            fraction_manager,
+            metadata: Pubkey::new_unique(),
+            creators: vec![],
+        },
+        MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+    )
+}
+
+fn rescue_record(fixture: &mut InitFixture, executed_at: Option<i64>) -> TestAccount {
+    let fraction_manager = fixture.fraction_manager.key;
+    open_record(
+        fixture,
+        &FractionMetadataRescue {
+            key: Key::FractionMetadataRescueV1,
+            fraction_manager,
+            metadata: Pubkey::new_unique(),
+            claimed_original_authority: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            requested_at: 0,
+            executed_at,
+        },
+        MAX_FRACTION_METADATA_RESCUE_SIZE,
+    )
+}
+
+/// The lookup of a metadata the manager holds, followed by the metadata and the wallet passed
+/// as the one it returns to
+fn lookup_records(
+    fixture: &mut InitFixture,
+    original_authority: Pubkey,
+    return_to: Option<Pubkey>,
+    return_wallet: Pubkey,
+) -> Vec<TestAccount> {
+    let mut metadata = TestAccount::new(
+        mpl_token_metadata::id(),
+        account_data(
+            &make_metadata(fixture.fraction_manager.key, Pubkey::new_unique(), None),
+            MAX_METADATA_LEN,
+        ),
+    );
+    metadata.key = Pubkey::new_unique();
+    let mut lookup = open_record(
+        fixture,
+        &FractionOriginalAuthorityLookup {
+            key: Key::FractionOriginalAuthorityLookupV1,
+            original_authority,
+            pda_authority: false,
+            return_to,
+        },
+        MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+    );
+    lookup.key = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            fixture.vault.key.as_ref(),
+            metadata.key.as_ref(),
+        ],
+        &fixture.program_id,
+    )
+    .0;
+
+    vec![lookup, metadata, TestAccount::wallet(return_wallet, false)]
+}
+
+fn closed_summary() -> FractionManagerClosed {
+    let (program_id, data) = get_return_data().unwrap();
+    assert_eq!(program_id, mpl_metaplex::id());
+    FractionManagerClosed::try_from_slice(&data).unwrap()
+}
+
+#[test]
+fn test_closing_summary_after_lifecycle() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 1_000);
+    let mut recipient = new_recipient();
+
+    assert_eq!(finalize(&mut fixture, &mut recipient, &mut payer), Ok(()));
+
+    assert_eq!(
+        closed_summary(),
+        FractionManagerClosed {
+            manager: fixture.fraction_manager.key,
+            vault: fixture.vault.key,
+            total_rent_reclaimed: MANAGER_RENT + PREPAID_TEARDOWN_RENT + ACCEPT_PAYMENT_RENT,
+            total_fractions_ever_minted: 1_000,
+            accounts_closed: 1,
+            dependent_rent_reclaimed: ACCEPT_PAYMENT_RENT,
+        }
+    );
+    // The deposit goes back to whoever paid it, not wherever the authority points. Accept
+    // payment's rent is moved by the token program, which doesn't run off-chain.
+    assert_eq!(payer.lamports, PREPAID_TEARDOWN_RENT);
+    assert_eq!(recipient.lamports, MANAGER_RENT);
+    assert_eq!(fixture.fraction_manager.lamports, 0);
+
+    // The manager is gone, so it can't be finalized twice
+    assert_eq!(
+        finalize(&mut fixture, &mut recipient, &mut payer),
+        Err(MetaplexError::DataTypeMismatch.into())
+    );
+}
+
+#[test]
+fn test_refund_only_to_payer() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 0);
+    let mut recipient = new_recipient();
+    let mut other = new_payer();
+
+    assert_eq!(
+        finalize(&mut fixture, &mut recipient, &mut other),
+        Err(MetaplexError::TeardownRentPayerMismatch.into())
+    );
+    assert_eq!(other.lamports, 0);
+    assert_eq!(recipient.lamports, 0);
+}
+
+#[test]
+fn test_requires_combined() {
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    let mut recipient = TestAccount::wallet(Pubkey::new_unique(), false);
+
+    for status in [
+        FractionManagerStatus::Initialized,
+        FractionManagerStatus::Redeemable,
+        FractionManagerStatus::Closed,
+    ] {
+        update_manager(&mut fixture, |manager| manager.state.status = status);
+
+        assert_eq!(
+            finalize(&mut fixture, &mut recipient, &mut payer),
+            Err(MetaplexError::InvalidStatus.into())
+        );
+    }
+}
+
+#[test]
+fn test_holders_unpaid() {
+    let installed = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    update_manager(&mut fixture, |manager| {
+        manager.state.buyout_holder_proceeds = 1_000;
+        manager.state.buyout_shares = 10;
+        manager.redeem_buyout_shares(9).unwrap();
+    });
+    let mut recipient = new_recipient();
+    let init_invoked = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize(&mut fixture, &mut recipient, &mut payer),
+        Err(MetaplexError::HolderProceedsOutstanding.into())
+    );
+    assert_eq!(installed.stubs.invoked().len(), init_invoked);
+
+    // The last holder redeeming settles what holders are owed
+    update_manager(&mut fixture, |manager| {
+        manager.redeem_buyout_shares(1).unwrap();
+    });
+    assert_eq!(finalize(&mut fixture, &mut recipient, &mut payer), Ok(()));
+}
+
+#[test]
+fn test_finalize_after_buyout() {
+    let installed = ProgramStubs::install();
+    let mut fixture = BuyoutFixture::new();
+    let mut payer = new_payer();
+    fixture.fraction_manager_state.state.teardown_rent_payer = payer.key;
+    fixture.fraction_manager_state.state.prepaid_teardown_rent = PREPAID_TEARDOWN_RENT;
+    fixture.init.fraction_manager.lamports = MANAGER_RENT + PREPAID_TEARDOWN_RENT;
+    fixture.metadata_state.data.seller_fee_basis_points = 1_000;
+    let manager = fixture.process_buyout().unwrap();
+    assert_eq!(manager.status(), FractionManagerStatus::Combined);
+    assert_eq!(manager.open_records(), 1);
+    let mut recipient = new_recipient();
+    let mut proceeds = std::mem::take(&mut fixture.creator_proceeds);
+
+    // Holders still hold every share they were bought out of
+    assert_eq!(
+        finalize_with(&mut fixture.init, &mut recipient, &mut payer, &mut proceeds),
+        Err(MetaplexError::HolderProceedsOutstanding.into())
+    );
+
+    update_manager(&mut fixture.init, |manager| {
+        let shares = manager.state.buyout_shares;
+        manager.redeem_buyout_shares(shares).unwrap();
+    });
+    // The creator hasn't withdrawn their royalties yet
+    assert_eq!(
+        finalize_with(&mut fixture.init, &mut recipient, &mut payer, &mut proceeds),
+        Err(MetaplexError::RoyaltiesOutstanding.into())
+    );
+    // Neither may the proceeds be left out to get around that
+    assert_eq!(
+        finalize(&mut fixture.init, &mut recipient, &mut payer),
+        Err(MetaplexError::FractionRecordsStillOpen.into())
+    );
+
+    let mut withdrawn: FractionCreatorProceeds = proceeds[0].read();
+    withdrawn.withdrawn = withdrawn.total_owed;
+    proceeds[0].data = account_data(&withdrawn, proceeds[0].data.len());
+    let proceeds_rent = proceeds[0].lamports;
+    let invoked_before = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize_with(&mut fixture.init, &mut recipient, &mut payer, &mut proceeds),
+        Ok(())
+    );
+    // The vault goes back to the manager authority before anything closes
+    assert_eq!(
+        installed.stubs.invoked()[invoked_before],
+        create_set_authority_instruction(
+            mpl_token_vault::id(),
+            fixture.init.vault.key,
+            fixture.init.fraction_manager.key,
+            fixture.init.authority.key,
+        )
+    );
+    assert_eq!(proceeds[0].lamports, 0);
+    assert_eq!(payer.lamports, PREPAID_TEARDOWN_RENT);
+    assert_eq!(recipient.lamports, MANAGER_RENT + proceeds_rent);
+    assert_eq!(fixture.init.fraction_manager.lamports, 0);
+    let summary = closed_summary();
+    assert_eq!(summary.accounts_closed, 2);
+    assert_eq!(
+        summary.dependent_rent_reclaimed,
+        proceeds_rent + fixture.init.accept_payment.lamports
+    );
+}
+
+#[test]
+fn test_closes_records_and_reports_rent() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let fraction_manager = fixture.fraction_manager.key;
+    let mut config = open_record(
+        &mut fixture,
+        &make_config(fraction_manager, 0),
+        make_config(fraction_manager, 0).created_size(),
+    );
+    config.key = Pubkey::new_unique();
+    let mut records = vec![
+        config,
+        snapshot_record(&mut fixture, fraction_manager),
+        rescue_record(&mut fixture, Some(100)),
+    ];
+    let mut recipient = new_recipient();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut records),
+        Ok(())
+    );
+
+    for record in &records {
+        assert_eq!(record.lamports, 0);
+        assert!(record.data.iter().all(|byte| *byte == 0));
+    }
+    assert_eq!(recipient.lamports, MANAGER_RENT + 3 * RECORD_RENT);
+    let summary = closed_summary();
+    assert_eq!(summary.accounts_closed, 4);
+    assert_eq!(
+        summary.dependent_rent_reclaimed,
+        3 * RECORD_RENT + ACCEPT_PAYMENT_RENT
+    );
+    assert_eq!(
+        summary.total_rent_reclaimed,
+        MANAGER_RENT + PREPAID_TEARDOWN_RENT + 3 * RECORD_RENT + ACCEPT_PAYMENT_RENT
+    );
+}
+
+#[test]
+fn test_record_left_open() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let fraction_manager = fixture.fraction_manager.key;
+    let snapshot = snapshot_record(&mut fixture, fraction_manager);
+    let _forgotten = snapshot_record(&mut fixture, fraction_manager);
+    let mut recipient = new_recipient();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut [snapshot]),
+        Err(MetaplexError::FractionRecordsStillOpen.into())
+    );
+}
+
+#[test]
+fn test_record_of_other_manager() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let others = snapshot_record(&mut fixture, Pubkey::new_unique());
+    let mut recipient = new_recipient();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut [others]),
+        Err(MetaplexError::NotAFractionManagerRecord.into())
+    );
+
+    let fraction_manager = fixture.fraction_manager.key;
+    let mut foreign = snapshot_record(&mut fixture, fraction_manager);
+    foreign.owner = Pubkey::new_unique();
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut [foreign]),
+        Err(MetaplexError::IncorrectOwner.into())
+    );
+}
+
+#[test]
+fn test_rescue_pending() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let rescue = rescue_record(&mut fixture, None);
+    let mut recipient = new_recipient();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut [rescue]),
+        Err(MetaplexError::RescuePending.into())
+    );
+}
+
+#[test]
+fn test_returns_metadata_authority() {
+    let installed = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let original_authority = Pubkey::new_unique();
+    let mut records = lookup_records(&mut fixture, original_authority, None, original_authority);
+    let mut recipient = new_recipient();
+    let init_invoked = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut records),
+        Ok(())
+    );
+
+    assert_eq!(
+        installed.stubs.invoked()[init_invoked],
+        update_metadata_accounts(
+            mpl_token_metadata::id(),
+            records[1].key,
+            fixture.fraction_manager.key,
+            Some(original_authority),
+            None,
+            None,
+        )
+    );
+    assert_eq!(records[0].lamports, 0);
+    assert_eq!(closed_summary().accounts_closed, 2);
+}
+
+#[test]
+fn test_returns_metadata_authority_to_return_to() {
+    let installed = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let return_to = Pubkey::new_unique();
+    let mut records = lookup_records(
+        &mut fixture,
+        Pubkey::new_unique(),
+        Some(return_to),
+        return_to,
+    );
+    let mut recipient = new_recipient();
+    let init_invoked = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut records),
+        Ok(())
+    );
+
+    assert_eq!(
+        installed.stubs.invoked()[init_invoked],
+        update_metadata_accounts(
+            mpl_token_metadata::id(),
+            records[1].key,
+            fixture.fraction_manager.key,
+            Some(return_to),
+            None,
+            None,
+        )
+    );
+}
+
+#[test]
+fn test_return_to_overrides_original_authority() {
+    let installed = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let original_authority = Pubkey::new_unique();
+    let mut records = lookup_records(
+        &mut fixture,
+        original_authority,
+        Some(Pubkey::new_unique()),
+        original_authority,
+    );
+    let mut recipient = new_recipient();
+    let init_invoked = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut records),
+        Err(MetaplexError::OriginalAuthorityMismatch.into())
+    );
+    assert_eq!(installed.stubs.invoked().len(), init_invoked);
+}
+
+#[test]
+fn test_returns_metadata_authority_to_wrong_wallet() {
+    let installed = ProgramStubs::install();
+    let mut fixture = InitFixture::new();
+    let mut payer = new_payer();
+    init(&mut fixture, &mut payer).unwrap();
+    combine_after_minting(&mut fixture, 10);
+    let mut records = lookup_records(
+        &mut fixture,
+        Pubkey::new_unique(),
+        None,
+        Pubkey::new_unique(),
+    );
+    let mut recipient = new_recipient();
+    let init_invoked = installed.stubs.invoked().len();
+
+    assert_eq!(
+        finalize_with(&mut fixture, &mut recipient, &mut payer, &mut records),
+        Err(MetaplexError::OriginalAuthorityMismatch.into())
+    );
+    assert_eq!(installed.stubs.invoked().len(), init_invoked);
+}
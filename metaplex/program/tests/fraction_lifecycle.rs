@@ -0,0 +1,1092 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    instruction::{
+        create_buyout_fraction_manager_instruction,
+        create_finalize_fraction_manager_teardown_instruction,
+        create_redeem_fraction_shares_instruction, create_rescue_stuck_metadata_instruction,
+        create_withdraw_creator_proceeds_instruction, BuyoutCreatorAccounts,
+        TeardownAuthorityLookup,
+    },
+    state::{
+        CreatorSnapshot, FractionCreatorProceeds, FractionCreatorSnapshot, FractionManager,
+        FractionManagerStatus, FractionManagerV1, FractionMetadataRescue,
+        FractionOriginalAuthorityLookup, Key, Store, CREATORS, MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+        MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, MAX_FRACTION_MANAGER_SIZE, MAX_STORE_SIZE, PREFIX,
+        PROCEEDS, RESCUE, RESCUE_DISPUTE_WINDOW,
+    },
+};
+use mpl_token_metadata::state::{Creator, Metadata, MAX_METADATA_LEN};
+use mpl_token_vault::state::{
+    ExternalPriceAccount, Vault, VaultState, MAX_EXTERNAL_ACCOUNT_SIZE, MAX_VAULT_SIZE,
+};
+use num_traits::FromPrimitive;
+use solana_program::{
+    borsh::try_from_slice_unchecked, clock::Clock, program_option::COption, program_pack::Pack,
+    pubkey::Pubkey, system_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use utils::{account_data, make_fraction_manager, make_metadata, packed_data};
+
+#[macro_export]
+macro_rules! assert_custom_error {
+    ($error:expr, $matcher:pat) => {
+        match $error {
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(x),
+            )) => match FromPrimitive::from_i32(x as i32) {
+                Some($matcher) => assert!(true),
+                _ => assert!(false),
+            },
+            _ => assert!(false),
+        };
+    };
+}
+
+const SHARES: u64 = 10;
+const PRICE_PER_SHARE: u64 = 100;
+const SELLER_FEE_BASIS_POINTS: u16 = 1000;
+const BUYOUT: u64 = SHARES * PRICE_PER_SHARE;
+const ROYALTIES: u64 = BUYOUT * SELLER_FEE_BASIS_POINTS as u64 / 10000;
+const HOLDER_PROCEEDS: u64 = BUYOUT - ROYALTIES;
+
+/// Accounts of a fractionalized NFT, written straight into the bank so each test starts from
+/// the state it exercises and runs the instruction under test against the real token, vault
+/// and metadata programs.
+struct Fixture {
+    admin: Keypair,
+    authority: Keypair,
+    buyer: Keypair,
+    holder: Keypair,
+    creator: Keypair,
+    original_authority: Keypair,
+    store: Pubkey,
+    vault: Pubkey,
+    fraction_manager: Pubkey,
+    payment_mint: Pubkey,
+    accept_payment: Pubkey,
+    fraction_mint: Pubkey,
+    vault_fraction_mint: Pubkey,
+    vault_fraction_treasury: Pubkey,
+    vault_redeem_treasury: Pubkey,
+    external_price_account: Pubkey,
+    metadata: Pubkey,
+    creator_snapshot: Pubkey,
+    original_authority_lookup: Pubkey,
+    buyer_payment: Pubkey,
+    buyer_vault_shares: Pubkey,
+    store_fee_account: Pubkey,
+    holder_shares: Pubkey,
+    holder_payment: Pubkey,
+    creator_payment: Pubkey,
+    recipient: Pubkey,
+    manager_state: FractionManagerV1,
+    vault_state: Vault,
+    with_lookup: bool,
+}
+
+impl Fixture {
+    /// A Redeemable manager holding the vault, with creators snapshotted and the metadata's
+    /// update authority handed to it at validation
+    fn redeemable() -> Fixture {
+        let program_id = id();
+        let admin = Keypair::new();
+        let authority = Keypair::new();
+        let vault = Pubkey::new_unique();
+        let store = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                admin.pubkey().as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let fraction_manager =
+            Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id).0;
+        let nft_mint = Pubkey::new_unique();
+        let metadata = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                nft_mint.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        )
+        .0;
+        let creator_snapshot = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.as_ref(),
+                metadata.as_ref(),
+                CREATORS.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+        let original_authority_lookup = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), vault.as_ref(), metadata.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let payment_mint = Pubkey::new_unique();
+        let accept_payment = Pubkey::new_unique();
+        let fraction_mint = Pubkey::new_unique();
+        let vault_fraction_mint = Pubkey::new_unique();
+        let vault_fraction_treasury = Pubkey::new_unique();
+        let vault_redeem_treasury = Pubkey::new_unique();
+        let external_price_account = Pubkey::new_unique();
+
+        let mut manager_state =
+            make_fraction_manager(authority.pubkey(), FractionManagerStatus::Redeemable);
+        manager_state.store = store;
+        manager_state.vault = vault;
+        manager_state.token_mint = payment_mint;
+        manager_state.accept_payment = accept_payment;
+        manager_state.external_pricing = external_price_account;
+        manager_state.state.fraction_mint = Some(fraction_mint);
+        manager_state.state.creators_snapshotted = true;
+        // The creator snapshot and the original authority lookup
+        manager_state.state.open_records = 2;
+
+        let mut vault_state: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        vault_state.key = mpl_token_vault::state::Key::VaultV1;
+        vault_state.token_program = spl_token::id();
+        vault_state.fraction_mint = vault_fraction_mint;
+        vault_state.authority = fraction_manager;
+        vault_state.fraction_treasury = vault_fraction_treasury;
+        vault_state.redeem_treasury = vault_redeem_treasury;
+        vault_state.pricing_lookup_address = external_price_account;
+        vault_state.token_type_count = 1;
+        vault_state.state = VaultState::Active;
+
+        Fixture {
+            admin,
+            authority,
+            buyer: Keypair::new(),
+            holder: Keypair::new(),
+            creator: Keypair::new(),
+            original_authority: Keypair::new(),
+            store,
+            vault,
+            fraction_manager,
+            payment_mint,
+            accept_payment,
+            fraction_mint,
+            vault_fraction_mint,
+            vault_fraction_treasury,
+            vault_redeem_treasury,
+            external_price_account,
+            metadata,
+            creator_snapshot,
+            original_authority_lookup,
+            buyer_payment: Pubkey::new_unique(),
+            buyer_vault_shares: Pubkey::new_unique(),
+            store_fee_account: Pubkey::new_unique(),
+            holder_shares: Pubkey::new_unique(),
+            holder_payment: Pubkey::new_unique(),
+            creator_payment: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            manager_state,
+            vault_state,
+            with_lookup: true,
+        }
+    }
+
+    /// A manager whose vault was combined without a buyout through it, so it still holds the
+    /// vault and owes nothing to holders or creators
+    fn combined_holding_vault() -> Fixture {
+        let mut fixture = Fixture::redeemable();
+        fixture.manager_state.state.status = FractionManagerStatus::Combined;
+        fixture.vault_state.state = VaultState::Combined;
+        fixture
+    }
+
+    /// A Combined manager whose original authority lookup was lost, leaving the metadata with it
+    fn combined_lookup_lost() -> Fixture {
+        let mut fixture = Fixture::combined_holding_vault();
+        fixture.with_lookup = false;
+        fixture.manager_state.state.open_records = 1;
+        fixture
+    }
+
+    async fn start(&self) -> ProgramTestContext {
+        let program_id = id();
+        let vault_authority = Pubkey::find_program_address(
+            &[
+                mpl_token_vault::state::PREFIX.as_bytes(),
+                mpl_token_vault::id().as_ref(),
+                self.vault.as_ref(),
+            ],
+            &mpl_token_vault::id(),
+        )
+        .0;
+
+        let mut program_test = ProgramTest::new("mpl_metaplex", program_id, None);
+        program_test.add_program("mpl_token_vault", mpl_token_vault::id(), None);
+        program_test.add_program("mpl_token_metadata", mpl_token_metadata::id(), None);
+
+        for wallet in [
+            &self.admin,
+            &self.authority,
+            &self.buyer,
+            &self.holder,
+            &self.creator,
+            &self.original_authority,
+        ] {
+            add_account(
+                &mut program_test,
+                wallet.pubkey(),
+                system_program::id(),
+                vec![],
+            );
+        }
+
+        add_account(
+            &mut program_test,
+            self.store,
+            program_id,
+            account_data(
+                &Store {
+                    key: Key::StoreV1,
+                    public: true,
+                    auction_program: mpl_auction::id(),
+                    token_vault_program: mpl_token_vault::id(),
+                    token_metadata_program: mpl_token_metadata::id(),
+                    token_program: spl_token::id(),
+                },
+                MAX_STORE_SIZE,
+            ),
+        );
+        add_account(
+            &mut program_test,
+            self.fraction_manager,
+            program_id,
+            account_data(&self.manager_state, MAX_FRACTION_MANAGER_SIZE),
+        );
+        add_account(
+            &mut program_test,
+            self.vault,
+            mpl_token_vault::id(),
+            account_data(&self.vault_state, MAX_VAULT_SIZE),
+        );
+        add_account(
+            &mut program_test,
+            self.external_price_account,
+            mpl_token_vault::id(),
+            account_data(
+                &ExternalPriceAccount {
+                    key: mpl_token_vault::state::Key::ExternalAccountKeyV1,
+                    price_per_share: PRICE_PER_SHARE,
+                    price_mint: self.payment_mint,
+                    allowed_to_combine: true,
+                },
+                MAX_EXTERNAL_ACCOUNT_SIZE,
+            ),
+        );
+
+        let mut metadata = make_metadata(
+            self.fraction_manager,
+            Pubkey::new_unique(),
+            Some(vec![Creator {
+                address: self.creator.pubkey(),
+                verified: true,
+                share: 100,
+            }]),
+        );
+        metadata.data.seller_fee_basis_points = SELLER_FEE_BASIS_POINTS;
+        add_account(
+            &mut program_test,
+            self.metadata,
+            mpl_token_metadata::id(),
+            account_data(&metadata, MAX_METADATA_LEN),
+        );
+        add_account(
+            &mut program_test,
+            self.creator_snapshot,
+            program_id,
+            account_data(
+                &FractionCreatorSnapshot {
+                    key: Key::FractionCreatorSnapshotV1,
+                    fraction_manager: self.fraction_manager,
+                    metadata: self.metadata,
+                    creators: vec![CreatorSnapshot {
+                        address: self.creator.pubkey(),
+                        verified: true,
+                        share: 100,
+                    }],
+                },
+                MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+            ),
+        );
+        if self.with_lookup {
+            add_account(
+                &mut program_test,
+                self.original_authority_lookup,
+                program_id,
+                account_data(
+                    &FractionOriginalAuthorityLookup {
+                        key: Key::FractionOriginalAuthorityLookupV1,
+                        original_authority: self.original_authority.pubkey(),
+                        pda_authority: false,
+                        return_to: None,
+                    },
+                    MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+                ),
+            );
+        }
+
+        // Every share the vault minted sits in its treasury, leaving none for the buyer to pay
+        // the vault for on top of the buyout
+        let shares_in_vault = if self.vault_state.state == VaultState::Active {
+            SHARES
+        } else {
+            0
+        };
+        let shares_held = if self.manager_state.status() == FractionManagerStatus::Redeemable {
+            SHARES
+        } else {
+            0
+        };
+        add_mint(
+            &mut program_test,
+            self.payment_mint,
+            Pubkey::new_unique(),
+            BUYOUT,
+        );
+        add_mint(
+            &mut program_test,
+            self.fraction_mint,
+            self.fraction_manager,
+            shares_held,
+        );
+        add_mint(
+            &mut program_test,
+            self.vault_fraction_mint,
+            vault_authority,
+            shares_in_vault,
+        );
+
+        let token_accounts = [
+            (
+                self.accept_payment,
+                self.payment_mint,
+                self.fraction_manager,
+                0,
+            ),
+            (
+                self.vault_fraction_treasury,
+                self.vault_fraction_mint,
+                vault_authority,
+                shares_in_vault,
+            ),
+            (
+                self.vault_redeem_treasury,
+                self.payment_mint,
+                vault_authority,
+                0,
+            ),
+            (
+                self.buyer_payment,
+                self.payment_mint,
+                self.buyer.pubkey(),
+                BUYOUT,
+            ),
+            (
+                self.buyer_vault_shares,
+                self.vault_fraction_mint,
+                self.buyer.pubkey(),
+                0,
+            ),
+            (
+                self.store_fee_account,
+                self.payment_mint,
+                self.admin.pubkey(),
+                0,
+            ),
+            (
+                self.holder_shares,
+                self.fraction_mint,
+                self.holder.pubkey(),
+                shares_held,
+            ),
+            (
+                self.holder_payment,
+                self.payment_mint,
+                self.holder.pubkey(),
+                0,
+            ),
+            (
+                self.creator_payment,
+                self.payment_mint,
+                self.creator.pubkey(),
+                0,
+            ),
+        ];
+        for (key, mint, owner, amount) in token_accounts {
+            add_token_account(&mut program_test, key, mint, owner, amount);
+        }
+
+        program_test.start_with_context().await
+    }
+
+    fn buyout_instruction(&self) -> Instruction {
+        create_buyout_fraction_manager_instruction(
+            id(),
+            self.fraction_manager,
+            self.vault,
+            self.fraction_mint,
+            self.external_price_account,
+            self.accept_payment,
+            self.buyer_payment,
+            self.buyer.pubkey(),
+            self.store,
+            self.store_fee_account,
+            self.admin.pubkey(),
+            mpl_token_vault::id(),
+            self.buyer_vault_shares,
+            self.vault_fraction_mint,
+            self.vault_fraction_treasury,
+            self.vault_redeem_treasury,
+            Some(BuyoutCreatorAccounts {
+                metadata: self.metadata,
+                payer: self.buyer.pubkey(),
+                verified_creators: vec![self.creator.pubkey()],
+            }),
+        )
+    }
+
+    fn withdraw_instruction(&self) -> Instruction {
+        create_withdraw_creator_proceeds_instruction(
+            id(),
+            self.fraction_manager,
+            self.accept_payment,
+            self.creator_payment,
+            self.creator.pubkey(),
+            self.metadata,
+            self.store,
+            spl_token::id(),
+        )
+    }
+
+    fn redeem_instruction(&self) -> Instruction {
+        create_redeem_fraction_shares_instruction(
+            id(),
+            self.fraction_manager,
+            self.vault,
+            self.fraction_mint,
+            self.holder_shares,
+            self.holder.pubkey(),
+            self.accept_payment,
+            self.holder_payment,
+            self.store,
+        )
+    }
+
+    fn teardown_instruction(&self, records: Vec<Pubkey>) -> Instruction {
+        create_finalize_fraction_manager_teardown_instruction(
+            id(),
+            self.fraction_manager,
+            self.authority.pubkey(),
+            self.recipient,
+            self.manager_state.teardown_rent_payer(),
+            self.vault,
+            self.accept_payment,
+            self.holder_payment,
+            self.store,
+            mpl_token_vault::id(),
+            records,
+            vec![TeardownAuthorityLookup {
+                metadata: self.metadata,
+                return_authority: self.original_authority.pubkey(),
+            }],
+        )
+    }
+
+    fn rescue_instruction(&self) -> Instruction {
+        create_rescue_stuck_metadata_instruction(
+            id(),
+            self.vault,
+            self.metadata,
+            self.original_authority.pubkey(),
+            self.admin.pubkey(),
+            self.admin.pubkey(),
+            mpl_token_metadata::id(),
+        )
+    }
+
+    fn creator_proceeds(&self) -> Pubkey {
+        let program_id = id();
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                self.fraction_manager.as_ref(),
+                self.creator.pubkey().as_ref(),
+                PROCEEDS.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0
+    }
+
+    fn rescue(&self) -> Pubkey {
+        let program_id = id();
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                self.metadata.as_ref(),
+                RESCUE.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0
+    }
+}
+
+fn add_account(program_test: &mut ProgramTest, key: Pubkey, owner: Pubkey, data: Vec<u8>) {
+    let lamports = Rent::default()
+        .minimum_balance(data.len())
+        .max(1_000_000_000);
+    program_test.add_account(
+        key,
+        Account {
+            lamports,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+fn add_mint(program_test: &mut ProgramTest, key: Pubkey, mint_authority: Pubkey, supply: u64) {
+    let data = packed_data(Mint {
+        mint_authority: COption::Some(mint_authority),
+        supply,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    });
+    add_account(program_test, key, spl_token::id(), data);
+}
+
+fn add_token_account(
+    program_test: &mut ProgramTest,
+    key: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+) {
+    let data = packed_data(TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    });
+    add_account(program_test, key, spl_token::id(), data);
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), TransportError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Moves to a later slot, so a repeated instruction isn't dropped as a duplicate transaction
+fn next_blockhash(context: &mut ProgramTestContext, slot: &mut u64) {
+    *slot += 100;
+    context.warp_to_slot(*slot).unwrap();
+}
+
+async fn get_account(context: &mut ProgramTestContext, key: Pubkey) -> Option<Account> {
+    context.banks_client.get_account(key).await.unwrap()
+}
+
+async fn token_amount(context: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    let account = get_account(context, key).await.unwrap();
+    TokenAccount::unpack(&account.data).unwrap().amount
+}
+
+async fn mint_supply(context: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    let account = get_account(context, key).await.unwrap();
+    Mint::unpack(&account.data).unwrap().supply
+}
+
+async fn fraction_manager(
+    context: &mut ProgramTestContext,
+    fixture: &Fixture,
+) -> FractionManagerV1 {
+    let account = get_account(context, fixture.fraction_manager)
+        .await
+        .unwrap();
+    try_from_slice_unchecked(&account.data).unwrap()
+}
+
+async fn vault(context: &mut ProgramTestContext, fixture: &Fixture) -> Vault {
+    let account = get_account(context, fixture.vault).await.unwrap();
+    try_from_slice_unchecked(&account.data).unwrap()
+}
+
+async fn metadata_update_authority(context: &mut ProgramTestContext, fixture: &Fixture) -> Pubkey {
+    let account = get_account(context, fixture.metadata).await.unwrap();
+    let metadata: Metadata = try_from_slice_unchecked(&account.data).unwrap();
+    metadata.update_authority
+}
+
+async fn lamports(context: &mut ProgramTestContext, key: Pubkey) -> u64 {
+    get_account(context, key)
+        .await
+        .map_or(0, |account| account.lamports)
+}
+
+mod buyout_fraction_manager {
+    use super::*;
+
+    #[tokio::test]
+    async fn buyout_pays_accept_payment_and_hands_over_vault() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+
+        process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(token_amount(&mut context, fixture.buyer_payment).await, 0);
+        assert_eq!(
+            token_amount(&mut context, fixture.accept_payment).await,
+            BUYOUT
+        );
+        assert_eq!(
+            token_amount(&mut context, fixture.store_fee_account).await,
+            0
+        );
+
+        // The vault is combined to the buyer, its own shares burned out of the treasury
+        let vault = vault(&mut context, &fixture).await;
+        assert!(vault.state == VaultState::Combined);
+        assert_eq!(vault.authority, fixture.buyer.pubkey());
+        assert_eq!(
+            token_amount(&mut context, fixture.vault_fraction_treasury).await,
+            0
+        );
+        assert_eq!(
+            mint_supply(&mut context, fixture.vault_fraction_mint).await,
+            0
+        );
+
+        let manager = fraction_manager(&mut context, &fixture).await;
+        assert_eq!(manager.status(), FractionManagerStatus::Combined);
+        assert_eq!(manager.state.buyout_holder_proceeds, HOLDER_PROCEEDS);
+        assert_eq!(manager.state.buyout_shares, SHARES);
+        assert_eq!(manager.open_records(), 3);
+
+        let proceeds_account = get_account(&mut context, fixture.creator_proceeds())
+            .await
+            .unwrap();
+        let proceeds: FractionCreatorProceeds =
+            try_from_slice_unchecked(&proceeds_account.data).unwrap();
+        assert_eq!(proceeds.total_owed, ROYALTIES);
+        assert_eq!(proceeds.withdrawn, 0);
+
+        // The metadata stays with the manager until teardown returns it
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.fraction_manager
+        );
+    }
+
+    #[tokio::test]
+    async fn buyout_short_of_payment() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+
+        let mut short = get_account(&mut context, fixture.buyer_payment)
+            .await
+            .unwrap();
+        let mut buyer_payment = TokenAccount::unpack(&short.data).unwrap();
+        buyer_payment.amount = BUYOUT - 1;
+        TokenAccount::pack(buyer_payment, &mut short.data).unwrap();
+        context.set_account(&fixture.buyer_payment, &short.into());
+
+        assert!(process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer]
+        )
+        .await
+        .is_err());
+
+        assert_eq!(
+            token_amount(&mut context, fixture.buyer_payment).await,
+            BUYOUT - 1
+        );
+        assert_eq!(token_amount(&mut context, fixture.accept_payment).await, 0);
+        assert_eq!(
+            vault(&mut context, &fixture).await.authority,
+            fixture.fraction_manager
+        );
+        assert_eq!(
+            fraction_manager(&mut context, &fixture).await.status(),
+            FractionManagerStatus::Redeemable
+        );
+    }
+}
+
+mod withdraw_creator_proceeds {
+    use super::*;
+
+    #[tokio::test]
+    async fn withdraw_pays_creator_royalties_once() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+        let mut slot = 0;
+
+        process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer],
+        )
+        .await
+        .unwrap();
+        process(
+            &mut context,
+            fixture.withdraw_instruction(),
+            &[&fixture.creator],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            token_amount(&mut context, fixture.creator_payment).await,
+            ROYALTIES
+        );
+        assert_eq!(
+            token_amount(&mut context, fixture.accept_payment).await,
+            HOLDER_PROCEEDS
+        );
+
+        let proceeds_account = get_account(&mut context, fixture.creator_proceeds())
+            .await
+            .unwrap();
+        let proceeds: FractionCreatorProceeds =
+            try_from_slice_unchecked(&proceeds_account.data).unwrap();
+        assert_eq!(proceeds.withdrawn, ROYALTIES);
+
+        next_blockhash(&mut context, &mut slot);
+        let result = process(
+            &mut context,
+            fixture.withdraw_instruction(),
+            &[&fixture.creator],
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(result, MetaplexError::NoVestedCreatorProceeds);
+        assert_eq!(
+            token_amount(&mut context, fixture.creator_payment).await,
+            ROYALTIES
+        );
+    }
+
+    #[tokio::test]
+    async fn withdraw_only_by_creator() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+
+        process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer],
+        )
+        .await
+        .unwrap();
+
+        let impostor = Keypair::new();
+        let instruction = create_withdraw_creator_proceeds_instruction(
+            id(),
+            fixture.fraction_manager,
+            fixture.accept_payment,
+            fixture.creator_payment,
+            impostor.pubkey(),
+            fixture.metadata,
+            fixture.store,
+            spl_token::id(),
+        );
+        assert!(process(&mut context, instruction, &[&impostor])
+            .await
+            .is_err());
+
+        assert_eq!(token_amount(&mut context, fixture.creator_payment).await, 0);
+        assert_eq!(
+            token_amount(&mut context, fixture.accept_payment).await,
+            BUYOUT
+        );
+    }
+}
+
+mod finalize_fraction_manager_teardown {
+    use super::*;
+
+    #[tokio::test]
+    async fn teardown_after_buyout_returns_authorities_and_closes_everything() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+
+        process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer],
+        )
+        .await
+        .unwrap();
+        process(
+            &mut context,
+            fixture.withdraw_instruction(),
+            &[&fixture.creator],
+        )
+        .await
+        .unwrap();
+        process(
+            &mut context,
+            fixture.redeem_instruction(),
+            &[&fixture.holder],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            token_amount(&mut context, fixture.holder_payment).await,
+            HOLDER_PROCEEDS
+        );
+        assert_eq!(token_amount(&mut context, fixture.holder_shares).await, 0);
+        assert_eq!(mint_supply(&mut context, fixture.fraction_mint).await, 0);
+        assert_eq!(token_amount(&mut context, fixture.accept_payment).await, 0);
+
+        let records = vec![fixture.creator_snapshot, fixture.creator_proceeds()];
+        let mut reclaimable = lamports(&mut context, fixture.fraction_manager).await
+            + lamports(&mut context, fixture.accept_payment).await
+            + lamports(&mut context, fixture.original_authority_lookup).await;
+        for record in records.iter() {
+            reclaimable += lamports(&mut context, *record).await;
+        }
+
+        process(
+            &mut context,
+            fixture.teardown_instruction(records.clone()),
+            &[&fixture.authority],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.original_authority.pubkey()
+        );
+        // The buyer took the vault at buyout, so there is nothing for teardown to hand back
+        assert_eq!(
+            vault(&mut context, &fixture).await.authority,
+            fixture.buyer.pubkey()
+        );
+
+        for closed in records.iter().chain(&[
+            fixture.fraction_manager,
+            fixture.accept_payment,
+            fixture.original_authority_lookup,
+        ]) {
+            assert!(get_account(&mut context, *closed).await.is_none());
+        }
+        assert_eq!(lamports(&mut context, fixture.recipient).await, reclaimable);
+    }
+
+    #[tokio::test]
+    async fn teardown_refused_while_holders_unpaid() {
+        let fixture = Fixture::redeemable();
+        let mut context = fixture.start().await;
+
+        process(
+            &mut context,
+            fixture.buyout_instruction(),
+            &[&fixture.buyer],
+        )
+        .await
+        .unwrap();
+        process(
+            &mut context,
+            fixture.withdraw_instruction(),
+            &[&fixture.creator],
+        )
+        .await
+        .unwrap();
+
+        let result = process(
+            &mut context,
+            fixture
+                .teardown_instruction(vec![fixture.creator_snapshot, fixture.creator_proceeds()]),
+            &[&fixture.authority],
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(result, MetaplexError::HolderProceedsOutstanding);
+
+        assert_eq!(
+            token_amount(&mut context, fixture.accept_payment).await,
+            HOLDER_PROCEEDS
+        );
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.fraction_manager
+        );
+        assert!(get_account(&mut context, fixture.fraction_manager)
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn teardown_returns_vault_held_by_manager() {
+        let fixture = Fixture::combined_holding_vault();
+        let mut context = fixture.start().await;
+
+        process(
+            &mut context,
+            fixture.teardown_instruction(vec![fixture.creator_snapshot]),
+            &[&fixture.authority],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            vault(&mut context, &fixture).await.authority,
+            fixture.authority.pubkey()
+        );
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.original_authority.pubkey()
+        );
+        assert!(get_account(&mut context, fixture.fraction_manager)
+            .await
+            .is_none());
+    }
+}
+
+mod rescue_stuck_metadata {
+    use super::*;
+
+    #[tokio::test]
+    async fn rescue_returns_metadata_after_dispute_window() {
+        let fixture = Fixture::combined_lookup_lost();
+        let mut context = fixture.start().await;
+        let mut slot = 0;
+
+        process(
+            &mut context,
+            fixture.rescue_instruction(),
+            &[&fixture.admin],
+        )
+        .await
+        .unwrap();
+
+        let rescue_account = get_account(&mut context, fixture.rescue()).await.unwrap();
+        let rescue: FractionMetadataRescue =
+            try_from_slice_unchecked(&rescue_account.data).unwrap();
+        assert_eq!(rescue.executed_at, None);
+        assert_eq!(
+            fraction_manager(&mut context, &fixture)
+                .await
+                .open_records(),
+            2
+        );
+
+        next_blockhash(&mut context, &mut slot);
+        let result = process(
+            &mut context,
+            fixture.rescue_instruction(),
+            &[&fixture.admin],
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(result, MetaplexError::RescueDisputeWindowOpen);
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.fraction_manager
+        );
+
+        next_blockhash(&mut context, &mut slot);
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = rescue.requested_at + RESCUE_DISPUTE_WINDOW;
+        context.set_sysvar(&clock);
+
+        process(
+            &mut context,
+            fixture.rescue_instruction(),
+            &[&fixture.admin],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            metadata_update_authority(&mut context, &fixture).await,
+            fixture.original_authority.pubkey()
+        );
+        let rescue_account = get_account(&mut context, fixture.rescue()).await.unwrap();
+        let rescue: FractionMetadataRescue =
+            try_from_slice_unchecked(&rescue_account.data).unwrap();
+        assert_eq!(rescue.executed_at, Some(clock.unix_timestamp));
+        // The executed rescue stands in for the lost lookup
+        assert_eq!(
+            fraction_manager(&mut context, &fixture)
+                .await
+                .open_records(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn rescue_refused_while_lookup_exists() {
+        let fixture = Fixture::combined_holding_vault();
+        let mut context = fixture.start().await;
+
+        let result = process(
+            &mut context,
+            fixture.rescue_instruction(),
+            &[&fixture.admin],
+        )
+        .await
+        .unwrap_err();
+        assert_custom_error!(result, MetaplexError::OriginalAuthorityLookupNotLost);
+        assert!(get_account(&mut context, fixture.rescue()).await.is_none());
+    }
+}
@@ -0,0 +1,1190 @@
+mod utils;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_auction::processor::{AuctionData, AuctionState, BidState, PriceFloor};
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    processor::{
+        init_fraction_manager::{assert_vault_not_in_active_auction, claim_vault_generation},
+        init_fraction_manager_with_mint::process_init_fraction_manager_with_mint,
+        set_fraction_manager_metadata_uri::process_set_fraction_manager_metadata_uri,
+        set_fraction_manager_metadata_uri_immutable::process_set_fraction_manager_metadata_uri_immutable,
+        set_fraction_store_config::process_set_fraction_store_config,
+        set_price_bounds::process_set_price_bounds,
+        set_redemption_paused::process_set_redemption_paused,
+    },
+    state::{
+        FractionCreatorSnapshot, FractionManager, FractionManagerStatus, FractionManagerSummary,
+        FractionManagerV1, FractionOriginalAuthorityLookup, FractionSafetyDepositConfig,
+        FractionStoreConfig, FractionVaultClaim, Key, Store, FRACTION_BASE_SAFETY_CONFIG_SIZE,
+        FRACTION_MANAGER_SUMMARY_SIZE, FRACTION_MINT, MAX_FRACTION_AUTHORITY_LOOKUP_SIZE,
+        MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, MAX_FRACTION_MANAGER_SIZE,
+        MAX_FRACTION_MANAGER_URI_LENGTH, MAX_FRACTION_STORE_CONFIG_SIZE,
+        MAX_FRACTION_VAULT_CLAIM_SIZE, PREFIX,
+    },
+    utils::{load_fraction_store_config, refund_prepaid_teardown_rent, split_buyout_proceeds},
+};
+use mpl_token_vault::state::{ExternalPriceAccount, Vault, MAX_VAULT_SIZE};
+use solana_program::{
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, rent::Rent},
+};
+use spl_token::state::Mint;
+use utils::*;
+
+mod set_redemption_paused {
+    use super::*;
+
+    fn set_paused(
+        fraction_manager: &FractionManagerV1,
+        signer: Pubkey,
+        is_signer: bool,
+        paused: bool,
+    ) -> Result<FractionManagerV1, ProgramError> {
+        let program_id = id();
+        let mut manager = TestAccount::new(program_id, fraction_manager_data(fraction_manager));
+        let mut authority = TestAccount::wallet(signer, is_signer);
+
+        process_set_redemption_paused(&program_id, &[manager.info(), authority.info()], paused)?;
+
+        Ok(manager.read())
+    }
+
+    #[test]
+    fn test_toggle_pause() {
+        let authority = Pubkey::new_unique();
+        let manager = make_fraction_manager(authority, FractionManagerStatus::Redeemable);
+
+        let paused = set_paused(&manager, authority, true, true).unwrap();
+        assert!(paused.state.redemption_paused);
+
+        let resumed = set_paused(&paused, authority, true, false).unwrap();
+        assert!(!resumed.state.redemption_paused);
+    }
+
+    #[test]
+    fn test_pause_requires_authority() {
+        let authority = Pubkey::new_unique();
+        let manager = make_fraction_manager(authority, FractionManagerStatus::Redeemable);
+
+        assert_eq!(
+            set_paused(&manager, Pubkey::new_unique(), true, true).unwrap_err(),
+            MetaplexError::AuctionManagerAuthorityMismatch.into()
+        );
+        assert_eq!(
+            set_paused(&manager, authority, false, true).unwrap_err(),
+            ProgramError::MissingRequiredSignature
+        );
+    }
+
+    #[test]
+    fn test_pause_requires_redeemable() {
+        let authority = Pubkey::new_unique();
+        let manager = make_fraction_manager(authority, FractionManagerStatus::Validated);
+
+        assert_eq!(
+            set_paused(&manager, authority, true, true).unwrap_err(),
+            MetaplexError::InvalidStatus.into()
+        );
+    }
+}
+
+mod price_bounds {
+    use super::*;
+
+    fn set_bounds(
+        fraction_manager: &FractionManagerV1,
+        min_price: Option<u64>,
+        max_price: Option<u64>,
+    ) -> Result<FractionManagerV1, ProgramError> {
+        let program_id = id();
+        let mut manager = TestAccount::new(program_id, fraction_manager_data(fraction_manager));
+        let mut authority = TestAccount::wallet(fraction_manager.authority, true);
+
+        process_set_price_bounds(
+            &program_id,
+            &[manager.info(), authority.info()],
+            min_price,
+            max_price,
+        )?;
+
+        Ok(manager.read())
+    }
+
+    #[test]
+    fn test_set_bounds() {
+        let manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Redeemable);
+        let bounded = set_bounds(&manager, Some(100), Some(200)).unwrap();
+        assert_eq!(bounded.price_bounds(), (Some(100), Some(200)));
+
+        let floored = set_bounds(&bounded, Some(100), None).unwrap();
+        assert_eq!(floored.price_bounds(), (Some(100), None));
+    }
+
+    #[test]
+    fn test_inverted_bounds() {
+        let manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Redeemable);
+
+        assert_eq!(
+            set_bounds(&manager, Some(200), Some(100)).unwrap_err(),
+            MetaplexError::InvalidPriceBounds.into()
+        );
+    }
+}
+
+mod init_fraction_manager {
+    use super::*;
+
+    #[test]
+    fn test_matching_pricing_lookup() {
+        assert!(InitFixture::new().assert_common_checks().is_ok());
+    }
+
+    #[test]
+    fn test_divergent_pricing_lookup() {
+        let mut fixture = InitFixture::new();
+        fixture.vault_state.pricing_lookup_address = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.assert_common_checks().err(),
+            Some(MetaplexError::PricingLookupMismatch.into())
+        );
+    }
+}
+
+mod init_fraction_manager_with_mint {
+    use super::*;
+
+    const FLOOR: u64 = 10;
+
+    pub fn fraction_mint_key(fraction_manager: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                id().as_ref(),
+                fraction_manager.as_ref(),
+                FRACTION_MINT.as_bytes(),
+            ],
+            &id(),
+        )
+        .0
+    }
+
+    pub fn init_with_mint(
+        fixture: &mut InitFixture,
+        fraction_mint: &mut TestAccount,
+        prepaid_teardown_rent: u64,
+    ) -> Result<FractionManagerV1, ProgramError> {
+        init_with_mint_checking(fixture, fraction_mint, prepaid_teardown_rent, None)
+    }
+
+    /// Initializes with check_vault_auction set when `vault_auction` is given
+    pub fn init_with_mint_checking(
+        fixture: &mut InitFixture,
+        fraction_mint: &mut TestAccount,
+        prepaid_teardown_rent: u64,
+        vault_auction: Option<&mut TestAccount>,
+    ) -> Result<FractionManagerV1, ProgramError> {
+        fixture.sync();
+        // Account creation is a no-op off-chain, so hand the manager its allocation up front.
+        fixture.fraction_manager.data = vec![0; MAX_FRACTION_MANAGER_SIZE];
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut token_program = TestAccount::wallet(spl_token::id(), false);
+        let check_vault_auction = vault_auction.is_some();
+
+        let mut accounts = vec![
+            fixture.fraction_manager.info(),
+            fixture.vault.info(),
+            fixture.token_mint.info(),
+            fixture.external_price_account.info(),
+            fixture.authority.info(),
+            payer.info(),
+            fixture.accept_payment.info(),
+            fixture.store.info(),
+            system.info(),
+            rent.info(),
+            fixture.vault_claim.info(),
+            fixture.fraction_store_config.info(),
+            fraction_mint.info(),
+            token_program.info(),
+        ];
+        if let Some(vault_auction) = vault_auction {
+            accounts.push(vault_auction.info());
+        }
+
+        process_init_fraction_manager_with_mint(
+            &fixture.program_id,
+            &accounts,
+            0,
+            None,
+            FLOOR,
+            prepaid_teardown_rent,
+            6,
+            check_vault_auction,
+        )?;
+
+        Ok(fixture.fraction_manager.read())
+    }
+
+    #[test]
+    fn test_init_with_mint() {
+        let mut fixture = InitFixture::new();
+        let mut fraction_mint =
+            TestAccount::wallet(fraction_mint_key(&fixture.fraction_manager.key), false);
+        fraction_mint.is_writable = true;
+
+        let manager = init_with_mint(&mut fixture, &mut fraction_mint, 0).unwrap();
+        assert_eq!(manager.key, Key::FractionManagerV1);
+        assert_eq!(manager.vault, fixture.vault.key);
+        assert_eq!(manager.state.status, FractionManagerStatus::Initialized);
+        assert_eq!(manager.state.min_redemption_price_per_share, FLOOR);
+        assert_eq!(manager.state.fraction_mint, Some(fraction_mint.key));
+    }
+
+    /// The manager is allocated with seeds that have to derive its own address, so signing with
+    /// anything but the vault key as the seed is refused like the runtime would.
+    #[test]
+    fn test_manager_allocation_signed_with_vault_seed() {
+        let stubs = ProgramStubs::install();
+        let mut fixture = InitFixture::new();
+        let mut fraction_mint =
+            TestAccount::wallet(fraction_mint_key(&fixture.fraction_manager.key), false);
+        fraction_mint.is_writable = true;
+
+        assert!(init_with_mint(&mut fixture, &mut fraction_mint, 0).is_ok());
+        assert!(stubs
+            .stubs
+            .invoked()
+            .iter()
+            .any(|instruction| instruction.program_id == system_program::id()
+                && instruction.accounts[0].pubkey == fixture.fraction_manager.key
+                && instruction.accounts[0].is_signer));
+    }
+
+    #[test]
+    fn test_preexisting_mint() {
+        let mut fixture = InitFixture::new();
+        let mut fraction_mint = TestAccount::new(spl_token::id(), vec![0; Mint::LEN]);
+        fraction_mint.key = fraction_mint_key(&fixture.fraction_manager.key);
+
+        assert_eq!(
+            init_with_mint(&mut fixture, &mut fraction_mint, 0).unwrap_err(),
+            MetaplexError::FractionMintAlreadyExists.into()
+        );
+    }
+
+    #[test]
+    fn test_mint_must_be_manager_derived() {
+        let mut fixture = InitFixture::new();
+        let mut fraction_mint = TestAccount::wallet(Pubkey::new_unique(), false);
+
+        assert_eq!(
+            init_with_mint(&mut fixture, &mut fraction_mint, 0).unwrap_err(),
+            MetaplexError::DerivedKeyInvalid.into()
+        );
+    }
+}
+
+mod prepaid_teardown_rent {
+    use super::{init_fraction_manager_with_mint::*, *};
+
+    const DEPOSIT: u64 = 1_000;
+
+    fn init(
+        teardown_rent_deposit: Option<u64>,
+        prepaid_teardown_rent: u64,
+    ) -> Result<FractionManagerV1, ProgramError> {
+        let mut fixture = InitFixture::new();
+        let mut config = FractionStoreConfig::new(fixture.store.key);
+        config.teardown_rent_deposit = teardown_rent_deposit;
+        fixture.fraction_store_config.owner = fixture.program_id;
+        fixture.fraction_store_config.data = account_data(&config, MAX_FRACTION_STORE_CONFIG_SIZE);
+        let mut fraction_mint =
+            TestAccount::wallet(fraction_mint_key(&fixture.fraction_manager.key), false);
+
+        init_with_mint(&mut fixture, &mut fraction_mint, prepaid_teardown_rent)
+    }
+
+    #[test]
+    fn test_prepay_recorded() {
+        let manager = init(Some(DEPOSIT), DEPOSIT + 1).unwrap();
+        assert_eq!(manager.state.prepaid_teardown_rent, DEPOSIT + 1);
+        assert_ne!(manager.teardown_rent_payer(), Pubkey::default());
+    }
+
+    #[test]
+    fn test_insufficient_prepay_rejected() {
+        assert_eq!(
+            init(Some(DEPOSIT), DEPOSIT - 1).unwrap_err(),
+            MetaplexError::InsufficientTeardownRentPrepay.into()
+        );
+    }
+
+    #[test]
+    fn test_no_deposit_required() {
+        let manager = init(None, 0).unwrap();
+        assert_eq!(manager.state.prepaid_teardown_rent, 0);
+    }
+
+    fn manager_account(status: FractionManagerStatus) -> (FractionManagerV1, TestAccount) {
+        let mut manager = make_fraction_manager(Pubkey::new_unique(), status);
+        manager.state.prepaid_teardown_rent = DEPOSIT;
+        let account = TestAccount::new(id(), fraction_manager_data(&manager));
+        (manager, account)
+    }
+
+    #[test]
+    fn test_refund_on_closed() {
+        let (mut manager, mut account) = manager_account(FractionManagerStatus::Closed);
+        let mut payer = TestAccount::wallet(manager.teardown_rent_payer(), false);
+        let lamports = account.lamports;
+
+        refund_prepaid_teardown_rent(&mut manager, &account.info(), &payer.info()).unwrap();
+
+        assert_eq!(account.lamports, lamports - DEPOSIT);
+        assert_eq!(payer.lamports, DEPOSIT);
+        assert_eq!(manager.state.prepaid_teardown_rent, 0);
+        assert_eq!(manager.total_rent_reclaimed(), DEPOSIT);
+    }
+
+    #[test]
+    fn test_refund_only_to_payer() {
+        let (mut manager, mut account) = manager_account(FractionManagerStatus::Closed);
+        let mut other = TestAccount::wallet(Pubkey::new_unique(), false);
+
+        assert_eq!(
+            refund_prepaid_teardown_rent(&mut manager, &account.info(), &other.info()),
+            Err(MetaplexError::TeardownRentPayerMismatch.into())
+        );
+        assert_eq!(other.lamports, 0);
+    }
+
+    #[test]
+    fn test_refund_requires_closed() {
+        let (mut manager, mut account) = manager_account(FractionManagerStatus::Combined);
+        let mut payer = TestAccount::wallet(manager.teardown_rent_payer(), false);
+
+        assert_eq!(
+            refund_prepaid_teardown_rent(&mut manager, &account.info(), &payer.info()),
+            Err(MetaplexError::InvalidStatus.into())
+        );
+        assert_eq!(payer.lamports, 0);
+    }
+}
+
+mod vault_auction {
+    use super::{init_fraction_manager_with_mint::*, *};
+
+    fn auction_in(fixture: &InitFixture, state: AuctionState) -> TestAccount {
+        let store: Store = fixture.store.read();
+        let auction = AuctionData {
+            authority: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            last_bid: None,
+            ended_at: None,
+            end_auction_at: None,
+            end_auction_gap: None,
+            price_floor: PriceFloor::None([0; 32]),
+            state,
+            bid_state: BidState::EnglishAuction {
+                bids: vec![],
+                max: 1,
+            },
+        };
+        let mut auction_account =
+            TestAccount::new(store.auction_program, auction.try_to_vec().unwrap());
+        auction_account.key = Pubkey::find_program_address(
+            &[
+                mpl_auction::PREFIX.as_bytes(),
+                store.auction_program.as_ref(),
+                fixture.vault.key.as_ref(),
+            ],
+            &store.auction_program,
+        )
+        .0;
+        auction_account
+    }
+
+    fn check(fixture: &mut InitFixture, auction: &mut TestAccount) -> ProgramResult {
+        let store: Store = fixture.store.read();
+        assert_vault_not_in_active_auction(&store, &fixture.vault.info(), &auction.info())
+    }
+
+    #[test]
+    fn test_rejects_unended_auction() {
+        let mut fixture = InitFixture::new();
+
+        for state in [AuctionState::Created, AuctionState::Started] {
+            let mut auction = auction_in(&fixture, state);
+            assert_eq!(
+                check(&mut fixture, &mut auction),
+                Err(MetaplexError::VaultInActiveAuction.into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_allows_ended_or_missing_auction() {
+        let mut fixture = InitFixture::new();
+
+        let mut ended = auction_in(&fixture, AuctionState::Ended);
+        assert_eq!(check(&mut fixture, &mut ended), Ok(()));
+
+        let mut missing = auction_in(&fixture, AuctionState::Started);
+        missing.data = vec![];
+        missing.owner = system_program::id();
+        assert_eq!(check(&mut fixture, &mut missing), Ok(()));
+    }
+
+    #[test]
+    fn test_rejects_auction_of_another_vault() {
+        let mut fixture = InitFixture::new();
+        let mut auction = auction_in(&fixture, AuctionState::Ended);
+        auction.key = Pubkey::new_unique();
+
+        assert_eq!(
+            check(&mut fixture, &mut auction),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    #[test]
+    fn test_init_with_flag() {
+        let mut fixture = InitFixture::new();
+        let mut fraction_mint =
+            TestAccount::wallet(fraction_mint_key(&fixture.fraction_manager.key), false);
+        fraction_mint.is_writable = true;
+
+        let mut active = auction_in(&fixture, AuctionState::Started);
+        assert_eq!(
+            init_with_mint_checking(&mut fixture, &mut fraction_mint, 0, Some(&mut active))
+                .unwrap_err(),
+            MetaplexError::VaultInActiveAuction.into()
+        );
+
+        let mut ended = auction_in(&fixture, AuctionState::Ended);
+        assert!(
+            init_with_mint_checking(&mut fixture, &mut fraction_mint, 0, Some(&mut ended)).is_ok()
+        );
+    }
+}
+
+mod vault_generations {
+    use super::*;
+
+    /// Simulates one fractionalize/teardown cycle on the fixture's vault
+    fn new_generation(fixture: &mut InitFixture) -> ProgramResult {
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let fraction_store_config = load_fraction_store_config(
+            &fixture.program_id,
+            &fixture.store.key,
+            &fixture.fraction_store_config.info(),
+        )
+        .unwrap();
+
+        claim_vault_generation(
+            &fixture.program_id,
+            &fixture.vault.info(),
+            &fixture.vault_claim.info(),
+            &fraction_store_config,
+            &payer.info(),
+            &rent.info(),
+            &system.info(),
+        )
+    }
+
+    fn cap_generations(fixture: &mut InitFixture, max_manager_generations: Option<u64>) {
+        let mut config = FractionStoreConfig::new(fixture.store.key);
+        config.max_manager_generations = max_manager_generations;
+        fixture.fraction_store_config.owner = fixture.program_id;
+        fixture.fraction_store_config.data = account_data(&config, MAX_FRACTION_STORE_CONFIG_SIZE);
+    }
+
+    #[test]
+    fn test_cycle_up_to_and_over_limit() {
+        let mut fixture = InitFixture::new();
+        cap_generations(&mut fixture, Some(2));
+
+        assert_eq!(new_generation(&mut fixture), Ok(()));
+        assert_eq!(new_generation(&mut fixture), Ok(()));
+        assert_eq!(
+            new_generation(&mut fixture),
+            Err(MetaplexError::VaultFractionalizationLimitReached.into())
+        );
+
+        let claim: FractionVaultClaim = fixture.vault_claim.read();
+        assert_eq!(claim.key, Key::FractionVaultClaimV1);
+        assert_eq!(claim.vault, fixture.vault.key);
+        assert_eq!(claim.manager_generations, 2);
+    }
+
+    #[test]
+    fn test_no_limit_configured() {
+        let mut fixture = InitFixture::new();
+
+        for _ in 0..5 {
+            assert_eq!(new_generation(&mut fixture), Ok(()));
+        }
+        assert_eq!(
+            fixture
+                .vault_claim
+                .read::<FractionVaultClaim>()
+                .manager_generations,
+            5
+        );
+    }
+
+    #[test]
+    fn test_claim_must_be_vault_derived() {
+        let mut fixture = InitFixture::new();
+        fixture.vault_claim.key = Pubkey::new_unique();
+
+        assert_eq!(
+            new_generation(&mut fixture),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    #[test]
+    fn test_set_fraction_store_config() {
+        let program_id = id();
+        let mut admin = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut store = TestAccount::new(program_id, vec![]);
+        store.key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), program_id.as_ref(), admin.key.as_ref()],
+            &program_id,
+        )
+        .0;
+        let mut config = TestAccount::new(program_id, vec![0; MAX_FRACTION_STORE_CONFIG_SIZE]);
+        config.key = fraction_store_config_key(&program_id, &store.key);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let metadata_fork = Pubkey::new_unique();
+
+        process_set_fraction_store_config(
+            &program_id,
+            &[
+                config.info(),
+                admin.info(),
+                payer.info(),
+                store.info(),
+                system.info(),
+                rent.info(),
+            ],
+            Some(3),
+            vec![],
+            250,
+            Some(1_000),
+            vec![metadata_fork],
+        )
+        .unwrap();
+
+        let config: FractionStoreConfig = config.read();
+        assert_eq!(config.key, Key::FractionStoreConfigV1);
+        assert_eq!(config.store, store.key);
+        assert_eq!(config.max_manager_generations, Some(3));
+        assert_eq!(config.buyout_fee_bps, 250);
+        assert_eq!(config.teardown_rent_deposit, Some(1_000));
+        assert_eq!(config.approved_metadata_programs, vec![metadata_fork]);
+    }
+}
+
+mod buyout_fee {
+    use super::*;
+
+    fn config_with_fee(buyout_fee_bps: u16) -> FractionStoreConfig {
+        let mut config = FractionStoreConfig::new(Pubkey::new_unique());
+        config.buyout_fee_bps = buyout_fee_bps;
+        config
+    }
+
+    #[test]
+    fn test_fee_and_residual() {
+        assert_eq!(
+            split_buyout_proceeds(&config_with_fee(250), 2_000_000),
+            Ok((50_000, 1_950_000))
+        );
+    }
+
+    #[test]
+    fn test_rounding_goes_to_store() {
+        // 2.5% of 1_000_001 is 25_000.025
+        assert_eq!(
+            split_buyout_proceeds(&config_with_fee(250), 1_000_001),
+            Ok((25_001, 975_000))
+        );
+        assert_eq!(split_buyout_proceeds(&config_with_fee(1), 1), Ok((1, 0)));
+    }
+
+    #[test]
+    fn test_no_fee() {
+        assert_eq!(
+            split_buyout_proceeds(&config_with_fee(0), 1_000_001),
+            Ok((0, 1_000_001))
+        );
+    }
+
+    #[test]
+    fn test_large_amount_does_not_overflow() {
+        assert_eq!(
+            split_buyout_proceeds(&config_with_fee(10000), u64::MAX),
+            Ok((u64::MAX, 0))
+        );
+        assert_eq!(
+            split_buyout_proceeds(&config_with_fee(5000), u64::MAX),
+            Ok((u64::MAX / 2 + 1, u64::MAX / 2))
+        );
+    }
+
+    #[test]
+    fn test_fee_above_whole_rejected() {
+        let program_id = id();
+        let mut admin = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut store = TestAccount::new(program_id, vec![]);
+        let mut config = TestAccount::new(program_id, vec![0; MAX_FRACTION_STORE_CONFIG_SIZE]);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+        assert_eq!(
+            process_set_fraction_store_config(
+                &program_id,
+                &[
+                    config.info(),
+                    admin.info(),
+                    payer.info(),
+                    store.info(),
+                    system.info(),
+                    rent.info(),
+                ],
+                None,
+                vec![],
+                10001,
+                None,
+                vec![],
+            ),
+            Err(MetaplexError::InvalidBuyoutFeeBps.into())
+        );
+    }
+}
+
+mod summary {
+    use super::*;
+
+    #[test]
+    fn test_summary_round_trip() {
+        let manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Redeemable);
+        let mut vault: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        vault.fraction_mint = Pubkey::new_unique();
+        let fraction_mint = Mint {
+            mint_authority: COption::None,
+            supply: 1_000,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let external_price_account = ExternalPriceAccount {
+            key: mpl_token_vault::state::Key::ExternalAccountKeyV1,
+            price_per_share: 25,
+            price_mint: Pubkey::new_unique(),
+            allowed_to_combine: true,
+        };
+
+        let summary = manager.summary(&vault, &fraction_mint, &external_price_account);
+        assert_eq!(summary.status, FractionManagerStatus::Redeemable);
+        assert_eq!(summary.vault, manager.vault);
+        assert_eq!(summary.fraction_mint, vault.fraction_mint);
+        assert_eq!(summary.fraction_shares, 1_000);
+        assert_eq!(summary.price_per_share, 25);
+
+        let data = summary.try_to_vec().unwrap();
+        assert_eq!(data.len(), FRACTION_MANAGER_SUMMARY_SIZE);
+        assert_eq!(
+            FractionManagerSummary::try_from_slice(&data).unwrap(),
+            summary
+        );
+
+        // A manager that created its own fraction mint reports that mint
+        let mut manager = manager;
+        manager.state.fraction_mint = Some(Pubkey::new_unique());
+        let summary = manager.summary(&vault, &fraction_mint, &external_price_account);
+        assert_eq!(summary.fraction_mint, manager.state.fraction_mint.unwrap());
+    }
+}
+
+mod discriminants {
+    use super::*;
+
+    type Load = fn(&AccountInfo) -> Result<(), ProgramError>;
+
+    /// `data` is a valid account of the type `load` reads
+    fn assert_discriminant_checked(data: Vec<u8>, load: Load) {
+        let mut account = TestAccount::new(id(), data.clone());
+        assert_eq!(load(&account.info()), Ok(()));
+
+        for key in [
+            Key::Uninitialized,
+            Key::AuctionManagerV2,
+            Key::FractionManagerV1,
+        ] {
+            if key as u8 == data[0] {
+                continue;
+            }
+            let mut data = data.clone();
+            data[0] = key as u8;
+            let mut account = TestAccount::new(id(), data);
+
+            assert_eq!(
+                load(&account.info()),
+                Err(MetaplexError::DataTypeMismatch.into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_fraction_manager() {
+        let manager = make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Validated);
+
+        assert_discriminant_checked(fraction_manager_data(&manager), |a| {
+            FractionManagerV1::from_account_info(a).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fraction_store_config() {
+        let config = FractionStoreConfig::new(Pubkey::new_unique());
+
+        assert_discriminant_checked(account_data(&config, MAX_FRACTION_STORE_CONFIG_SIZE), |a| {
+            FractionStoreConfig::from_account_info(a).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fraction_vault_claim() {
+        let claim = FractionVaultClaim {
+            key: Key::FractionVaultClaimV1,
+            vault: Pubkey::new_unique(),
+            manager_generations: 1,
+        };
+
+        assert_discriminant_checked(account_data(&claim, MAX_FRACTION_VAULT_CLAIM_SIZE), |a| {
+            FractionVaultClaim::from_account_info(a).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fraction_creator_snapshot() {
+        let snapshot = FractionCreatorSnapshot {
+            key: Key::FractionCreatorSnapshotV1,
+            fraction_manager: Pubkey::new_unique(),
+            metadata: Pubkey::new_unique(),
+            creators: vec![],
+        };
+
+        assert_discriminant_checked(
+            account_data(&snapshot, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE),
+            |a| FractionCreatorSnapshot::from_account_info(a).map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn test_fraction_original_authority_lookup() {
+        let lookup = FractionOriginalAuthorityLookup {
+            key: Key::FractionOriginalAuthorityLookupV1,
+            original_authority: Pubkey::new_unique(),
+            pda_authority: false,
+            return_to: None,
+        };
+
+        assert_discriminant_checked(
+            account_data(&lookup, MAX_FRACTION_AUTHORITY_LOOKUP_SIZE),
+            |a| FractionOriginalAuthorityLookup::from_account_info(a).map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn test_fraction_safety_deposit_config() {
+        let fraction_manager = Pubkey::new_unique();
+        let mut account = TestAccount::new(id(), vec![0; FRACTION_BASE_SAFETY_CONFIG_SIZE]);
+        make_config(fraction_manager, 0)
+            .create(&account.info(), &fraction_manager)
+            .unwrap();
+
+        assert_discriminant_checked(account.data, |a| {
+            FractionSafetyDepositConfig::from_account_info(a).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_initializing_accepts_fresh_account() {
+        let mut manager = TestAccount::new(id(), vec![0; MAX_FRACTION_MANAGER_SIZE]);
+        let mut claim = TestAccount::new(id(), vec![0; MAX_FRACTION_VAULT_CLAIM_SIZE]);
+
+        assert!(FractionManagerV1::from_account_info_or_uninitialized(&manager.info()).is_ok());
+        assert!(FractionVaultClaim::from_account_info_or_uninitialized(&claim.info()).is_ok());
+
+        // Still only an uninitialized buffer or one of its own type
+        let mut data = vec![0; MAX_FRACTION_VAULT_CLAIM_SIZE];
+        data[0] = Key::FractionManagerV1 as u8;
+        let mut other = TestAccount::new(id(), data);
+        assert_eq!(
+            FractionVaultClaim::from_account_info_or_uninitialized(&other.info()).err(),
+            Some(MetaplexError::DataTypeMismatch.into())
+        );
+    }
+}
+
+mod metadata_uri {
+    use super::*;
+
+    const URI: &str = "https://example.com/fraction.json";
+
+    fn set_uri(manager: &mut TestAccount, authority: &mut TestAccount, uri: &str) -> ProgramResult {
+        process_set_fraction_manager_metadata_uri(
+            &id(),
+            &[manager.info(), authority.info()],
+            uri.to_string(),
+        )
+    }
+
+    fn lock_uri(manager: &mut TestAccount, authority: &mut TestAccount) -> ProgramResult {
+        process_set_fraction_manager_metadata_uri_immutable(
+            &id(),
+            &[manager.info(), authority.info()],
+        )
+    }
+
+    fn validated_manager() -> (TestAccount, TestAccount) {
+        let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        let manager = TestAccount::new(
+            id(),
+            fraction_manager_data(&make_fraction_manager(
+                authority.key,
+                FractionManagerStatus::Validated,
+            )),
+        );
+        (manager, authority)
+    }
+
+    #[test]
+    fn test_update_before_locking() {
+        let (mut manager, mut authority) = validated_manager();
+
+        assert_eq!(set_uri(&mut manager, &mut authority, URI), Ok(()));
+        let updated: FractionManagerV1 = manager.read();
+        assert_eq!(updated.metadata_uri(), URI);
+        assert!(!updated.uri_immutable());
+
+        assert_eq!(set_uri(&mut manager, &mut authority, ""), Ok(()));
+        assert_eq!(manager.read::<FractionManagerV1>().metadata_uri(), "");
+    }
+
+    #[test]
+    fn test_blocked_after_locking() {
+        let (mut manager, mut authority) = validated_manager();
+        set_uri(&mut manager, &mut authority, URI).unwrap();
+
+        assert_eq!(lock_uri(&mut manager, &mut authority), Ok(()));
+        assert!(manager.read::<FractionManagerV1>().uri_immutable());
+
+        assert_eq!(
+            set_uri(
+                &mut manager,
+                &mut authority,
+                "https://example.com/other.json"
+            ),
+            Err(MetaplexError::ManagerUriImmutable.into())
+        );
+        assert_eq!(manager.read::<FractionManagerV1>().metadata_uri(), URI);
+
+        // Locking again is harmless and still leaves it locked
+        assert_eq!(lock_uri(&mut manager, &mut authority), Ok(()));
+        assert!(manager.read::<FractionManagerV1>().uri_immutable());
+    }
+
+    #[test]
+    fn test_lock_requires_validated() {
+        let mut authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut manager = TestAccount::new(
+            id(),
+            fraction_manager_data(&make_fraction_manager(
+                authority.key,
+                FractionManagerStatus::Initialized,
+            )),
+        );
+
+        assert_eq!(
+            lock_uri(&mut manager, &mut authority),
+            Err(MetaplexError::InvalidStatus.into())
+        );
+    }
+
+    #[test]
+    fn test_requires_authority() {
+        let (mut manager, _) = validated_manager();
+        let mut other = TestAccount::wallet(Pubkey::new_unique(), true);
+
+        assert_eq!(
+            set_uri(&mut manager, &mut other, URI),
+            Err(MetaplexError::AuctionManagerAuthorityMismatch.into())
+        );
+        assert_eq!(
+            lock_uri(&mut manager, &mut other),
+            Err(MetaplexError::AuctionManagerAuthorityMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_uri_length_capped() {
+        let (mut manager, mut authority) = validated_manager();
+
+        assert_eq!(
+            set_uri(
+                &mut manager,
+                &mut authority,
+                &"a".repeat(MAX_FRACTION_MANAGER_URI_LENGTH + 1)
+            ),
+            Err(MetaplexError::ManagerUriTooLong.into())
+        );
+        assert_eq!(
+            set_uri(
+                &mut manager,
+                &mut authority,
+                &"a".repeat(MAX_FRACTION_MANAGER_URI_LENGTH)
+            ),
+            Ok(())
+        );
+    }
+}
+
+mod migrate_fraction_manager {
+    use super::*;
+    use mpl_metaplex::{
+        processor::migrate_fraction_manager::process_migrate_fraction_manager,
+        state::{
+            FractionManagerLegacyState, FractionManagerLegacyV1, LEGACY_FRACTION_MANAGER_SIZE,
+        },
+    };
+
+    fn legacy_manager(authority: Pubkey, vault: Pubkey) -> FractionManagerLegacyV1 {
+        FractionManagerLegacyV1 {
+            key: Key::FractionManagerV1,
+            store: Pubkey::new_unique(),
+            authority,
+            vault,
+            token_mint: Pubkey::new_unique(),
+            accept_payment: Pubkey::new_unique(),
+            state: FractionManagerLegacyState {
+                status: FractionManagerStatus::Active,
+                safety_config_items_validated: 2,
+                token_pools_active: 1,
+                has_participation: false,
+            },
+        }
+    }
+
+    fn make_vault() -> Vault {
+        let mut vault: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        vault.key = mpl_token_vault::state::Key::VaultV1;
+        vault.token_type_count = 2;
+        vault.pricing_lookup_address = Pubkey::new_unique();
+        vault
+    }
+
+    struct Fixture {
+        manager: TestAccount,
+        authority: TestAccount,
+        vault: TestAccount,
+        payer: TestAccount,
+        system: TestAccount,
+        rent: TestAccount,
+    }
+
+    impl Fixture {
+        fn new(manager_data: Vec<u8>) -> Fixture {
+            let program_id = id();
+            let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+            let mut vault = TestAccount::new(
+                mpl_token_vault::id(),
+                account_data(&make_vault(), MAX_VAULT_SIZE),
+            );
+            vault.key = Pubkey::new_unique();
+            let mut manager = TestAccount::new(program_id, manager_data);
+            manager.key =
+                Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.key.as_ref()], &program_id)
+                    .0;
+            Fixture {
+                manager,
+                authority,
+                vault,
+                payer: TestAccount::wallet(Pubkey::new_unique(), true),
+                system: TestAccount::wallet(system_program::id(), false),
+                rent: TestAccount::sysvar(sysvar::rent::id(), &Rent::default()),
+            }
+        }
+
+        fn legacy() -> Fixture {
+            let mut fixture = Fixture::new(Vec::new());
+            let legacy = legacy_manager(fixture.authority.key, fixture.vault.key);
+            fixture.manager.data = account_data(&legacy, LEGACY_FRACTION_MANAGER_SIZE);
+            fixture
+        }
+
+        fn migrate(&mut self) -> ProgramResult {
+            process_migrate_fraction_manager(
+                &id(),
+                &[
+                    self.manager.info(),
+                    self.authority.info(),
+                    self.vault.info(),
+                    self.payer.info(),
+                    self.system.info(),
+                    self.rent.info(),
+                ],
+            )
+        }
+    }
+
+    #[test]
+    fn test_legacy_size_is_original_layout() {
+        assert_eq!(LEGACY_FRACTION_MANAGER_SIZE, 379);
+        assert!(LEGACY_FRACTION_MANAGER_SIZE < MAX_FRACTION_MANAGER_SIZE);
+    }
+
+    #[test]
+    fn test_legacy_manager_unreadable_until_migrated() {
+        let mut fixture = Fixture::legacy();
+
+        assert_eq!(
+            FractionManagerV1::from_account_info(&fixture.manager.info()).unwrap_err(),
+            MetaplexError::DataTypeMismatch.into()
+        );
+        let legacy = FractionManagerLegacyV1::from_account_info(&fixture.manager.info()).unwrap();
+        assert_eq!(legacy.authority, fixture.authority.key);
+        assert_eq!(legacy.state.status, FractionManagerStatus::Active);
+    }
+
+    #[test]
+    fn test_migrate_carries_original_fields() {
+        let vault = make_vault();
+        let legacy = legacy_manager(Pubkey::new_unique(), Pubkey::new_unique());
+        let payer = Pubkey::new_unique();
+
+        let migrated = legacy.clone().migrate(&vault, &payer);
+
+        assert_eq!(migrated.key, Key::FractionManagerV1);
+        assert_eq!(migrated.store, legacy.store);
+        assert_eq!(migrated.authority, legacy.authority);
+        assert_eq!(migrated.vault, legacy.vault);
+        assert_eq!(migrated.token_mint, legacy.token_mint);
+        assert_eq!(migrated.accept_payment, legacy.accept_payment);
+        assert_eq!(migrated.status(), FractionManagerStatus::Active);
+        assert_eq!(migrated.state.safety_config_items_validated, 2);
+        assert_eq!(migrated.state.token_pools_active, 1);
+        assert_eq!(
+            migrated.state.vault_token_type_count,
+            vault.token_type_count
+        );
+        assert_eq!(migrated.external_pricing, vault.pricing_lookup_address);
+        assert_eq!(migrated.teardown_rent_payer(), payer);
+        assert_eq!(migrated.prepaid_teardown_rent(), 0);
+        assert_eq!(migrated.state.fraction_mint, None);
+        // One config per validated box, for teardown to close
+        assert_eq!(migrated.open_records(), 2);
+    }
+
+    #[test]
+    fn test_migrated_manager_fits_current_layout() {
+        let migrated = legacy_manager(Pubkey::new_unique(), Pubkey::new_unique())
+            .migrate(&make_vault(), &Pubkey::new_unique());
+        let mut manager = TestAccount::new(id(), fraction_manager_data(&migrated));
+
+        let read = FractionManagerV1::from_account_info(&manager.info()).unwrap();
+        assert_eq!(read.authority, migrated.authority);
+        assert_eq!(read.open_records(), 2);
+    }
+
+    #[test]
+    fn test_current_manager_not_migrated() {
+        let mut fixture = Fixture::new(Vec::new());
+        let mut current =
+            make_fraction_manager(fixture.authority.key, FractionManagerStatus::Active);
+        current.vault = fixture.vault.key;
+        fixture.manager.data = fraction_manager_data(&current);
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(MetaplexError::FractionManagerNotLegacy.into())
+        );
+        assert_eq!(fixture.manager.data.len(), MAX_FRACTION_MANAGER_SIZE);
+    }
+
+    #[test]
+    fn test_requires_manager_authority() {
+        let mut fixture = Fixture::legacy();
+        fixture.authority = TestAccount::wallet(Pubkey::new_unique(), true);
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(MetaplexError::AuctionManagerAuthorityMismatch.into())
+        );
+
+        let mut fixture = Fixture::legacy();
+        fixture.authority.is_signer = false;
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(fixture.manager.data.len(), LEGACY_FRACTION_MANAGER_SIZE);
+    }
+
+    #[test]
+    fn test_requires_payer_signature() {
+        let mut fixture = Fixture::legacy();
+        fixture.payer.is_signer = false;
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_requires_manager_vault() {
+        let mut fixture = Fixture::legacy();
+        fixture.vault.key = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(MetaplexError::FractionManagerVaultMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_requires_manager_address() {
+        let mut fixture = Fixture::legacy();
+        fixture.manager.key = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.migrate(),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+}
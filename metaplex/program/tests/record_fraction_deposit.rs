@@ -0,0 +1,238 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    processor::record_fraction_deposit::process_record_fraction_deposit,
+    state::{
+        FractionDepositRecord, FractionManagerStatus, FractionManagerV1, FractionWinningConfigType,
+        Key, DEPOSIT, MAX_FRACTION_DEPOSIT_RECORD_SIZE, PREFIX,
+    },
+};
+use mpl_token_metadata::state::MAX_METADATA_LEN;
+use mpl_token_vault::state::VaultState;
+use solana_program::{
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, rent::Rent},
+};
+use spl_token::state::{Account as TokenAccount, AccountState};
+use utils::*;
+
+/// A master edition box whose depositor still holds the NFT, in a vault still being filled
+struct DepositFixture {
+    validation: ValidationFixture,
+    deposit_record: TestAccount,
+    depositor: TestAccount,
+    depositor_token_account: TestAccount,
+}
+
+impl DepositFixture {
+    fn new() -> DepositFixture {
+        let mut validation = ValidationFixture::new();
+        validation.winning_config_type = FractionWinningConfigType::FractionMasterEditionV2;
+        validation.edition = master_edition(&validation.mint.key);
+        validation.vault_state.state = VaultState::Inactive;
+        validation.original_authority_lookup.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                validation.vault.key.as_ref(),
+                validation.metadata.key.as_ref(),
+            ],
+            &validation.program_id,
+        )
+        .0;
+        validation.original_authority_lookup.lamports = 1_000_000_000;
+        validation.metadata.data = account_data(&validation.metadata_state, MAX_METADATA_LEN);
+
+        // Account creation is a no-op off-chain, so the record starts out allocated
+        let mut deposit_record = TestAccount::new(
+            validation.program_id,
+            vec![0; MAX_FRACTION_DEPOSIT_RECORD_SIZE],
+        );
+        deposit_record.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                validation.program_id.as_ref(),
+                validation.vault.key.as_ref(),
+                validation.mint.key.as_ref(),
+                DEPOSIT.as_bytes(),
+            ],
+            &validation.program_id,
+        )
+        .0;
+
+        let depositor = TestAccount::wallet(Pubkey::new_unique(), true);
+        let depositor_token_account =
+            TestAccount::new(spl_token::id(), holding(&validation, depositor.key));
+
+        DepositFixture {
+            validation,
+            deposit_record,
+            depositor,
+            depositor_token_account,
+        }
+    }
+
+    /// Runs RecordFractionDeposit
+    fn record(&mut self) -> ProgramResult {
+        let fixture = &mut self.validation;
+        fixture.sync();
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+        process_record_fraction_deposit(
+            &fixture.program_id,
+            &[
+                self.deposit_record.info(),
+                fixture.vault.info(),
+                fixture.metadata.info(),
+                self.depositor.info(),
+                self.depositor_token_account.info(),
+                payer.info(),
+                fixture.store.info(),
+                system.info(),
+                rent.info(),
+            ],
+        )
+    }
+
+    /// Runs ValidateFractionSafetyDepositBox with require_depositor_custody set, returning the
+    /// manager it saved
+    fn validate(&mut self) -> Result<FractionManagerV1, ProgramError> {
+        self.validation
+            .process_validate(Some((&mut self.depositor, &mut self.deposit_record)))?;
+
+        Ok(self.validation.fraction_manager.read())
+    }
+
+    /// The depositor added the NFT and the vault was activated
+    fn deposit_into_vault(&mut self) {
+        let vault_authority = vault_authority(&self.validation.vault.key);
+        self.validation.safety_deposit_token_store.data =
+            holding(&self.validation, vault_authority);
+        self.depositor_token_account.data = vec![];
+        self.validation.vault_state.state = VaultState::Active;
+    }
+}
+
+/// A token account of `owner` holding the box's NFT
+fn holding(fixture: &ValidationFixture, owner: Pubkey) -> Vec<u8> {
+    packed_data(TokenAccount {
+        mint: fixture.mint.key,
+        owner,
+        amount: 1,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    })
+}
+
+#[test]
+fn test_deposit_recorded() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+
+    assert_eq!(fixture.record(), Ok(()));
+
+    let record: FractionDepositRecord = fixture.deposit_record.read();
+    assert_eq!(record.key, Key::FractionDepositRecordV1);
+    assert_eq!(record.vault, fixture.validation.vault.key);
+    assert_eq!(record.mint, fixture.validation.mint.key);
+    assert_eq!(record.depositor, fixture.depositor.key);
+}
+
+#[test]
+fn test_non_owner_cannot_record() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    fixture.depositor_token_account.data = holding(&fixture.validation, Pubkey::new_unique());
+
+    assert_eq!(
+        fixture.record(),
+        Err(MetaplexError::DepositorDoesNotOwnNft.into())
+    );
+}
+
+#[test]
+fn test_recorded_once() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    assert_eq!(fixture.record(), Ok(()));
+
+    fixture.depositor = TestAccount::wallet(Pubkey::new_unique(), true);
+    fixture.depositor_token_account.data = holding(&fixture.validation, fixture.depositor.key);
+    assert_eq!(
+        fixture.record(),
+        Err(MetaplexError::AlreadyInitialized.into())
+    );
+}
+
+#[test]
+fn test_record_after_vault_activated() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    fixture.validation.vault_state.state = VaultState::Active;
+
+    assert_eq!(
+        fixture.record(),
+        Err(MetaplexError::DepositRecordedTooLate.into())
+    );
+}
+
+#[test]
+fn test_validate_recorded_master_edition_box() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    assert_eq!(fixture.record(), Ok(()));
+    fixture.deposit_into_vault();
+
+    let manager = fixture.validate().unwrap();
+    assert_eq!(manager.state.status, FractionManagerStatus::Validated);
+    assert_eq!(manager.state.safety_config_items_validated, 1);
+}
+
+#[test]
+fn test_validate_without_record() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    fixture.deposit_into_vault();
+
+    assert_eq!(
+        fixture.validate().map(|_| ()),
+        Err(MetaplexError::DepositorDoesNotOwnNft.into())
+    );
+}
+
+#[test]
+fn test_validate_record_of_other_depositor() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    assert_eq!(fixture.record(), Ok(()));
+    fixture.deposit_into_vault();
+    fixture.depositor = TestAccount::wallet(Pubkey::new_unique(), true);
+
+    assert_eq!(
+        fixture.validate().map(|_| ()),
+        Err(MetaplexError::DepositRecordMismatch.into())
+    );
+}
+
+#[test]
+fn test_depositor_must_sign_validation() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = DepositFixture::new();
+    assert_eq!(fixture.record(), Ok(()));
+    fixture.deposit_into_vault();
+    fixture.depositor.is_signer = false;
+
+    assert_eq!(
+        fixture.validate().map(|_| ()),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
@@ -0,0 +1,185 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    processor::redeem_fraction_shares::process_redeem_fraction_shares,
+    state::{FractionManagerStatus, FractionManagerV1},
+};
+use solana_program::{entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey};
+use spl_token::instruction::TokenInstruction;
+use spl_token::state::{Account as TokenAccount, AccountState};
+use utils::*;
+
+/// A fixture whose `supply` shares were bought out, leaving `holder_proceeds` for holders
+fn bought_out(supply: u64, holder_proceeds: u64) -> BuyoutFixture {
+    let mut fixture = BuyoutFixture::new();
+    let state = &mut fixture.fraction_manager_state.state;
+    state.status = FractionManagerStatus::Combined;
+    state.buyout_holder_proceeds = holder_proceeds;
+    state.buyout_shares = supply;
+    fixture.fraction_mint_state.supply = supply;
+    fixture
+}
+
+/// Redeems a holder's account of `shares` of `mint`, saving the manager back into the fixture
+fn redeem_from(fixture: &mut BuyoutFixture, mint: Pubkey, shares: u64) -> ProgramResult {
+    fixture.sync();
+    let init = &mut fixture.init;
+    let mut holder = TestAccount::wallet(Pubkey::new_unique(), true);
+    let mut holder_shares = TestAccount::new(
+        spl_token::id(),
+        packed_data(TokenAccount {
+            mint,
+            owner: holder.key,
+            amount: shares,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }),
+    );
+    let mut destination = TestAccount::new(spl_token::id(), vec![]);
+    let mut token_program = TestAccount::wallet(spl_token::id(), false);
+
+    process_redeem_fraction_shares(
+        &init.program_id,
+        &[
+            init.fraction_manager.info(),
+            init.vault.info(),
+            fixture.fraction_mint.info(),
+            holder_shares.info(),
+            holder.info(),
+            init.accept_payment.info(),
+            destination.info(),
+            init.store.info(),
+            token_program.info(),
+        ],
+    )?;
+    fixture.fraction_manager_state = init.fraction_manager.read::<FractionManagerV1>();
+
+    Ok(())
+}
+
+fn redeem(fixture: &mut BuyoutFixture, shares: u64) -> ProgramResult {
+    let mint = fixture.fraction_mint.key;
+    redeem_from(fixture, mint, shares)
+}
+
+/// Shares burned and tokens paid by every token instruction invoked, in order
+fn burns_and_payouts(stubs: &ProgramStubs) -> Vec<(&'static str, u64)> {
+    stubs
+        .invoked()
+        .iter()
+        .filter(|instruction| instruction.program_id == spl_token::id())
+        .map(
+            |instruction| match TokenInstruction::unpack(&instruction.data).unwrap() {
+                TokenInstruction::Burn { amount } => ("burn", amount),
+                TokenInstruction::Transfer { amount } => ("pay", amount),
+                other => panic!("unexpected token instruction {:?}", other),
+            },
+        )
+        .collect()
+}
+
+#[test]
+fn test_redeem_pays_pro_rata() {
+    let installed = ProgramStubs::install();
+    let mut fixture = bought_out(3, 100);
+
+    redeem(&mut fixture, 1).unwrap();
+    redeem(&mut fixture, 1).unwrap();
+    // The last share takes the dust the others rounded away
+    redeem(&mut fixture, 1).unwrap();
+
+    assert_eq!(
+        burns_and_payouts(&installed.stubs),
+        vec![
+            ("burn", 1),
+            ("pay", 33),
+            ("burn", 1),
+            ("pay", 33),
+            ("burn", 1),
+            ("pay", 34)
+        ]
+    );
+    let state = &fixture.fraction_manager_state.state;
+    assert_eq!(state.shares_redeemed, 3);
+    assert_eq!(state.holder_proceeds_paid, 100);
+}
+
+#[test]
+fn test_redeem_pays_from_accept_payment_as_manager() {
+    let installed = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+
+    redeem(&mut fixture, 4).unwrap();
+
+    let invoked = installed.stubs.invoked();
+    let payout = invoked.last().unwrap();
+    assert_eq!(payout.accounts[0].pubkey, fixture.init.accept_payment.key);
+    assert_eq!(payout.accounts[2].pubkey, fixture.init.fraction_manager.key);
+    assert_eq!(
+        burns_and_payouts(&installed.stubs),
+        vec![("burn", 4), ("pay", 400)]
+    );
+}
+
+#[test]
+fn test_redeem_requires_buyout() {
+    let installed = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+    fixture.fraction_manager_state.state.status = FractionManagerStatus::Redeemable;
+
+    assert_eq!(
+        redeem(&mut fixture, 1),
+        Err(MetaplexError::InvalidStatus.into())
+    );
+    assert_eq!(installed.stubs.invoked(), vec![]);
+}
+
+#[test]
+fn test_paused_redemption() {
+    let installed = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+    fixture.fraction_manager_state.state.redemption_paused = true;
+
+    assert_eq!(
+        redeem(&mut fixture, 1),
+        Err(MetaplexError::RedemptionPaused.into())
+    );
+    assert_eq!(installed.stubs.invoked(), vec![]);
+}
+
+#[test]
+fn test_redeem_shares_of_other_mint() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+
+    assert_eq!(
+        redeem_from(&mut fixture, Pubkey::new_unique(), 1),
+        Err(MetaplexError::FractionMintMismatch.into())
+    );
+}
+
+#[test]
+fn test_redeem_no_shares() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+
+    assert_eq!(
+        redeem(&mut fixture, 0),
+        Err(MetaplexError::NoFractionShares.into())
+    );
+}
+
+#[test]
+fn test_redeem_more_than_bought_out() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = bought_out(10, 1_000);
+
+    assert_eq!(
+        redeem(&mut fixture, 11),
+        Err(MetaplexError::NumericalOverflowError.into())
+    );
+}
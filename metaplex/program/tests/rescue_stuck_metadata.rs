@@ -0,0 +1,385 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    processor::{
+        dispute_metadata_rescue::process_dispute_metadata_rescue,
+        rescue_stuck_metadata::process_rescue_stuck_metadata,
+    },
+    state::{
+        FractionManagerStatus, FractionManagerV1, FractionMetadataRescue, Key, Store,
+        MAX_FRACTION_METADATA_RESCUE_SIZE, MAX_STORE_SIZE, PREFIX, RESCUE, RESCUE_DISPUTE_WINDOW,
+    },
+};
+use mpl_token_metadata::state::MAX_METADATA_LEN;
+use solana_program::{
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, clock::Clock, rent::Rent},
+};
+use utils::*;
+
+const REQUESTED_AT: i64 = 1_000;
+
+struct RescueFixture {
+    program_id: Pubkey,
+    rescue: TestAccount,
+    fraction_manager: TestAccount,
+    metadata: TestAccount,
+    original_authority_lookup: TestAccount,
+    claimed_original_authority: TestAccount,
+    admin: TestAccount,
+    store: TestAccount,
+    fraction_manager_authority: TestAccount,
+    token_metadata_program: Pubkey,
+}
+
+impl RescueFixture {
+    /// Metadata held by a bought out fraction manager whose lookup for it has been closed
+    fn new() -> RescueFixture {
+        let program_id = id();
+        let admin = TestAccount::wallet(Pubkey::new_unique(), true);
+        let vault = Pubkey::new_unique();
+
+        let mut store = TestAccount::new(
+            program_id,
+            account_data(
+                &Store {
+                    key: Key::StoreV1,
+                    public: true,
+                    auction_program: Pubkey::new_unique(),
+                    token_vault_program: mpl_token_vault::id(),
+                    token_metadata_program: mpl_token_metadata::id(),
+                    token_program: spl_token::id(),
+                },
+                MAX_STORE_SIZE,
+            ),
+        );
+        store.key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), program_id.as_ref(), admin.key.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let fraction_manager_authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut fraction_manager_state = make_fraction_manager(
+            fraction_manager_authority.key,
+            FractionManagerStatus::Combined,
+        );
+        fraction_manager_state.store = store.key;
+        fraction_manager_state.vault = vault;
+        let mut fraction_manager =
+            TestAccount::new(program_id, fraction_manager_data(&fraction_manager_state));
+        fraction_manager.key =
+            Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.as_ref()], &program_id).0;
+
+        let mut metadata = TestAccount::new(
+            mpl_token_metadata::id(),
+            account_data(
+                &make_metadata(fraction_manager.key, Pubkey::new_unique(), None),
+                MAX_METADATA_LEN,
+            ),
+        );
+        metadata.key = Pubkey::new_unique();
+
+        let mut original_authority_lookup = TestAccount::wallet(Pubkey::default(), false);
+        original_authority_lookup.key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), vault.as_ref(), metadata.key.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        // Account creation is a no-op off-chain, so the rescue starts out allocated
+        let mut rescue = TestAccount::new(program_id, vec![0; MAX_FRACTION_METADATA_RESCUE_SIZE]);
+        rescue.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.key.as_ref(),
+                RESCUE.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        RescueFixture {
+            program_id,
+            rescue,
+            fraction_manager,
+            metadata,
+            original_authority_lookup,
+            claimed_original_authority: TestAccount::wallet(wallet_key(), false),
+            admin,
+            store,
+            fraction_manager_authority,
+            token_metadata_program: mpl_token_metadata::id(),
+        }
+    }
+
+    fn rescue_at(&mut self, unix_timestamp: i64) -> ProgramResult {
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut token_metadata_program = TestAccount::wallet(self.token_metadata_program, false);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut clock = TestAccount::sysvar(
+            sysvar::clock::id(),
+            &Clock {
+                unix_timestamp,
+                ..Clock::default()
+            },
+        );
+
+        process_rescue_stuck_metadata(
+            &self.program_id,
+            &[
+                self.rescue.info(),
+                self.fraction_manager.info(),
+                self.metadata.info(),
+                self.original_authority_lookup.info(),
+                self.claimed_original_authority.info(),
+                self.admin.info(),
+                payer.info(),
+                self.store.info(),
+                token_metadata_program.info(),
+                system.info(),
+                rent.info(),
+                clock.info(),
+            ],
+        )
+    }
+
+    fn dispute_at(&mut self, unix_timestamp: i64) -> ProgramResult {
+        let mut clock = TestAccount::sysvar(
+            sysvar::clock::id(),
+            &Clock {
+                unix_timestamp,
+                ..Clock::default()
+            },
+        );
+
+        process_dispute_metadata_rescue(
+            &self.program_id,
+            &[
+                self.rescue.info(),
+                self.fraction_manager.info(),
+                self.fraction_manager_authority.info(),
+                self.admin.info(),
+                clock.info(),
+            ],
+        )
+    }
+
+    fn record(&self) -> FractionMetadataRescue {
+        self.rescue.read()
+    }
+
+    fn open_records(&self) -> u64 {
+        self.fraction_manager
+            .read::<FractionManagerV1>()
+            .state
+            .open_records
+    }
+}
+
+/// An ed25519 point, as every wallet key is
+fn wallet_key() -> Pubkey {
+    loop {
+        let key = Pubkey::new_unique();
+        if key.is_on_curve() {
+            return key;
+        }
+    }
+}
+
+#[test]
+fn test_request_records_claim() {
+    let mut fixture = RescueFixture::new();
+
+    assert_eq!(fixture.rescue_at(REQUESTED_AT), Ok(()));
+
+    let record = fixture.record();
+    assert_eq!(record.key, Key::FractionMetadataRescueV1);
+    assert_eq!(record.fraction_manager, fixture.fraction_manager.key);
+    assert_eq!(record.metadata, fixture.metadata.key);
+    assert_eq!(
+        record.claimed_original_authority,
+        fixture.claimed_original_authority.key
+    );
+    assert_eq!(record.admin, fixture.admin.key);
+    assert_eq!(record.requested_at, REQUESTED_AT);
+    assert_eq!(record.executed_at, None);
+}
+
+#[test]
+fn test_rescue_after_dispute_window() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+
+    let executed_at = REQUESTED_AT + RESCUE_DISPUTE_WINDOW;
+    assert_eq!(fixture.rescue_at(executed_at), Ok(()));
+    assert_eq!(fixture.record().executed_at, Some(executed_at));
+}
+
+#[test]
+fn test_rejected_before_dispute_window() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW - 1),
+        Err(MetaplexError::RescueDisputeWindowOpen.into())
+    );
+    assert_eq!(fixture.record().executed_at, None);
+}
+
+#[test]
+fn test_rescue_only_once() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    fixture
+        .rescue_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW)
+        .unwrap();
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT + 2 * RESCUE_DISPUTE_WINDOW),
+        Err(MetaplexError::RescueAlreadyExecuted.into())
+    );
+}
+
+#[test]
+fn test_claim_cannot_change() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    fixture.claimed_original_authority = TestAccount::wallet(wallet_key(), false);
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW),
+        Err(MetaplexError::RescueClaimMismatch.into())
+    );
+}
+
+#[test]
+fn test_rejected_while_lookup_exists() {
+    let mut fixture = RescueFixture::new();
+    fixture.original_authority_lookup.data = vec![0; 1];
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT),
+        Err(MetaplexError::OriginalAuthorityLookupNotLost.into())
+    );
+}
+
+#[test]
+fn test_rejects_non_admin() {
+    let mut fixture = RescueFixture::new();
+    fixture.admin = TestAccount::wallet(Pubkey::new_unique(), true);
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT),
+        Err(MetaplexError::DerivedKeyInvalid.into())
+    );
+}
+
+#[test]
+fn test_rejected_before_buyout() {
+    for status in [
+        FractionManagerStatus::Validated,
+        FractionManagerStatus::Redeemable,
+    ] {
+        let mut fixture = RescueFixture::new();
+        let mut manager: FractionManagerV1 = fixture.fraction_manager.read();
+        manager.state.status = status;
+        fixture.fraction_manager.data = fraction_manager_data(&manager);
+
+        assert_eq!(
+            fixture.rescue_at(REQUESTED_AT),
+            Err(MetaplexError::InvalidStatus.into())
+        );
+    }
+}
+
+#[test]
+fn test_rejects_other_token_metadata_program() {
+    let mut fixture = RescueFixture::new();
+    fixture.token_metadata_program = Pubkey::new_unique();
+
+    assert_eq!(
+        fixture.rescue_at(REQUESTED_AT),
+        Err(MetaplexError::AuctionManagerTokenMetadataProgramMismatch.into())
+    );
+}
+
+#[test]
+fn test_dispute_drops_claim() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    assert_eq!(fixture.open_records(), 1);
+    let rescue_rent = fixture.rescue.lamports;
+
+    assert_eq!(
+        fixture.dispute_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW - 1),
+        Ok(())
+    );
+
+    assert_eq!(fixture.rescue.lamports, 0);
+    assert_eq!(fixture.admin.lamports, rescue_rent);
+    assert_eq!(fixture.open_records(), 0);
+    // Calling again starts a new claim with its own window rather than executing the old one
+    let requested_again = REQUESTED_AT + RESCUE_DISPUTE_WINDOW;
+    assert_eq!(fixture.rescue_at(requested_again), Ok(()));
+    assert_eq!(fixture.record().requested_at, requested_again);
+    assert_eq!(fixture.record().executed_at, None);
+}
+
+#[test]
+fn test_dispute_after_window() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+
+    assert_eq!(
+        fixture.dispute_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW),
+        Err(MetaplexError::RescueDisputeWindowClosed.into())
+    );
+    assert_eq!(fixture.open_records(), 1);
+}
+
+#[test]
+fn test_dispute_after_rescue() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    fixture
+        .rescue_at(REQUESTED_AT + RESCUE_DISPUTE_WINDOW)
+        .unwrap();
+
+    assert_eq!(
+        fixture.dispute_at(REQUESTED_AT),
+        Err(MetaplexError::RescueAlreadyExecuted.into())
+    );
+}
+
+#[test]
+fn test_dispute_only_by_manager_authority() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    fixture.fraction_manager_authority = TestAccount::wallet(Pubkey::new_unique(), true);
+
+    assert_eq!(
+        fixture.dispute_at(REQUESTED_AT),
+        Err(MetaplexError::AuctionManagerAuthorityMismatch.into())
+    );
+    assert_eq!(fixture.record().executed_at, None);
+}
+
+#[test]
+fn test_dispute_refunds_requesting_admin() {
+    let mut fixture = RescueFixture::new();
+    fixture.rescue_at(REQUESTED_AT).unwrap();
+    fixture.admin = TestAccount::wallet(Pubkey::new_unique(), false);
+
+    assert_eq!(
+        fixture.dispute_at(REQUESTED_AT),
+        Err(MetaplexError::RescueAdminMismatch.into())
+    );
+}
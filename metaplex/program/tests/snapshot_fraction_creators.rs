@@ -0,0 +1,288 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    processor::{
+        report_fraction_creator_drift::process_report_fraction_creator_drift,
+        snapshot_fraction_creators::process_snapshot_fraction_creators,
+    },
+    state::{
+        CreatorSnapshot, FractionCreatorSnapshot, FractionManagerStatus, FractionManagerV1, Key,
+        CREATORS, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, PREFIX,
+    },
+};
+use mpl_token_metadata::state::{Creator, MAX_METADATA_LEN};
+use solana_program::{
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, rent::Rent},
+};
+use utils::*;
+
+fn snapshot(fixture: &mut ValidationFixture) -> Result<FractionCreatorSnapshot, ProgramError> {
+    let mut creator_snapshot = snapshot_account(fixture);
+    snapshot_into(fixture, &mut creator_snapshot)?;
+
+    Ok(creator_snapshot.read())
+}
+
+/// The snapshot address of the fixture's metadata. Account creation is a no-op off-chain, so
+/// the snapshot starts out allocated.
+fn snapshot_account(fixture: &ValidationFixture) -> TestAccount {
+    let mut creator_snapshot = TestAccount::new(
+        fixture.program_id,
+        vec![0; MAX_FRACTION_CREATOR_SNAPSHOT_SIZE],
+    );
+    creator_snapshot.key = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            fixture.program_id.as_ref(),
+            fixture.fraction_manager.key.as_ref(),
+            fixture.metadata.key.as_ref(),
+            CREATORS.as_bytes(),
+        ],
+        &fixture.program_id,
+    )
+    .0;
+    creator_snapshot
+}
+
+fn snapshot_into(
+    fixture: &mut ValidationFixture,
+    creator_snapshot: &mut TestAccount,
+) -> ProgramResult {
+    fixture.sync();
+    fixture.metadata.data = account_data(&fixture.metadata_state, MAX_METADATA_LEN);
+    let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+    let mut system = TestAccount::wallet(system_program::id(), false);
+    let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+    process_snapshot_fraction_creators(
+        &fixture.program_id,
+        &[
+            creator_snapshot.info(),
+            fixture.fraction_manager.info(),
+            fixture.safety_deposit.info(),
+            fixture.metadata.info(),
+            fixture.authority.info(),
+            payer.info(),
+            fixture.store.info(),
+            system.info(),
+            rent.info(),
+        ],
+    )
+}
+
+fn creator(address: Pubkey, verified: bool) -> Creator {
+    Creator {
+        address,
+        verified,
+        share: 50,
+    }
+}
+
+#[test]
+fn test_mixed_verified_snapshot() {
+    let verified = Pubkey::new_unique();
+    let unverified = Pubkey::new_unique();
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.data.creators =
+        Some(vec![creator(verified, true), creator(unverified, false)]);
+
+    let snapshot = snapshot(&mut fixture).unwrap();
+    assert_eq!(snapshot.key, Key::FractionCreatorSnapshotV1);
+    assert_eq!(snapshot.fraction_manager, fixture.fraction_manager.key);
+    assert_eq!(snapshot.metadata, fixture.metadata.key);
+    assert_eq!(
+        snapshot.creators,
+        vec![
+            CreatorSnapshot {
+                address: verified,
+                verified: true,
+                share: 50,
+            },
+            CreatorSnapshot {
+                address: unverified,
+                verified: false,
+                share: 50,
+            },
+        ]
+    );
+
+    assert_eq!(snapshot.assert_payout_verified(&verified), Ok(()));
+    assert_eq!(
+        snapshot.assert_payout_verified(&unverified),
+        Err(MetaplexError::UnverifiedCreatorPayout.into())
+    );
+    assert_eq!(
+        snapshot.assert_payout_verified(&Pubkey::new_unique()),
+        Err(MetaplexError::UnverifiedCreatorPayout.into())
+    );
+}
+
+#[test]
+fn test_snapshot_marks_manager() {
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.data.creators = Some(vec![creator(Pubkey::new_unique(), true)]);
+    assert!(!fixture.fraction_manager_state.state.creators_snapshotted);
+
+    snapshot(&mut fixture).unwrap();
+    let manager = fixture.fraction_manager.read::<FractionManagerV1>();
+    assert!(manager.state.creators_snapshotted);
+    // Teardown has the snapshot to close
+    assert_eq!(manager.state.open_records, 1);
+}
+
+#[test]
+fn test_snapshot_taken_once() {
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.data.creators = Some(vec![creator(Pubkey::new_unique(), true)]);
+    let mut creator_snapshot = snapshot_account(&fixture);
+    snapshot_into(&mut fixture, &mut creator_snapshot).unwrap();
+
+    // The update authority swaps the creators before redemption opens
+    fixture.metadata_state.data.creators = Some(vec![creator(Pubkey::new_unique(), true)]);
+    assert_eq!(
+        snapshot_into(&mut fixture, &mut creator_snapshot),
+        Err(MetaplexError::AlreadyInitialized.into())
+    );
+}
+
+#[test]
+fn test_snapshot_per_metadata() {
+    let mut fixture = ValidationFixture::new();
+    let first = snapshot_account(&fixture);
+    fixture.metadata.key = Pubkey::new_unique();
+
+    assert_ne!(snapshot_account(&fixture).key, first.key);
+    let mut creator_snapshot = first;
+    assert_eq!(
+        snapshot_into(&mut fixture, &mut creator_snapshot),
+        Err(MetaplexError::DerivedKeyInvalid.into())
+    );
+}
+
+#[test]
+fn test_snapshot_after_activation() {
+    let mut fixture = ValidationFixture::new();
+    fixture.fraction_manager_state.state.status = FractionManagerStatus::Redeemable;
+
+    assert_eq!(
+        snapshot(&mut fixture).unwrap_err(),
+        MetaplexError::InvalidStatus.into()
+    );
+}
+
+#[test]
+fn test_metadata_must_match_box() {
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.mint = Pubkey::new_unique();
+
+    assert_eq!(
+        snapshot(&mut fixture).unwrap_err(),
+        MetaplexError::SafetyDepositBoxMetadataMismatch.into()
+    );
+}
+
+fn report_drift(
+    fixture: &mut ValidationFixture,
+    snapshot: &FractionCreatorSnapshot,
+) -> ProgramResult {
+    fixture.metadata.data = account_data(&fixture.metadata_state, MAX_METADATA_LEN);
+    let mut creator_snapshot = TestAccount::new(
+        fixture.program_id,
+        account_data(snapshot, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE),
+    );
+
+    process_report_fraction_creator_drift(
+        &fixture.program_id,
+        &[
+            creator_snapshot.info(),
+            fixture.metadata.info(),
+            fixture.store.info(),
+        ],
+    )
+}
+
+#[test]
+fn test_unchanged_creators() {
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.data.creators = Some(vec![
+        creator(Pubkey::new_unique(), true),
+        creator(Pubkey::new_unique(), false),
+    ]);
+
+    let snapshot = snapshot(&mut fixture).unwrap();
+    assert!(!snapshot.creators_drifted(&fixture.metadata_state));
+    assert_eq!(report_drift(&mut fixture, &snapshot), Ok(()));
+}
+
+#[test]
+fn test_unchanged_without_creators() {
+    let mut fixture = ValidationFixture::new();
+    fixture.metadata_state.data.creators = None;
+
+    let snapshot = snapshot(&mut fixture).unwrap();
+    assert!(!snapshot.creators_drifted(&fixture.metadata_state));
+
+    fixture.metadata_state.data.creators = Some(vec![]);
+    assert!(!snapshot.creators_drifted(&fixture.metadata_state));
+}
+
+#[test]
+fn test_changed_creators() {
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+    let mut fixture = ValidationFixture::new();
+    let creators = vec![creator(first, true), creator(second, false)];
+    fixture.metadata_state.data.creators = Some(creators.clone());
+    let snapshot = snapshot(&mut fixture).unwrap();
+
+    let drifted = |fixture: &mut ValidationFixture, live: Vec<Creator>| {
+        fixture.metadata_state.data.creators = Some(live);
+        snapshot.creators_drifted(&fixture.metadata_state)
+    };
+
+    // A creator swapped out
+    assert!(drifted(
+        &mut fixture,
+        vec![creator(first, true), creator(Pubkey::new_unique(), false)]
+    ));
+    // Verified after the fact
+    assert!(drifted(
+        &mut fixture,
+        vec![creator(first, true), creator(second, true)]
+    ));
+    // Shares moved between the same creators
+    let mut reshared = creators.clone();
+    reshared[0].share = 60;
+    reshared[1].share = 40;
+    assert!(drifted(&mut fixture, reshared));
+    // Reordered
+    assert!(drifted(
+        &mut fixture,
+        vec![creator(second, false), creator(first, true)]
+    ));
+    // One added, and all removed
+    let mut added = creators.clone();
+    added.push(creator(Pubkey::new_unique(), false));
+    assert!(drifted(&mut fixture, added));
+    fixture.metadata_state.data.creators = None;
+    assert!(snapshot.creators_drifted(&fixture.metadata_state));
+
+    assert!(!drifted(&mut fixture, creators));
+}
+
+#[test]
+fn test_drift_report_requires_snapshotted_metadata() {
+    let mut fixture = ValidationFixture::new();
+    let mut snapshot = snapshot(&mut fixture).unwrap();
+    snapshot.metadata = Pubkey::new_unique();
+
+    assert_eq!(
+        report_drift(&mut fixture, &snapshot),
+        Err(MetaplexError::CreatorSnapshotMetadataMismatch.into())
+    );
+}
@@ -0,0 +1,144 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    instruction::UpdateFractionSafetyDepositConfigArgs,
+    processor::update_fraction_safety_deposit_config::process_update_fraction_safety_deposit_config,
+    state::{FractionManagerStatus, FractionSafetyDepositConfig},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use utils::*;
+
+/// A validated config on a manager in `status`, ready to be updated by `authority`
+struct UpdateFixture {
+    program_id: Pubkey,
+    config: TestAccount,
+    fraction_manager: TestAccount,
+    safety_deposit: TestAccount,
+    authority: TestAccount,
+}
+
+impl UpdateFixture {
+    fn new(status: FractionManagerStatus, immutable: bool) -> UpdateFixture {
+        let program_id = id();
+        let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        let fraction_manager = TestAccount::new(
+            program_id,
+            fraction_manager_data(&make_fraction_manager(authority.key, status)),
+        );
+        let safety_deposit = TestAccount::new(mpl_token_vault::id(), vec![]);
+
+        let mut safety_deposit_config = make_config(fraction_manager.key, 0);
+        safety_deposit_config.immutable = immutable;
+        let mut config =
+            TestAccount::new(program_id, vec![0; safety_deposit_config.created_size()]);
+        config.key = config_key(&program_id, &fraction_manager.key, &safety_deposit.key);
+        safety_deposit_config
+            .create(&config.info(), &fraction_manager.key)
+            .unwrap();
+
+        UpdateFixture {
+            program_id,
+            config,
+            fraction_manager,
+            safety_deposit,
+            authority,
+        }
+    }
+
+    fn update(
+        &mut self,
+        fixed_price: Option<u64>,
+        immutable: bool,
+    ) -> Result<FractionSafetyDepositConfig, ProgramError> {
+        process_update_fraction_safety_deposit_config(
+            &self.program_id,
+            &[
+                self.config.info(),
+                self.fraction_manager.info(),
+                self.safety_deposit.info(),
+                self.authority.info(),
+            ],
+            UpdateFractionSafetyDepositConfigArgs {
+                fixed_price,
+                immutable,
+            },
+        )?;
+
+        FractionSafetyDepositConfig::from_account_info(&self.config.info())
+    }
+}
+
+#[test]
+fn test_update_mutable_config() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Validated, false);
+
+    let config = fixture.update(Some(1_000), false).unwrap();
+    assert_eq!(config.fixed_price, Some(1_000));
+    assert!(!config.immutable);
+
+    let config = fixture.update(None, false).unwrap();
+    assert_eq!(config.fixed_price, None);
+}
+
+#[test]
+fn test_update_immutable_config() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Initialized, true);
+
+    assert_eq!(
+        fixture.update(Some(1_000), false).unwrap_err(),
+        MetaplexError::ConfigImmutable.into()
+    );
+}
+
+#[test]
+fn test_immutable_config_never_rewritten() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Initialized, true);
+    let mut rewrite = make_config(fixture.fraction_manager.key, 0);
+    rewrite.fixed_price = Some(1_000);
+
+    // Validation and pre-allocation write through the same path as updates
+    assert_eq!(
+        rewrite
+            .create(&fixture.config.info(), &fixture.fraction_manager.key)
+            .unwrap_err(),
+        MetaplexError::ConfigImmutable.into()
+    );
+    let config = FractionSafetyDepositConfig::from_account_info(&fixture.config.info()).unwrap();
+    assert_eq!(config.fixed_price, None);
+    assert!(config.immutable);
+}
+
+#[test]
+fn test_lock_config() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Initialized, false);
+
+    let config = fixture.update(Some(1_000), true).unwrap();
+    assert!(config.immutable);
+    assert_eq!(
+        fixture.update(Some(2_000), false).unwrap_err(),
+        MetaplexError::ConfigImmutable.into()
+    );
+}
+
+#[test]
+fn test_update_requires_authority() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Initialized, false);
+    fixture.authority = TestAccount::wallet(Pubkey::new_unique(), true);
+
+    assert_eq!(
+        fixture.update(Some(1_000), false).unwrap_err(),
+        MetaplexError::AuctionManagerAuthorityMismatch.into()
+    );
+}
+
+#[test]
+fn test_update_after_activation() {
+    let mut fixture = UpdateFixture::new(FractionManagerStatus::Active, false);
+
+    assert_eq!(
+        fixture.update(Some(1_000), false).unwrap_err(),
+        MetaplexError::InvalidStatus.into()
+    );
+}
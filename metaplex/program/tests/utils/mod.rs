@@ -0,0 +1,1065 @@
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_metaplex::{
+    id,
+    processor::{
+        buyout_fraction_manager::process_buyout_fraction_manager,
+        init_fraction_manager,
+        validate_and_activate::process_validate_and_activate,
+        validate_fraction_safety_deposit_box::{
+            assert_common_checks, process_validate_fraction_safety_deposit_box, CommonCheckArgs,
+        },
+    },
+    state::{
+        CreatorSnapshot, FractionCreatorProceeds, FractionCreatorSnapshot, FractionManagerState,
+        FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig,
+        FractionWinningConfigType, Key, Store, CLAIM, CREATORS, DENYLIST, FRACTION_CONFIG,
+        MAX_FRACTION_CREATOR_PROCEEDS_SIZE, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+        MAX_FRACTION_MANAGER_SIZE, MAX_FRACTION_VAULT_CLAIM_SIZE, MAX_STORE_SIZE, PREFIX, PROCEEDS,
+    },
+};
+use mpl_token_metadata::state::{Creator, Data, Metadata, MAX_METADATA_LEN};
+use mpl_token_vault::state::{
+    ExternalPriceAccount, SafetyDepositBox, Vault, VaultState, MAX_EXTERNAL_ACCOUNT_SIZE,
+    MAX_SAFETY_DEPOSIT_SIZE, MAX_VAULT_SIZE,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    system_program,
+    sysvar::{self, rent::Rent, Sysvar},
+};
+use solana_sdk::account::create_account_for_test;
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+/// Owned backing storage for an `AccountInfo` handed to a processor
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TestAccount {
+    pub fn new(owner: Pubkey, data: Vec<u8>) -> TestAccount {
+        TestAccount {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            is_signer: false,
+            is_writable: true,
+        }
+    }
+
+    /// A data-less system account acting as a wallet
+    pub fn wallet(key: Pubkey, is_signer: bool) -> TestAccount {
+        TestAccount {
+            key,
+            owner: system_program::id(),
+            lamports: 0,
+            data: vec![],
+            is_signer,
+            is_writable: false,
+        }
+    }
+
+    /// A sysvar account holding the serialized `sysvar`
+    pub fn sysvar<S: Sysvar>(key: Pubkey, sysvar: &S) -> TestAccount {
+        let account = create_account_for_test(sysvar);
+        TestAccount {
+            key,
+            owner: account.owner,
+            lamports: account.lamports,
+            data: account.data,
+            is_signer: false,
+            is_writable: false,
+        }
+    }
+
+    pub fn info(&mut self) -> AccountInfo {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+
+    /// Saving advances an account's data slice, so read back from the buffer itself.
+    pub fn read<T: BorshDeserialize>(&self) -> T {
+        try_from_slice_unchecked(&self.data).unwrap()
+    }
+}
+
+/// Syscall stubs for tests that run whole instructions. They keep the last return data set and
+/// every instruction invoked, check signers the way the runtime does, and give an empty account
+/// the space a system allocate asks for so accounts the program creates can be written. The
+/// space only lives in the `AccountInfo`s handed to the processor, so read created accounts back
+/// through those.
+#[derive(Clone, Default)]
+pub struct ProgramStubs {
+    return_data: Arc<RwLock<Option<Vec<u8>>>>,
+    invoked: Arc<RwLock<Vec<Instruction>>>,
+}
+
+/// Holds the process wide stubs for one test, so tests installing them run one at a time
+pub struct InstalledStubs {
+    pub stubs: ProgramStubs,
+    _lock: MutexGuard<'static, ()>,
+}
+
+static STUBS_LOCK: Mutex<()> = Mutex::new(());
+
+impl ProgramStubs {
+    pub fn install() -> InstalledStubs {
+        let lock = STUBS_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let stubs = ProgramStubs::default();
+        set_syscall_stubs(Box::new(stubs.clone()));
+        InstalledStubs { stubs, _lock: lock }
+    }
+
+    /// Instructions invoked so far, in order
+    pub fn invoked(&self) -> Vec<Instruction> {
+        self.invoked.read().unwrap().clone()
+    }
+}
+
+impl SyscallStubs for ProgramStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        // A signer the caller didn't sign for has to be a PDA of the program signing with its seeds
+        let signed_pdas = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &id()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        for meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+            let signed = account_infos
+                .iter()
+                .any(|account| *account.key == meta.pubkey && account.is_signer);
+            if !signed && !signed_pdas.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+        }
+
+        if instruction.program_id == system_program::id() {
+            if let Ok(SystemInstruction::Allocate { space }) =
+                limited_deserialize(&instruction.data, 64)
+            {
+                let account = account_infos
+                    .iter()
+                    .find(|account| *account.key == instruction.accounts[0].pubkey)
+                    .unwrap();
+                if account.data_is_empty() {
+                    *account.data.borrow_mut() =
+                        Box::leak(vec![0; space as usize].into_boxed_slice());
+                }
+            }
+        }
+
+        self.invoked.write().unwrap().push(instruction.clone());
+        Ok(())
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.return_data
+            .read()
+            .unwrap()
+            .clone()
+            .map(|data| (id(), data))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        *self.return_data.write().unwrap() = Some(data.to_vec());
+    }
+}
+
+/// Serialize `acct` into a zeroed buffer of the on-chain allocation length
+pub fn account_data(acct: &impl BorshSerialize, alloc_len: usize) -> Vec<u8> {
+    let mut data = vec![0_u8; alloc_len];
+    // Borrow as a slice to impose a fixed allocation length
+    acct.serialize(&mut data.as_mut_slice()).unwrap();
+    data
+}
+
+/// Pack an SPL account into a buffer of its packed length
+pub fn packed_data<T: Pack>(acct: T) -> Vec<u8> {
+    let mut data = vec![0_u8; T::LEN];
+    T::pack(acct, &mut data).unwrap();
+    data
+}
+
+/// Token metadata for `mint` with the given creators
+pub fn make_metadata(
+    update_authority: Pubkey,
+    mint: Pubkey,
+    creators: Option<Vec<Creator>>,
+) -> Metadata {
+    Metadata {
+        key: mpl_token_metadata::state::Key::MetadataV1,
+        update_authority,
+        mint,
+        data: Data {
+            name: "Fraction".to_string(),
+            symbol: "FRAC".to_string(),
+            uri: "https://notgoogle.com".to_string(),
+            seller_fee_basis_points: 0,
+            creators,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        collection: None,
+        uses: None,
+    }
+}
+
+/// The vault's fraction treasury, holding `amount` fraction tokens
+pub fn treasury_holding(vault: &mut Vault, amount: u64) -> TestAccount {
+    let treasury = TestAccount::new(
+        spl_token::id(),
+        packed_data(TokenAccount {
+            mint: vault.fraction_mint,
+            owner: Pubkey::new_unique(),
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }),
+    );
+    vault.fraction_treasury = treasury.key;
+    treasury
+}
+
+/// A master edition account for `mint` at its canonical address
+pub fn master_edition(mint: &Pubkey) -> TestAccount {
+    let mut edition = TestAccount::new(mpl_token_metadata::id(), vec![]);
+    edition.key = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0;
+    edition
+}
+
+/// Build a fraction manager in the given status with every other field zeroed
+pub fn make_fraction_manager(
+    authority: Pubkey,
+    status: FractionManagerStatus,
+) -> FractionManagerV1 {
+    FractionManagerV1 {
+        key: Key::FractionManagerV1,
+        store: Pubkey::new_unique(),
+        authority,
+        vault: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        accept_payment: Pubkey::new_unique(),
+        state: FractionManagerState {
+            status,
+            safety_config_items_validated: 0,
+            token_pools_active: 0,
+            has_participation: false,
+            redemption_paused: false,
+            creator_priority_until: None,
+            vault_token_type_count: 1,
+            min_price: None,
+            max_price: None,
+            min_redemption_price_per_share: 0,
+            prepaid_teardown_rent: 0,
+            royalty_vesting_start: None,
+            royalty_vesting_seconds: 0,
+            total_rent_reclaimed: 0,
+            total_fractions_minted: 0,
+            metadata_uri: String::new(),
+            uri_immutable: false,
+            max_fraction_supply: None,
+            max_fraction_shares_per_wallet: None,
+            validated_orders: [0; 32],
+            teardown_rent_payer: Pubkey::new_unique(),
+            fraction_mint: None,
+            fixed_price_per_share: None,
+            creators_snapshotted: false,
+            buyout_holder_proceeds: 0,
+            buyout_shares: 0,
+            shares_redeemed: 0,
+            holder_proceeds_paid: 0,
+            open_records: 0,
+        },
+        external_pricing: Pubkey::new_unique(),
+    }
+}
+
+/// Serialize a fraction manager into a buffer of the on-chain allocation length
+pub fn fraction_manager_data(fraction_manager: &FractionManagerV1) -> Vec<u8> {
+    account_data(fraction_manager, MAX_FRACTION_MANAGER_SIZE)
+}
+
+/// A mutable FractionToken config for the box at `order`
+pub fn make_config(fraction_manager: Pubkey, order: u64) -> FractionSafetyDepositConfig {
+    FractionSafetyDepositConfig {
+        key: Key::FractionSafetyDepositConfigV1,
+        fraction_manager,
+        order,
+        fraction_winning_config_type: FractionWinningConfigType::FractionToken,
+        fixed_price: None,
+        immutable: false,
+        metadata_authority: None,
+    }
+}
+
+/// Canonical config address, ['metaplex', program id, fraction manager, safety deposit]
+pub fn config_key(
+    program_id: &Pubkey,
+    fraction_manager: &Pubkey,
+    safety_deposit: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            fraction_manager.as_ref(),
+            safety_deposit.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Authority the vault program holds a vault's token stores with,
+/// ['vault', vault program id, vault]
+pub fn vault_authority(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            mpl_token_vault::state::PREFIX.as_bytes(),
+            mpl_token_vault::id().as_ref(),
+            vault.as_ref(),
+        ],
+        &mpl_token_vault::id(),
+    )
+    .0
+}
+
+/// Store fraction config address, ['metaplex', program id, store, 'fraction_config']
+pub fn fraction_store_config_key(program_id: &Pubkey, store: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            FRACTION_CONFIG.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// The accounts `init_fraction_manager::assert_common_checks` looks at, built so the
+/// manager can be initialized. Tests tweak the parsed state before calling `assert_common_checks`.
+pub struct InitFixture {
+    pub program_id: Pubkey,
+    pub fraction_manager: TestAccount,
+    pub vault: TestAccount,
+    pub token_mint: TestAccount,
+    pub external_price_account: TestAccount,
+    pub store: TestAccount,
+    pub accept_payment: TestAccount,
+    pub authority: TestAccount,
+    pub vault_claim: TestAccount,
+    pub fraction_store_config: TestAccount,
+    pub vault_state: Vault,
+    pub external_price_account_state: ExternalPriceAccount,
+    pub accept_payment_state: TokenAccount,
+}
+
+impl InitFixture {
+    pub fn new() -> InitFixture {
+        let program_id = id();
+        let store = TestAccount::new(
+            program_id,
+            account_data(
+                &Store {
+                    key: Key::StoreV1,
+                    public: true,
+                    auction_program: Pubkey::new_unique(),
+                    token_vault_program: mpl_token_vault::id(),
+                    token_metadata_program: mpl_token_metadata::id(),
+                    token_program: spl_token::id(),
+                },
+                MAX_STORE_SIZE,
+            ),
+        );
+        let vault = TestAccount::new(mpl_token_vault::id(), vec![]);
+        let mut fraction_manager = TestAccount::new(program_id, vec![]);
+        fraction_manager.key =
+            Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.key.as_ref()], &program_id).0;
+        let token_mint = TestAccount::new(spl_token::id(), vec![]);
+        let external_price_account = TestAccount::new(Pubkey::new_unique(), vec![]);
+        let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        // Account creation is a no-op off-chain, so the claim starts out allocated
+        let mut vault_claim = TestAccount::new(program_id, vec![0; MAX_FRACTION_VAULT_CLAIM_SIZE]);
+        vault_claim.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                vault.key.as_ref(),
+                CLAIM.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+        let fraction_store_config =
+            TestAccount::wallet(fraction_store_config_key(&program_id, &store.key), false);
+
+        let mut vault_state: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        vault_state.key = mpl_token_vault::state::Key::VaultV1;
+        vault_state.token_program = spl_token::id();
+        vault_state.fraction_mint = Pubkey::new_unique();
+        vault_state.authority = authority.key;
+        vault_state.pricing_lookup_address = external_price_account.key;
+        vault_state.token_type_count = 1;
+        vault_state.state = VaultState::Active;
+
+        let accept_payment_state = TokenAccount {
+            mint: token_mint.key,
+            owner: fraction_manager.key,
+            amount: 0,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+
+        InitFixture {
+            program_id,
+            fraction_manager,
+            vault,
+            external_price_account,
+            store,
+            accept_payment: TestAccount::new(spl_token::id(), vec![]),
+            authority,
+            vault_claim,
+            fraction_store_config,
+            vault_state,
+            external_price_account_state: ExternalPriceAccount {
+                key: mpl_token_vault::state::Key::ExternalAccountKeyV1,
+                price_per_share: 1,
+                price_mint: token_mint.key,
+                allowed_to_combine: false,
+            },
+            accept_payment_state,
+            token_mint,
+        }
+    }
+
+    /// Write the parsed state back into the accounts
+    pub fn sync(&mut self) {
+        self.vault.data = account_data(&self.vault_state, MAX_VAULT_SIZE);
+        self.external_price_account.data = account_data(
+            &self.external_price_account_state,
+            MAX_EXTERNAL_ACCOUNT_SIZE,
+        );
+        self.accept_payment.data = packed_data(self.accept_payment_state);
+    }
+
+    pub fn assert_common_checks(&mut self) -> Result<(u8, Vault), ProgramError> {
+        self.sync();
+
+        init_fraction_manager::assert_common_checks(
+            &self.program_id,
+            &self.fraction_manager.info(),
+            &self.vault.info(),
+            &self.token_mint.info(),
+            &self.external_price_account.info(),
+            &self.store.info(),
+            &self.accept_payment.info(),
+            &self.authority.info(),
+        )
+    }
+}
+
+/// Every account and parsed state `assert_common_checks` looks at while validating one
+/// safety deposit box. `new` builds a FractionToken box in a public store that passes;
+/// tests break one piece at a time.
+pub struct ValidationFixture {
+    pub program_id: Pubkey,
+    pub fraction_manager: TestAccount,
+    pub metadata: TestAccount,
+    pub original_authority_lookup: TestAccount,
+    pub whitelisted_creator: TestAccount,
+    pub safety_deposit: TestAccount,
+    pub safety_deposit_token_store: TestAccount,
+    pub edition: TestAccount,
+    pub vault: TestAccount,
+    pub mint: TestAccount,
+    pub token_metadata_program: TestAccount,
+    pub store: TestAccount,
+    pub authority: TestAccount,
+    pub mint_denylist: TestAccount,
+    pub fraction_treasury: TestAccount,
+    pub store_state: Store,
+    pub fraction_manager_state: FractionManagerV1,
+    pub metadata_state: Metadata,
+    pub safety_deposit_state: SafetyDepositBox,
+    pub vault_state: Vault,
+    pub winning_config_type: FractionWinningConfigType,
+    /// Passed as report_cu by `process_validate`
+    pub report_cu: bool,
+    /// When set `process_validate` runs ValidateAndActivate at this fixed price instead
+    pub activate_at_price: Option<u64>,
+}
+
+impl ValidationFixture {
+    pub fn new() -> ValidationFixture {
+        let program_id = id();
+        let store_state = Store {
+            key: Key::StoreV1,
+            public: true,
+            auction_program: Pubkey::new_unique(),
+            token_vault_program: mpl_token_vault::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+        };
+
+        let store = TestAccount::new(program_id, vec![]);
+        let mut fraction_manager = TestAccount::new(program_id, vec![]);
+        let authority = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut vault = TestAccount::new(mpl_token_vault::id(), vec![]);
+        fraction_manager.key =
+            Pubkey::find_program_address(&[PREFIX.as_bytes(), vault.key.as_ref()], &program_id).0;
+        let mint = TestAccount::new(
+            spl_token::id(),
+            packed_data(Mint {
+                mint_authority: COption::None,
+                supply: 1,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            }),
+        );
+
+        let mut safety_deposit = TestAccount::new(mpl_token_vault::id(), vec![]);
+        safety_deposit.key = Pubkey::find_program_address(
+            &[
+                mpl_token_vault::state::PREFIX.as_bytes(),
+                vault.key.as_ref(),
+                mint.key.as_ref(),
+            ],
+            &mpl_token_vault::id(),
+        )
+        .0;
+
+        let safety_deposit_token_store = TestAccount::new(
+            spl_token::id(),
+            packed_data(TokenAccount {
+                mint: mint.key,
+                owner: vault_authority(&vault.key),
+                amount: 1,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            }),
+        );
+
+        // FractionToken boxes pass the system program in place of an edition
+        let edition = TestAccount::wallet(system_program::id(), false);
+
+        let mut mint_denylist = TestAccount::wallet(Pubkey::default(), false);
+        mint_denylist.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store.key.as_ref(),
+                DENYLIST.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let mut fraction_manager_state =
+            make_fraction_manager(authority.key, FractionManagerStatus::Initialized);
+        fraction_manager_state.store = store.key;
+        fraction_manager_state.vault = vault.key;
+
+        let mut vault_state: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        vault_state.key = mpl_token_vault::state::Key::VaultV1;
+        vault_state.token_program = spl_token::id();
+        vault_state.fraction_mint = Pubkey::new_unique();
+        vault_state.authority = fraction_manager.key;
+        vault_state.token_type_count = 1;
+        vault_state.state = VaultState::Active;
+        vault_state.pricing_lookup_address = fraction_manager_state.external_pricing;
+        let fraction_treasury = treasury_holding(&mut vault_state, 0);
+        vault.data = account_data(&vault_state, MAX_VAULT_SIZE);
+
+        let safety_deposit_state = SafetyDepositBox {
+            key: mpl_token_vault::state::Key::SafetyDepositBoxV1,
+            vault: vault.key,
+            token_mint: mint.key,
+            store: safety_deposit_token_store.key,
+            order: 0,
+        };
+
+        let metadata_state = make_metadata(authority.key, mint.key, None);
+
+        ValidationFixture {
+            program_id,
+            fraction_manager,
+            metadata: TestAccount::new(mpl_token_metadata::id(), vec![]),
+            original_authority_lookup: TestAccount::wallet(Pubkey::new_unique(), false),
+            whitelisted_creator: TestAccount::wallet(system_program::id(), false),
+            safety_deposit,
+            safety_deposit_token_store,
+            edition,
+            vault,
+            mint,
+            token_metadata_program: TestAccount::wallet(mpl_token_metadata::id(), false),
+            store,
+            authority,
+            mint_denylist,
+            fraction_treasury,
+            store_state,
+            fraction_manager_state,
+            metadata_state,
+            safety_deposit_state,
+            vault_state,
+            winning_config_type: FractionWinningConfigType::FractionToken,
+            report_cu: false,
+            activate_at_price: None,
+        }
+    }
+
+    /// Write the parsed state back into the accounts the checks read from directly
+    pub fn sync(&mut self) {
+        self.store.data = account_data(&self.store_state, MAX_STORE_SIZE);
+        self.vault.data = account_data(&self.vault_state, MAX_VAULT_SIZE);
+        self.fraction_manager.data = fraction_manager_data(&self.fraction_manager_state);
+        self.safety_deposit.data =
+            account_data(&self.safety_deposit_state, MAX_SAFETY_DEPOSIT_SIZE);
+    }
+
+    /// Runs the whole ValidateFractionSafetyDepositBox instruction with a fresh config, passing
+    /// `custody` as the depositor and deposit record when given. Needs `ProgramStubs` installed
+    /// for the config, and the original authority lookup of a master edition box, to be created.
+    pub fn process_validate(
+        &mut self,
+        custody: Option<(&mut TestAccount, &mut TestAccount)>,
+    ) -> ProgramResult {
+        self.sync();
+        self.metadata.data = account_data(&self.metadata_state, MAX_METADATA_LEN);
+        let mut config = TestAccount::wallet(
+            config_key(
+                &self.program_id,
+                &self.fraction_manager.key,
+                &self.safety_deposit.key,
+            ),
+            false,
+        );
+        config.lamports = 1_000_000_000;
+        let mut metadata_authority =
+            TestAccount::wallet(self.metadata_state.update_authority, true);
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut clock = TestAccount::sysvar(sysvar::clock::id(), &Clock::default());
+        let mut fraction_store_config = TestAccount::wallet(
+            fraction_store_config_key(&self.program_id, &self.store.key),
+            false,
+        );
+        let mut safety_deposit_config = make_config(
+            self.fraction_manager.key,
+            self.safety_deposit_state.order as u64,
+        );
+        safety_deposit_config.fraction_winning_config_type = self.winning_config_type;
+
+        let mut infos = vec![
+            config.info(),
+            self.fraction_manager.info(),
+            self.metadata.info(),
+            self.original_authority_lookup.info(),
+            self.whitelisted_creator.info(),
+            self.store.info(),
+            self.safety_deposit.info(),
+            self.safety_deposit_token_store.info(),
+            self.mint.info(),
+            self.edition.info(),
+            self.vault.info(),
+            self.authority.info(),
+            metadata_authority.info(),
+            payer.info(),
+            self.token_metadata_program.info(),
+            system.info(),
+            rent.info(),
+            self.mint_denylist.info(),
+            clock.info(),
+            fraction_store_config.info(),
+            self.fraction_treasury.info(),
+        ];
+        let require_depositor_custody = custody.is_some();
+        if let Some((depositor, deposit_record)) = custody {
+            infos.push(depositor.info());
+            infos.push(deposit_record.info());
+        }
+
+        if let Some(price_per_share) = self.activate_at_price {
+            safety_deposit_config.fixed_price = Some(price_per_share);
+            return process_validate_and_activate(
+                &self.program_id,
+                &infos,
+                safety_deposit_config,
+                None,
+                false,
+                None,
+                self.report_cu,
+                None,
+                require_depositor_custody,
+                None,
+            );
+        }
+
+        process_validate_fraction_safety_deposit_box(
+            &self.program_id,
+            &infos,
+            safety_deposit_config,
+            None,
+            None,
+            self.report_cu,
+            None,
+            require_depositor_custody,
+            None,
+        )
+    }
+
+    pub fn assert_common_checks(&mut self) -> ProgramResult {
+        self.sync();
+
+        assert_common_checks(CommonCheckArgs {
+            program_id: &self.program_id,
+            fraction_manager_info: &self.fraction_manager.info(),
+            metadata_info: &self.metadata.info(),
+            original_authority_lookup_info: &self.original_authority_lookup.info(),
+            whitelisted_creator_info: &self.whitelisted_creator.info(),
+            safety_deposit_info: &self.safety_deposit.info(),
+            safety_deposit_token_store_info: &self.safety_deposit_token_store.info(),
+            edition_info: &self.edition.info(),
+            vault_info: &self.vault.info(),
+            mint_info: &self.mint.info(),
+            token_metadata_program_info: &self.token_metadata_program.info(),
+            fraction_manager_store_info: &self.store.info(),
+            authority_info: &self.authority.info(),
+            mint_denylist_info: &self.mint_denylist.info(),
+            store: &self.store_state,
+            fraction_manager: &self.fraction_manager_state,
+            metadata: &self.metadata_state,
+            safety_deposit: &self.safety_deposit_state,
+            vault: &self.vault_state,
+            winning_config_type: &self.winning_config_type,
+        })
+    }
+}
+
+/// A Redeemable fraction manager over InitFixture's vault, minting from its own fraction mint
+/// and with its creators snapshotted, and a buyer paying for every share at the external price.
+/// `new` builds a buyout that succeeds; tests break one piece at a time.
+pub struct BuyoutFixture {
+    pub init: InitFixture,
+    pub fraction_manager_state: FractionManagerV1,
+    pub fraction_mint: TestAccount,
+    pub fraction_mint_state: Mint,
+    pub buyer_token_account: TestAccount,
+    pub buyer: TestAccount,
+    pub store_fee_account: TestAccount,
+    pub store_admin: TestAccount,
+    /// Snapshot of the NFT's creators, `creator` alone and verified
+    pub creator_snapshot_state: FractionCreatorSnapshot,
+    pub creator_snapshot: TestAccount,
+    pub creator: Pubkey,
+    /// The NFT's metadata, which royalties are taken at the seller fee of
+    pub metadata_state: Metadata,
+    pub metadata: TestAccount,
+    /// Proceeds of every verified creator in the snapshot, in snapshot order
+    pub creator_proceeds: Vec<TestAccount>,
+    pub payer: TestAccount,
+    /// Unix timestamp the buyout runs at
+    pub now: i64,
+}
+
+impl BuyoutFixture {
+    pub fn new() -> BuyoutFixture {
+        let mut init = InitFixture::new();
+        let store_admin = TestAccount::wallet(Pubkey::new_unique(), false);
+        init.store.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                init.program_id.as_ref(),
+                store_admin.key.as_ref(),
+            ],
+            &init.program_id,
+        )
+        .0;
+        init.fraction_store_config.key =
+            fraction_store_config_key(&init.program_id, &init.store.key);
+        let authority = init.authority.key;
+        let mut fraction_manager_state =
+            make_fraction_manager(authority, FractionManagerStatus::Redeemable);
+        fraction_manager_state.store = init.store.key;
+        fraction_manager_state.vault = init.vault.key;
+        fraction_manager_state.token_mint = init.token_mint.key;
+        fraction_manager_state.accept_payment = init.accept_payment.key;
+        fraction_manager_state.external_pricing = init.external_price_account.key;
+        fraction_manager_state.state.creators_snapshotted = true;
+        init.external_price_account_state.price_per_share = 10;
+        init.vault_state.authority = init.fraction_manager.key;
+        init.vault_state.fraction_treasury = Pubkey::new_unique();
+        init.vault_state.redeem_treasury = Pubkey::new_unique();
+
+        let fraction_mint = TestAccount::new(spl_token::id(), vec![]);
+        fraction_manager_state.state.fraction_mint = Some(fraction_mint.key);
+        let fraction_mint_state = Mint {
+            mint_authority: COption::Some(init.fraction_manager.key),
+            supply: 1_000,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+
+        let buyer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let buyer_token_account = TestAccount::new(
+            spl_token::id(),
+            packed_data(TokenAccount {
+                mint: init.token_mint.key,
+                owner: buyer.key,
+                amount: 10_000,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            }),
+        );
+
+        let store_fee_account = TestAccount::new(
+            spl_token::id(),
+            packed_data(TokenAccount {
+                mint: init.token_mint.key,
+                owner: store_admin.key,
+                amount: 0,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            }),
+        );
+
+        let creator = Pubkey::new_unique();
+        let metadata_state = make_metadata(
+            init.fraction_manager.key,
+            Pubkey::new_unique(),
+            Some(vec![Creator {
+                address: creator,
+                verified: true,
+                share: 100,
+            }]),
+        );
+        let metadata = TestAccount::new(mpl_token_metadata::id(), vec![]);
+        let creator_snapshot_state = FractionCreatorSnapshot {
+            key: Key::FractionCreatorSnapshotV1,
+            fraction_manager: init.fraction_manager.key,
+            metadata: metadata.key,
+            creators: vec![CreatorSnapshot {
+                address: creator,
+                verified: true,
+                share: 100,
+            }],
+        };
+        let mut creator_snapshot = TestAccount::new(init.program_id, vec![]);
+        creator_snapshot.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                init.program_id.as_ref(),
+                init.fraction_manager.key.as_ref(),
+                creator_snapshot_state.metadata.as_ref(),
+                CREATORS.as_bytes(),
+            ],
+            &init.program_id,
+        )
+        .0;
+
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        payer.is_writable = true;
+
+        let mut fixture = BuyoutFixture {
+            init,
+            fraction_manager_state,
+            fraction_mint,
+            fraction_mint_state,
+            buyer_token_account,
+            buyer,
+            store_fee_account,
+            store_admin,
+            creator_snapshot_state,
+            creator_snapshot,
+            creator,
+            metadata_state,
+            metadata,
+            creator_proceeds: vec![],
+            payer,
+            now: 0,
+        };
+        fixture.set_creators(vec![CreatorSnapshot {
+            address: creator,
+            verified: true,
+            share: 100,
+        }]);
+        fixture
+    }
+
+    /// Snapshots `creators`, with a fresh proceeds account for each verified one
+    pub fn set_creators(&mut self, creators: Vec<CreatorSnapshot>) {
+        let program_id = self.init.program_id;
+        let fraction_manager = self.init.fraction_manager.key;
+        self.creator_proceeds = creators
+            .iter()
+            .filter(|creator| creator.verified)
+            .map(|creator| {
+                // Account creation is a no-op off-chain, so proceeds start out allocated
+                let mut proceeds =
+                    TestAccount::new(program_id, vec![0; MAX_FRACTION_CREATOR_PROCEEDS_SIZE]);
+                proceeds.key = Pubkey::find_program_address(
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        fraction_manager.as_ref(),
+                        creator.address.as_ref(),
+                        PROCEEDS.as_bytes(),
+                    ],
+                    &program_id,
+                )
+                .0;
+                proceeds
+            })
+            .collect();
+        self.creator_snapshot_state.creators = creators;
+    }
+
+    /// Royalties owed to each verified creator after the buyout, in snapshot order
+    pub fn royalties_owed(&self) -> Vec<u64> {
+        self.creator_proceeds
+            .iter()
+            .map(|proceeds| proceeds.read::<FractionCreatorProceeds>().total_owed)
+            .collect()
+    }
+
+    /// Price of every outstanding share at the external price
+    pub fn price(&self) -> u64 {
+        self.init.external_price_account_state.price_per_share * self.fraction_mint_state.supply
+    }
+
+    /// Write the parsed state back into the accounts
+    pub fn sync(&mut self) {
+        self.init.sync();
+        self.init.fraction_manager.data = fraction_manager_data(&self.fraction_manager_state);
+        self.fraction_mint.data = packed_data(self.fraction_mint_state);
+        self.creator_snapshot.data = account_data(
+            &self.creator_snapshot_state,
+            MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+        );
+        self.metadata.data = account_data(&self.metadata_state, MAX_METADATA_LEN);
+    }
+
+    /// Runs BuyoutFractionManager, returning the manager it saved
+    pub fn process_buyout(&mut self) -> Result<FractionManagerV1, ProgramError> {
+        self.sync();
+        let init = &mut self.init;
+        let mut token_program = TestAccount::wallet(spl_token::id(), false);
+        let mut clock = TestAccount::sysvar(
+            sysvar::clock::id(),
+            &Clock {
+                unix_timestamp: self.now,
+                ..Clock::default()
+            },
+        );
+        let mut token_vault_program = TestAccount::wallet(mpl_token_vault::id(), false);
+        let mut buyer_vault_shares = TestAccount::new(spl_token::id(), vec![]);
+        let mut vault_fraction_mint = TestAccount::wallet(init.vault_state.fraction_mint, false);
+        let mut vault_fraction_treasury =
+            TestAccount::wallet(init.vault_state.fraction_treasury, false);
+        let mut vault_redeem_treasury =
+            TestAccount::wallet(init.vault_state.redeem_treasury, false);
+        let mut vault_burn_authority = TestAccount::wallet(vault_authority(&init.vault.key), false);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+        let mut accounts = vec![
+            init.fraction_manager.info(),
+            init.vault.info(),
+            self.fraction_mint.info(),
+            init.external_price_account.info(),
+            init.accept_payment.info(),
+            self.buyer_token_account.info(),
+            self.buyer.info(),
+            init.store.info(),
+            token_program.info(),
+            init.fraction_store_config.info(),
+            self.store_fee_account.info(),
+            self.store_admin.info(),
+            clock.info(),
+            token_vault_program.info(),
+            buyer_vault_shares.info(),
+            vault_fraction_mint.info(),
+            vault_fraction_treasury.info(),
+            vault_redeem_treasury.info(),
+            vault_burn_authority.info(),
+            self.creator_snapshot.info(),
+            self.metadata.info(),
+            self.payer.info(),
+            system.info(),
+            rent.info(),
+        ];
+        accounts.extend(
+            self.creator_proceeds
+                .iter_mut()
+                .map(|proceeds| proceeds.info()),
+        );
+
+        process_buyout_fraction_manager(&init.program_id, &accounts)?;
+
+        Ok(init.fraction_manager.read())
+    }
+}
@@ -0,0 +1,212 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    processor::{
+        validate_and_activate::{
+            activate_redemption, assert_can_validate_and_activate, process_validate_and_activate,
+        },
+        validate_fraction_safety_deposit_box::assert_vault_treasury_empty,
+    },
+    state::{FractionManagerStatus, FractionManagerV1, FractionSafetyDepositConfig},
+};
+use mpl_token_vault::state::{Vault, MAX_VAULT_SIZE};
+use solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey};
+use utils::*;
+
+fn vault_with_boxes(token_type_count: u8) -> Vault {
+    let mut vault: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+    vault.token_type_count = token_type_count;
+    vault
+}
+
+fn priced_config() -> FractionSafetyDepositConfig {
+    let mut config = make_config(Pubkey::new_unique(), 0);
+    config.fixed_price = Some(1_000_000);
+    config
+}
+
+#[test]
+fn test_single_box_validate_and_activate() {
+    assert_eq!(
+        assert_can_validate_and_activate(&vault_with_boxes(1), &priced_config(), false),
+        Ok(())
+    );
+
+    // Validating the only box leaves the manager Validated, which activation opens.
+    let mut manager = make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Validated);
+    assert_eq!(activate_redemption(&mut manager, 500, 1_000), Ok(()));
+    assert_eq!(manager.state.status, FractionManagerStatus::Redeemable);
+    // Buyouts settle at the price redemption opened at
+    assert_eq!(manager.state.fixed_price_per_share, Some(500));
+    // Royalties start vesting as redemption opens
+    assert_eq!(manager.state.royalty_vesting_start, Some(1_000));
+}
+
+#[test]
+fn test_multi_box_rejected() {
+    assert_eq!(
+        assert_can_validate_and_activate(&vault_with_boxes(2), &priced_config(), false),
+        Err(MetaplexError::ActivationRequiresSingleBox.into())
+    );
+}
+
+#[test]
+fn test_multi_box_rejected_before_validation() {
+    let program_id = id();
+    let mut vault = vault_with_boxes(2);
+    vault.key = mpl_token_vault::state::Key::VaultV1;
+    let mut accounts: Vec<TestAccount> = (0..19)
+        .map(|_| TestAccount::new(program_id, vec![]))
+        .collect();
+    accounts[10] = TestAccount::new(mpl_token_vault::id(), account_data(&vault, MAX_VAULT_SIZE));
+    let infos: Vec<_> = accounts.iter_mut().map(|a| a.info()).collect();
+
+    assert_eq!(
+        process_validate_and_activate(
+            &program_id,
+            &infos,
+            priced_config(),
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None
+        ),
+        Err(MetaplexError::ActivationRequiresSingleBox.into())
+    );
+}
+
+#[test]
+fn test_multi_box_allowed() {
+    assert_eq!(
+        assert_can_validate_and_activate(&vault_with_boxes(2), &priced_config(), true),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_price_required() {
+    assert_eq!(
+        assert_can_validate_and_activate(
+            &vault_with_boxes(1),
+            &make_config(Pubkey::new_unique(), 0),
+            false
+        ),
+        Err(MetaplexError::ActivationPriceMissing.into())
+    );
+}
+
+#[test]
+fn test_boxes_left_to_validate() {
+    let mut manager =
+        make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+
+    assert_eq!(
+        activate_redemption(&mut manager, 500, 1_000),
+        Err(MetaplexError::ActivationBeforeAllBoxesValidated.into())
+    );
+    assert_eq!(manager.state.status, FractionManagerStatus::Initialized);
+}
+
+#[test]
+fn test_price_below_floor() {
+    let mut manager = make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Validated);
+    manager.state.min_redemption_price_per_share = 501;
+
+    assert_eq!(
+        activate_redemption(&mut manager, 500, 1_000),
+        Err(MetaplexError::PriceBelowFloor.into())
+    );
+    assert_eq!(manager.state.status, FractionManagerStatus::Validated);
+    assert_eq!(manager.state.fixed_price_per_share, None);
+}
+
+#[test]
+fn test_processor_validates_and_activates() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+    fixture.activate_at_price = Some(750);
+
+    assert_eq!(fixture.process_validate(None), Ok(()));
+    let manager: FractionManagerV1 = fixture.fraction_manager.read();
+    assert_eq!(manager.state.status, FractionManagerStatus::Redeemable);
+    assert_eq!(manager.state.fixed_price_per_share, Some(750));
+    assert_eq!(manager.state.safety_config_items_validated, 1);
+}
+
+#[test]
+fn test_processor_checks_floor() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+    fixture
+        .fraction_manager_state
+        .state
+        .min_redemption_price_per_share = 751;
+    fixture.activate_at_price = Some(750);
+
+    assert_eq!(
+        fixture.process_validate(None),
+        Err(MetaplexError::PriceBelowFloor.into())
+    );
+}
+
+#[test]
+fn test_empty_treasury() {
+    let mut vault = vault_with_boxes(1);
+    let mut treasury = treasury_holding(&mut vault, 0);
+
+    assert_eq!(
+        assert_vault_treasury_empty(&vault, &treasury.info()),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_pre_funded_treasury() {
+    let mut vault = vault_with_boxes(1);
+    let mut treasury = treasury_holding(&mut vault, 1);
+
+    assert_eq!(
+        assert_vault_treasury_empty(&vault, &treasury.info()),
+        Err(MetaplexError::VaultTreasuryNotEmpty.into())
+    );
+}
+
+#[test]
+fn test_treasury_must_be_the_vaults() {
+    let mut vault = vault_with_boxes(1);
+    treasury_holding(&mut vault, 1);
+    let mut other_treasury = treasury_holding(&mut vault_with_boxes(1), 0);
+
+    assert_eq!(
+        assert_vault_treasury_empty(&vault, &other_treasury.info()),
+        Err(MetaplexError::VaultTreasuryMismatch.into())
+    );
+}
+
+#[test]
+fn test_plain_validate_with_pre_funded_treasury() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+    fixture.fraction_treasury = treasury_holding(&mut fixture.vault_state, 1);
+
+    // Validating the vault's only box without activating still needs an empty treasury
+    assert_eq!(
+        fixture.process_validate(None),
+        Err(MetaplexError::VaultTreasuryNotEmpty.into())
+    );
+}
+
+#[test]
+fn test_plain_validate_with_empty_treasury() {
+    let _stubs = ProgramStubs::install();
+    let mut fixture = ValidationFixture::new();
+
+    assert_eq!(fixture.process_validate(None), Ok(()));
+    let manager: FractionManagerV1 = fixture.fraction_manager.read();
+    assert_eq!(manager.state.status, FractionManagerStatus::Validated);
+}
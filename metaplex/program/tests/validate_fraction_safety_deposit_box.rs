@@ -0,0 +1,1563 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    instruction::{FractionOrderRange, PdaAuthority},
+    processor::{
+        preallocate_fraction_safety_deposit_config::process_preallocate_fraction_safety_deposit_config,
+        validate_fraction_safety_deposit_box::{
+            assert_config_allocation, assert_depositor_owns_nft,
+            assert_expected_metadata_authority, assert_supply_logic_check,
+            make_fraction_safety_deposit_config, process_validate_fraction_safety_deposit_box,
+            record_box_validated, SupplyLogicCheckArgs,
+        },
+    },
+    state::{
+        FractionManager, FractionManagerStatus, FractionManagerV1, FractionOriginalAuthorityLookup,
+        FractionSafetyDepositConfig, FractionStoreConfig, FractionWinningConfigType, Key,
+        MintDenylist, WhitelistedCreator, FRACTION_BASE_SAFETY_CONFIG_SIZE,
+        MAX_FRACTION_AUTHORITY_LOOKUP_SIZE, MAX_MINT_DENYLIST_SIZE, MAX_WHITELISTED_CREATOR_SIZE,
+        PREFIX,
+    },
+    utils::{
+        assert_fraction_manager_status_consistent, assert_is_genuine_nft, assert_slot_not_past,
+    },
+};
+use mpl_token_metadata::state::Creator;
+use mpl_token_vault::state::{Vault, MAX_VAULT_SIZE};
+use solana_program::{
+    borsh::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, clock::Clock, rent::Rent},
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use utils::*;
+
+mod make_fraction_safety_deposit_config {
+    use super::*;
+
+    /// Allocate `config` at its canonical address, funded with `lamports`
+    fn allocate_config(
+        config: &FractionSafetyDepositConfig,
+        lamports: u64,
+    ) -> Result<TestAccount, ProgramError> {
+        let program_id = id();
+        let fraction_manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        let mut manager = TestAccount::new(program_id, vec![]);
+        manager.key = config.fraction_manager;
+        let mut safety_deposit = TestAccount::new(mpl_token_vault::id(), vec![]);
+        let mut config_account = TestAccount::new(program_id, vec![0; config.created_size()]);
+        config_account.key = fraction_manager
+            .config_address(&program_id, &manager.key, &safety_deposit.key)
+            .0;
+        config_account.lamports = lamports;
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut system = TestAccount::wallet(system_program::id(), false);
+
+        make_fraction_safety_deposit_config(
+            &program_id,
+            &fraction_manager,
+            &manager.info(),
+            &safety_deposit.info(),
+            &config_account.info(),
+            &payer.info(),
+            &rent.info(),
+            &system.info(),
+            config,
+        )?;
+
+        Ok(config_account)
+    }
+
+    fn make_config(lamports: u64) -> ProgramResult {
+        allocate_config(&utils::make_config(Pubkey::new_unique(), 0), lamports).map(|_| ())
+    }
+
+    #[test]
+    fn test_rent_exempt_config() {
+        let size = utils::make_config(Pubkey::default(), 0).created_size();
+
+        assert_eq!(make_config(Rent::default().minimum_balance(size)), Ok(()));
+    }
+
+    #[test]
+    fn test_created_config_reads_back() {
+        let mut config = utils::make_config(Pubkey::new_unique(), 3);
+        config.fixed_price = Some(500);
+        config.immutable = true;
+        let mut account = allocate_config(
+            &config,
+            Rent::default().minimum_balance(config.created_size()),
+        )
+        .unwrap();
+
+        let read = FractionSafetyDepositConfig::from_account_info(&account.info()).unwrap();
+        assert_eq!(read.key, Key::FractionSafetyDepositConfigV1);
+        assert_eq!(read.fraction_manager, config.fraction_manager);
+        assert_eq!(read.order, 3);
+        assert_eq!(read.fixed_price, Some(500));
+        assert!(read.immutable);
+    }
+
+    /// Created configs used to carry the auction manager's `SafetyDepositConfigV1` key, which
+    /// the fraction config reader rejects.
+    #[test]
+    fn test_created_config_is_fraction_keyed() {
+        let config = utils::make_config(Pubkey::new_unique(), 0);
+        let account = allocate_config(
+            &config,
+            Rent::default().minimum_balance(config.created_size()),
+        )
+        .unwrap();
+
+        assert_eq!(account.data[0], Key::FractionSafetyDepositConfigV1 as u8);
+        assert_ne!(account.data[0], Key::SafetyDepositConfigV1 as u8);
+    }
+
+    #[test]
+    fn test_config_address_matches_seeds() {
+        let program_id = id();
+        let fraction_manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        let fraction_manager_key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), fraction_manager.vault().as_ref()],
+            &program_id,
+        )
+        .0;
+        let safety_deposit = Pubkey::new_unique();
+
+        let (address, bump) =
+            fraction_manager.config_address(&program_id, &fraction_manager_key, &safety_deposit);
+        assert_eq!(
+            address,
+            config_key(&program_id, &fraction_manager_key, &safety_deposit)
+        );
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    fraction_manager_key.as_ref(),
+                    safety_deposit.as_ref(),
+                    &[bump],
+                ],
+                &program_id,
+            ),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn test_config_of_other_manager() {
+        let program_id = id();
+        let fraction_manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        let mut manager = TestAccount::new(program_id, vec![]);
+        let mut safety_deposit = TestAccount::new(mpl_token_vault::id(), vec![]);
+        let mut config_account = TestAccount::new(program_id, vec![]);
+        config_account.key = config_key(&program_id, &Pubkey::new_unique(), &safety_deposit.key);
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let config = utils::make_config(manager.key, 0);
+
+        assert_eq!(
+            make_fraction_safety_deposit_config(
+                &program_id,
+                &fraction_manager,
+                &manager.info(),
+                &safety_deposit.info(),
+                &config_account.info(),
+                &payer.info(),
+                &rent.info(),
+                &system.info(),
+                &config,
+            ),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    /// The system program transfer is a no-op off-chain, so an unfunded
+    /// account stands in for an allocation that did not receive its rent.
+    #[test]
+    fn test_underfunded_config() {
+        assert_eq!(
+            make_config(0),
+            Err(MetaplexError::ConfigNotRentExempt.into())
+        );
+    }
+}
+
+mod preallocated_config {
+    use super::*;
+
+    pub fn config_account(fixture: &ValidationFixture) -> TestAccount {
+        let mut config = TestAccount::new(
+            fixture.program_id,
+            vec![0; FRACTION_BASE_SAFETY_CONFIG_SIZE],
+        );
+        config.key = config_key(
+            &fixture.program_id,
+            &fixture.fraction_manager.key,
+            &fixture.safety_deposit.key,
+        );
+        config
+    }
+
+    pub fn preallocate(
+        fixture: &mut ValidationFixture,
+        config: &mut TestAccount,
+        fraction_winning_config_type: FractionWinningConfigType,
+        metadata_authority: Option<Pubkey>,
+    ) -> ProgramResult {
+        fixture.sync();
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+        process_preallocate_fraction_safety_deposit_config(
+            &fixture.program_id,
+            &[
+                config.info(),
+                fixture.fraction_manager.info(),
+                fixture.safety_deposit.info(),
+                fixture.authority.info(),
+                payer.info(),
+                fixture.store.info(),
+                system.info(),
+                rent.info(),
+            ],
+            fraction_winning_config_type,
+            metadata_authority,
+        )
+    }
+
+    fn preallocated(
+        fraction_winning_config_type: FractionWinningConfigType,
+    ) -> (ValidationFixture, TestAccount) {
+        let mut fixture = ValidationFixture::new();
+        let mut config = config_account(&fixture);
+        preallocate(
+            &mut fixture,
+            &mut config,
+            fraction_winning_config_type,
+            None,
+        )
+        .unwrap();
+        (fixture, config)
+    }
+
+    #[test]
+    fn test_records_declared_type() {
+        let (_, mut config) = preallocated(FractionWinningConfigType::FractionMasterEditionV2);
+
+        assert_eq!(
+            config.data[0],
+            Key::FractionSafetyDepositConfigPreallocatedV1 as u8
+        );
+        assert_eq!(
+            FractionSafetyDepositConfig::get_preallocated_type(&config.info()),
+            Ok(Some(FractionWinningConfigType::FractionMasterEditionV2))
+        );
+        // Not a config validation can be skipped with
+        assert!(FractionSafetyDepositConfig::from_account_info(&config.info()).is_err());
+    }
+
+    #[test]
+    fn test_matching_validate() {
+        let (fixture, mut config) = preallocated(FractionWinningConfigType::FractionToken);
+
+        assert_eq!(
+            assert_config_allocation(
+                &fixture.program_id,
+                &config.info(),
+                FractionWinningConfigType::FractionToken
+            ),
+            Ok(())
+        );
+
+        // Validation then writes the real config over the pre-allocated one
+        let mut manager = TestAccount::new(fixture.program_id, vec![]);
+        manager.key = fixture.fraction_manager.key;
+        let mut safety_deposit = TestAccount::new(mpl_token_vault::id(), vec![]);
+        safety_deposit.key = fixture.safety_deposit.key;
+        let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+        let mut system = TestAccount::wallet(system_program::id(), false);
+        make_fraction_safety_deposit_config(
+            &fixture.program_id,
+            &fixture.fraction_manager.read::<FractionManagerV1>(),
+            &manager.info(),
+            &safety_deposit.info(),
+            &config.info(),
+            &payer.info(),
+            &rent.info(),
+            &system.info(),
+            &make_config(fixture.fraction_manager.key, 0),
+        )
+        .unwrap();
+
+        let read = FractionSafetyDepositConfig::from_account_info(&config.info()).unwrap();
+        assert_eq!(read.key, Key::FractionSafetyDepositConfigV1);
+        assert_eq!(
+            read.fraction_winning_config_type,
+            FractionWinningConfigType::FractionToken
+        );
+    }
+
+    #[test]
+    fn test_mismatching_validate() {
+        let (fixture, mut config) =
+            preallocated(FractionWinningConfigType::FractionMasterEditionV2);
+
+        assert_eq!(
+            assert_config_allocation(
+                &fixture.program_id,
+                &config.info(),
+                FractionWinningConfigType::FractionToken
+            ),
+            Err(MetaplexError::PreallocatedTypeMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_validated_config_rejected() {
+        let fixture = ValidationFixture::new();
+        let mut config = config_account(&fixture);
+        make_config(fixture.fraction_manager.key, 0)
+            .create(&config.info(), &fixture.fraction_manager.key)
+            .unwrap();
+
+        assert_eq!(
+            assert_config_allocation(
+                &fixture.program_id,
+                &config.info(),
+                FractionWinningConfigType::FractionToken
+            ),
+            Err(MetaplexError::AlreadyValidated.into())
+        );
+    }
+
+    #[test]
+    fn test_preallocate_once() {
+        let (mut fixture, mut config) = preallocated(FractionWinningConfigType::FractionToken);
+
+        assert_eq!(
+            preallocate(
+                &mut fixture,
+                &mut config,
+                FractionWinningConfigType::FractionMasterEditionV2,
+                None
+            ),
+            Err(MetaplexError::AlreadyInitialized.into())
+        );
+    }
+}
+
+mod expected_metadata_authority {
+    use super::{preallocated_config::*, *};
+
+    fn expecting(metadata_authority: Option<Pubkey>) -> FractionSafetyDepositConfig {
+        let mut config = make_config(Pubkey::new_unique(), 0);
+        config.metadata_authority = metadata_authority;
+        config
+    }
+
+    /// A config pre-allocated expecting `expected` to sign
+    fn preallocated_expecting(expected: Pubkey) -> TestAccount {
+        let mut fixture = ValidationFixture::new();
+        let mut config = config_account(&fixture);
+        preallocate(
+            &mut fixture,
+            &mut config,
+            FractionWinningConfigType::FractionToken,
+            Some(expected),
+        )
+        .unwrap();
+        config
+    }
+
+    fn check(
+        config_account: &mut TestAccount,
+        config: &FractionSafetyDepositConfig,
+        metadata_authority: &mut TestAccount,
+    ) -> ProgramResult {
+        assert_expected_metadata_authority(
+            &config_account.info(),
+            config,
+            &metadata_authority.info(),
+        )
+    }
+
+    #[test]
+    fn test_no_expected_authority() {
+        let mut config_account = TestAccount::new(id(), vec![]);
+        let mut anyone = TestAccount::wallet(Pubkey::new_unique(), false);
+
+        assert_eq!(
+            check(&mut config_account, &expecting(None), &mut anyone),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validator_cannot_name_authority() {
+        let mut config_account = TestAccount::new(id(), vec![]);
+        let mut chosen = TestAccount::wallet(Pubkey::new_unique(), true);
+        let config = expecting(Some(chosen.key));
+
+        assert_eq!(
+            check(&mut config_account, &config, &mut chosen),
+            Err(MetaplexError::MetadataAuthorityRequiresPreallocation.into())
+        );
+    }
+
+    #[test]
+    fn test_matching_authority() {
+        let mut expected = TestAccount::wallet(Pubkey::new_unique(), true);
+        let mut config_account = preallocated_expecting(expected.key);
+        let config = expecting(Some(expected.key));
+
+        assert_eq!(check(&mut config_account, &config, &mut expected), Ok(()));
+    }
+
+    #[test]
+    fn test_non_matching_authority() {
+        let expected = Pubkey::new_unique();
+        let mut config_account = preallocated_expecting(expected);
+        let mut other = TestAccount::wallet(Pubkey::new_unique(), true);
+
+        assert_eq!(
+            check(&mut config_account, &expecting(Some(expected)), &mut other),
+            Err(MetaplexError::UnexpectedMetadataAuthority.into())
+        );
+    }
+
+    #[test]
+    fn test_expected_authority_must_sign() {
+        let mut expected = TestAccount::wallet(Pubkey::new_unique(), false);
+        let mut config_account = preallocated_expecting(expected.key);
+        let config = expecting(Some(expected.key));
+
+        assert_eq!(
+            check(&mut config_account, &config, &mut expected),
+            Err(MetaplexError::UnexpectedMetadataAuthority.into())
+        );
+    }
+
+    #[test]
+    fn test_preallocated_authority_binds() {
+        let expected = Pubkey::new_unique();
+        let mut config_account = preallocated_expecting(expected);
+        let mut expected = TestAccount::wallet(expected, true);
+
+        // A config passed without the restriction, or naming someone else, cannot lift it
+        assert_eq!(
+            check(&mut config_account, &expecting(None), &mut expected),
+            Err(MetaplexError::PreallocatedConfigMismatch.into())
+        );
+        assert_eq!(
+            check(
+                &mut config_account,
+                &expecting(Some(Pubkey::new_unique())),
+                &mut expected
+            ),
+            Err(MetaplexError::PreallocatedConfigMismatch.into())
+        );
+    }
+}
+
+mod max_slot {
+    use super::*;
+
+    fn clock_at(slot: u64) -> TestAccount {
+        TestAccount::sysvar(
+            sysvar::clock::id(),
+            &Clock {
+                slot,
+                ..Clock::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_within_max_slot() {
+        assert_eq!(
+            assert_slot_not_past(&clock_at(99).info(), Some(100)),
+            Ok(())
+        );
+        assert_eq!(
+            assert_slot_not_past(&clock_at(100).info(), Some(100)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_past_max_slot() {
+        assert_eq!(
+            assert_slot_not_past(&clock_at(101).info(), Some(100)),
+            Err(MetaplexError::TransactionTooOld.into())
+        );
+    }
+
+    #[test]
+    fn test_no_max_slot() {
+        assert_eq!(
+            assert_slot_not_past(&clock_at(u64::MAX).info(), None),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_clock_must_be_sysvar() {
+        let mut clock = clock_at(0);
+        clock.key = Pubkey::new_unique();
+
+        assert_eq!(
+            assert_slot_not_past(&clock.info(), Some(100)),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_past_max_slot() {
+        let program_id = id();
+        let mut accounts: Vec<TestAccount> = (0..18)
+            .map(|_| TestAccount::new(program_id, vec![]))
+            .collect();
+        accounts.push(clock_at(101));
+        accounts.push(TestAccount::new(program_id, vec![]));
+        accounts.push(TestAccount::new(program_id, vec![]));
+        let infos: Vec<_> = accounts.iter_mut().map(|a| a.info()).collect();
+
+        assert_eq!(
+            process_validate_fraction_safety_deposit_box(
+                &program_id,
+                &infos,
+                utils::make_config(Pubkey::new_unique(), 0),
+                Some(100),
+                None,
+                false,
+                None,
+                false,
+                None,
+            ),
+            Err(MetaplexError::TransactionTooOld.into())
+        );
+    }
+}
+
+mod pricing_lookup {
+    use super::*;
+
+    #[test]
+    fn test_divergent_pricing_lookup() {
+        let mut fixture = ValidationFixture::new();
+        fixture.vault_state.pricing_lookup_address = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::PricingLookupMismatch.into())
+        );
+    }
+}
+
+mod vault_token_types {
+    use super::*;
+
+    #[test]
+    fn test_token_type_added_after_init() {
+        let mut fixture = ValidationFixture::new();
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+
+        // The vault is reopened and a second box is added
+        fixture.vault_state.token_type_count = 2;
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::VaultTokenTypesAdded.into())
+        );
+    }
+}
+
+mod token_box_edition {
+    use super::*;
+
+    #[test]
+    fn test_master_edition_for_token_box() {
+        let mut fixture = ValidationFixture::new();
+        fixture.edition = master_edition(&fixture.mint.key);
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::UnexpectedEditionForTokenBox.into())
+        );
+    }
+}
+
+mod whitelisted_creator {
+    use super::*;
+
+    fn whitelist_entry() -> Vec<u8> {
+        account_data(
+            &WhitelistedCreator {
+                key: Key::WhitelistedCreatorV1,
+                address: Pubkey::new_unique(),
+                activated: true,
+            },
+            MAX_WHITELISTED_CREATOR_SIZE,
+        )
+    }
+
+    #[test]
+    fn test_no_whitelisted_creator() {
+        let mut fixture = ValidationFixture::new();
+        fixture.whitelisted_creator = TestAccount::wallet(system_program::id(), false);
+
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+    }
+
+    #[test]
+    fn test_uninitialized_whitelisted_creator() {
+        let mut fixture = ValidationFixture::new();
+        fixture.whitelisted_creator = TestAccount::wallet(Pubkey::new_unique(), false);
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::WhitelistedCreatorUninitialized.into())
+        );
+    }
+
+    #[test]
+    fn test_whitelisted_creator_wrong_owner() {
+        let mut fixture = ValidationFixture::new();
+        fixture.whitelisted_creator = TestAccount::new(Pubkey::new_unique(), whitelist_entry());
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::WhitelistedCreatorWrongOwner.into())
+        );
+    }
+}
+
+mod vault_token_store {
+    use super::*;
+
+    fn token_store_held_by(fixture: &ValidationFixture, owner: Pubkey) -> Vec<u8> {
+        packed_data(TokenAccount {
+            mint: fixture.mint.key,
+            owner,
+            amount: 1,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        })
+    }
+
+    #[test]
+    fn test_vault_derived_store() {
+        let mut fixture = ValidationFixture::new();
+        fixture.safety_deposit_token_store.data =
+            token_store_held_by(&fixture, vault_authority(&fixture.vault.key));
+
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+    }
+
+    #[test]
+    fn test_non_canonical_store() {
+        let mut fixture = ValidationFixture::new();
+        // Still token program owned, but held by an authority the vault program never derived
+        fixture.safety_deposit_token_store.data =
+            token_store_held_by(&fixture, Pubkey::new_unique());
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::StoreNotVaultDerived.into())
+        );
+    }
+
+    #[test]
+    fn test_store_of_other_vault() {
+        let mut fixture = ValidationFixture::new();
+        fixture.safety_deposit_token_store.data =
+            token_store_held_by(&fixture, vault_authority(&Pubkey::new_unique()));
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::StoreNotVaultDerived.into())
+        );
+    }
+}
+
+mod mint_denylist {
+    use super::*;
+
+    fn denylist(fixture: &ValidationFixture, mints: &[Pubkey]) -> Vec<u8> {
+        let mut denylist = MintDenylist {
+            key: Key::MintDenylistV1,
+            store: fixture.store.key,
+            mints: vec![],
+        };
+        for mint in mints {
+            denylist.set_denylisted(*mint, true).unwrap();
+        }
+        account_data(&denylist, MAX_MINT_DENYLIST_SIZE)
+    }
+
+    /// Turn the fixture's store private with the metadata's sole creator whitelisted
+    fn whitelist_creator(fixture: &mut ValidationFixture) {
+        let creator = Pubkey::new_unique();
+        fixture.store_state.public = false;
+        fixture.metadata_state.data.creators = Some(vec![Creator {
+            address: creator,
+            verified: true,
+            share: 100,
+        }]);
+        fixture.whitelisted_creator = TestAccount::new(
+            fixture.program_id,
+            account_data(
+                &WhitelistedCreator {
+                    key: Key::WhitelistedCreatorV1,
+                    address: creator,
+                    activated: true,
+                },
+                MAX_WHITELISTED_CREATOR_SIZE,
+            ),
+        );
+        fixture.whitelisted_creator.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                fixture.program_id.as_ref(),
+                fixture.store.key.as_ref(),
+                creator.as_ref(),
+            ],
+            &fixture.program_id,
+        )
+        .0;
+    }
+
+    #[test]
+    fn test_no_denylist() {
+        assert_eq!(ValidationFixture::new().assert_common_checks(), Ok(()));
+    }
+
+    #[test]
+    fn test_denylisted_mint() {
+        let mut fixture = ValidationFixture::new();
+        fixture.mint_denylist.data = denylist(&fixture, &[fixture.mint.key]);
+        fixture.mint_denylist.owner = fixture.program_id;
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::MintDenylisted.into())
+        );
+    }
+
+    #[test]
+    fn test_other_mints_denylisted() {
+        let mut fixture = ValidationFixture::new();
+        let others = [Pubkey::new_unique(), Pubkey::new_unique()];
+        fixture.mint_denylist.data = denylist(&fixture, &others);
+        fixture.mint_denylist.owner = fixture.program_id;
+
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+    }
+
+    #[test]
+    fn test_denylist_must_be_store_derived() {
+        let mut fixture = ValidationFixture::new();
+        fixture.mint_denylist.key = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    #[test]
+    fn test_denylist_overrides_whitelisted_creator() {
+        let mut fixture = ValidationFixture::new();
+        whitelist_creator(&mut fixture);
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+
+        fixture.mint_denylist.data = denylist(&fixture, &[fixture.mint.key]);
+        fixture.mint_denylist.owner = fixture.program_id;
+        assert_eq!(
+            fixture.assert_common_checks(),
+            Err(MetaplexError::MintDenylisted.into())
+        );
+    }
+
+    #[test]
+    fn test_set_denylisted_keeps_order() {
+        let mut denylist = MintDenylist {
+            key: Key::MintDenylistV1,
+            store: Pubkey::new_unique(),
+            mints: vec![],
+        };
+        let mints: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        for mint in mints.iter().rev() {
+            denylist.set_denylisted(*mint, true).unwrap();
+        }
+        // Denylisting twice does not duplicate the entry
+        denylist.set_denylisted(mints[2], true).unwrap();
+
+        let mut sorted = mints.clone();
+        sorted.sort();
+        assert_eq!(denylist.mints, sorted);
+
+        denylist.set_denylisted(mints[2], false).unwrap();
+        assert!(!denylist.is_denylisted(&mints[2]));
+        assert!(mints
+            .iter()
+            .filter(|mint| **mint != mints[2])
+            .all(|mint| denylist.is_denylisted(mint)));
+    }
+}
+
+mod pda_authority {
+    use super::*;
+
+    const LAUNCHPAD_SEED: &[u8] = b"launchpad";
+
+    pub struct PdaFixture {
+        pub validation: ValidationFixture,
+        pub fraction_store_config: FractionStoreConfig,
+        pub pda_authority: Option<PdaAuthority>,
+        pub metadata_authority: TestAccount,
+        pub return_to: Option<Pubkey>,
+    }
+
+    impl PdaFixture {
+        /// A master edition box whose metadata is updated through a launchpad PDA
+        pub fn new() -> PdaFixture {
+            let launchpad = Pubkey::new_unique();
+            let (update_authority, bump) =
+                Pubkey::find_program_address(&[LAUNCHPAD_SEED], &launchpad);
+
+            let mut validation = ValidationFixture::new();
+            validation.winning_config_type = FractionWinningConfigType::FractionMasterEditionV2;
+            validation.metadata_state.update_authority = update_authority;
+            validation.edition = master_edition(&validation.mint.key);
+            // Account creation is a no-op off-chain, so the lookup starts out allocated
+            validation.original_authority_lookup = TestAccount::new(
+                validation.program_id,
+                vec![0; MAX_FRACTION_AUTHORITY_LOOKUP_SIZE],
+            );
+            validation.original_authority_lookup.key = Pubkey::find_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    validation.vault.key.as_ref(),
+                    validation.metadata.key.as_ref(),
+                ],
+                &validation.program_id,
+            )
+            .0;
+
+            let mut fraction_store_config = FractionStoreConfig::new(validation.store.key);
+            fraction_store_config.approved_authority_programs = vec![launchpad];
+
+            PdaFixture {
+                validation,
+                fraction_store_config,
+                pda_authority: Some(PdaAuthority {
+                    program: launchpad,
+                    seeds: vec![LAUNCHPAD_SEED.to_vec(), vec![bump]],
+                }),
+                metadata_authority: TestAccount::wallet(update_authority, false),
+                return_to: None,
+            }
+        }
+
+        pub fn assert_supply_logic_check(&mut self) -> ProgramResult {
+            let fixture = &mut self.validation;
+            fixture.sync();
+            let mut payer = TestAccount::wallet(Pubkey::new_unique(), true);
+            let mut system = TestAccount::wallet(system_program::id(), false);
+            let mut rent = TestAccount::sysvar(sysvar::rent::id(), &Rent::default());
+
+            assert_supply_logic_check(SupplyLogicCheckArgs {
+                program_id: &fixture.program_id,
+                fraction_manager_info: &fixture.fraction_manager.info(),
+                metadata_info: &fixture.metadata.info(),
+                edition_info: &fixture.edition.info(),
+                metadata_authority_info: &self.metadata_authority.info(),
+                original_authority_lookup_info: &fixture.original_authority_lookup.info(),
+                rent_info: &rent.info(),
+                system_info: &system.info(),
+                payer_info: &payer.info(),
+                token_metadata_program_info: &fixture.token_metadata_program.info(),
+                safety_deposit_token_store_info: &fixture.safety_deposit_token_store.info(),
+                mint_info: &fixture.mint.info(),
+                fraction_manager: &fixture.fraction_manager_state,
+                winning_config_type: &fixture.winning_config_type,
+                metadata: &fixture.metadata_state,
+                safety_deposit: &fixture.safety_deposit_state,
+                store: &fixture.store_state,
+                fraction_store_config: &self.fraction_store_config,
+                pda_authority: self.pda_authority.as_ref(),
+                return_to: self.return_to,
+            })
+        }
+
+        pub fn lookup(&self) -> FractionOriginalAuthorityLookup {
+            self.validation.original_authority_lookup.read()
+        }
+    }
+
+    #[test]
+    fn test_approved_pda_authority_recorded() {
+        let mut fixture = PdaFixture::new();
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+
+        let lookup = fixture.lookup();
+        assert_eq!(lookup.key, Key::FractionOriginalAuthorityLookupV1);
+        assert_eq!(
+            lookup.original_authority,
+            fixture.validation.metadata_state.update_authority
+        );
+        assert!(lookup.pda_authority);
+    }
+
+    #[test]
+    fn test_unapproved_program() {
+        let mut fixture = PdaFixture::new();
+        fixture.fraction_store_config.approved_authority_programs = vec![];
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::AuthorityProgramNotApproved.into())
+        );
+    }
+
+    #[test]
+    fn test_seeds_must_derive_update_authority() {
+        let mut fixture = PdaFixture::new();
+        fixture.pda_authority.as_mut().unwrap().seeds[0] = b"other".to_vec();
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    #[test]
+    fn test_pda_of_other_program() {
+        let mut fixture = PdaFixture::new();
+        let other = Pubkey::new_unique();
+        fixture.pda_authority.as_mut().unwrap().program = other;
+        fixture
+            .fraction_store_config
+            .approved_authority_programs
+            .push(other);
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::DerivedKeyInvalid.into())
+        );
+    }
+
+    #[test]
+    fn test_signing_authority_still_transferred() {
+        let mut fixture = PdaFixture::new();
+        let wallet = Pubkey::new_unique();
+        fixture.validation.metadata_state.update_authority = wallet;
+        fixture.metadata_authority = TestAccount::wallet(wallet, true);
+        fixture.pda_authority = None;
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+
+        let lookup = fixture.lookup();
+        assert_eq!(lookup.original_authority, wallet);
+        assert!(!lookup.pda_authority);
+    }
+}
+
+mod return_to {
+    use super::{pda_authority::PdaFixture, *};
+
+    /// A box validated by the wallet holding its update authority
+    fn signed_fixture() -> PdaFixture {
+        let mut fixture = PdaFixture::new();
+        let wallet = Pubkey::new_unique();
+        fixture.validation.metadata_state.update_authority = wallet;
+        fixture.metadata_authority = TestAccount::wallet(wallet, true);
+        fixture.pda_authority = None;
+        fixture
+    }
+
+    /// An ed25519 point, as every wallet key is
+    fn wallet_key() -> Pubkey {
+        loop {
+            let key = Pubkey::new_unique();
+            if key.is_on_curve() {
+                return key;
+            }
+        }
+    }
+
+    #[test]
+    fn test_returns_to_self() {
+        let mut fixture = signed_fixture();
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+
+        let lookup = fixture.lookup();
+        assert_eq!(lookup.return_to, None);
+        assert_eq!(lookup.return_authority(), fixture.metadata_authority.key);
+    }
+
+    #[test]
+    fn test_returns_to_new_owner() {
+        let mut fixture = signed_fixture();
+        let heir = wallet_key();
+        fixture.return_to = Some(heir);
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+
+        let lookup = fixture.lookup();
+        assert_eq!(lookup.original_authority, fixture.metadata_authority.key);
+        assert_eq!(lookup.return_to, Some(heir));
+        assert_eq!(lookup.return_authority(), heir);
+    }
+
+    #[test]
+    fn test_return_to_must_be_wallet() {
+        let pda = Pubkey::find_program_address(&[b"heir"], &Pubkey::new_unique()).0;
+
+        for return_to in [pda, Pubkey::default()] {
+            let mut fixture = signed_fixture();
+            fixture.return_to = Some(return_to);
+
+            assert_eq!(
+                fixture.assert_supply_logic_check(),
+                Err(MetaplexError::InvalidReturnToAddress.into())
+            );
+        }
+    }
+}
+
+mod genuine_nft {
+    use super::{pda_authority::PdaFixture, *};
+
+    fn mint(supply: u64, decimals: u8) -> Mint {
+        Mint {
+            mint_authority: COption::None,
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+    }
+
+    fn holding(amount: u64) -> TokenAccount {
+        TokenAccount {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }
+    }
+
+    #[test]
+    fn test_one_of_one() {
+        assert_eq!(assert_is_genuine_nft(&mint(1, 0), &holding(1)), Ok(()));
+    }
+
+    #[test]
+    fn test_mint_with_decimals() {
+        assert_eq!(
+            assert_is_genuine_nft(&mint(1, 2), &holding(1)),
+            Err(MetaplexError::NotAGenuineNft.into())
+        );
+    }
+
+    #[test]
+    fn test_mint_with_supply_of_two() {
+        assert_eq!(
+            assert_is_genuine_nft(&mint(2, 0), &holding(1)),
+            Err(MetaplexError::NotAGenuineNft.into())
+        );
+    }
+
+    #[test]
+    fn test_master_edition_box_checked() {
+        let mut fixture = PdaFixture::new();
+        fixture.validation.mint.data = packed_data(mint(1, 2));
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::NotAGenuineNft.into())
+        );
+    }
+}
+
+mod edition_program {
+    use super::{pda_authority::PdaFixture, *};
+
+    /// The box's edition, moved to `program` at the address `derived_by` gives it
+    fn edition_on(fixture: &mut PdaFixture, program: Pubkey, derived_by: Pubkey) {
+        let mint = fixture.validation.metadata_state.mint;
+        let edition = &mut fixture.validation.edition;
+        edition.owner = program;
+        edition.key = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                derived_by.as_ref(),
+                mint.as_ref(),
+                mpl_token_metadata::state::EDITION.as_bytes(),
+            ],
+            &derived_by,
+        )
+        .0;
+    }
+
+    #[test]
+    fn test_store_metadata_program() {
+        let mut fixture = PdaFixture::new();
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+    }
+
+    #[test]
+    fn test_common_checks_leave_edition_to_supply_logic() {
+        let mut fixture = ValidationFixture::new();
+        fixture.winning_config_type = FractionWinningConfigType::FractionMasterEditionV2;
+        fixture.edition.owner = Pubkey::new_unique();
+
+        assert_eq!(fixture.assert_common_checks(), Ok(()));
+    }
+
+    #[test]
+    fn test_consistent_approved_program() {
+        let metadata_fork = Pubkey::new_unique();
+        let mut fixture = PdaFixture::new();
+        fixture
+            .fraction_store_config
+            .approved_metadata_programs
+            .push(metadata_fork);
+        edition_on(&mut fixture, metadata_fork, metadata_fork);
+
+        assert_eq!(fixture.assert_supply_logic_check(), Ok(()));
+    }
+
+    #[test]
+    fn test_unapproved_program() {
+        let metadata_fork = Pubkey::new_unique();
+        let mut fixture = PdaFixture::new();
+        edition_on(&mut fixture, metadata_fork, metadata_fork);
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::EditionProgramNotApproved.into())
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_edition_address() {
+        // Owned by the fork, but at the address the store's own program derives
+        let metadata_fork = Pubkey::new_unique();
+        let mut fixture = PdaFixture::new();
+        fixture
+            .fraction_store_config
+            .approved_metadata_programs
+            .push(metadata_fork);
+        edition_on(&mut fixture, metadata_fork, mpl_token_metadata::id());
+
+        assert_eq!(
+            fixture.assert_supply_logic_check(),
+            Err(MetaplexError::InvalidEditionAddress.into())
+        );
+    }
+}
+
+mod depositor_custody {
+    use super::*;
+
+    struct CustodyFixture {
+        validation: ValidationFixture,
+        depositor: TestAccount,
+        depositor_token_account: TestAccount,
+    }
+
+    impl CustodyFixture {
+        /// A depositor holding the box's NFT in its own token account
+        fn new() -> CustodyFixture {
+            let validation = ValidationFixture::new();
+            let depositor = TestAccount::wallet(Pubkey::new_unique(), true);
+            let depositor_token_account =
+                TestAccount::new(spl_token::id(), holding(&validation, depositor.key, 1));
+
+            CustodyFixture {
+                validation,
+                depositor,
+                depositor_token_account,
+            }
+        }
+
+        fn assert_depositor_owns_nft(&mut self) -> ProgramResult {
+            assert_depositor_owns_nft(
+                &self.validation.store_state,
+                &self.validation.metadata_state,
+                &self.depositor.info(),
+                &self.depositor_token_account.info(),
+            )
+        }
+    }
+
+    fn holding(fixture: &ValidationFixture, owner: Pubkey, amount: u64) -> Vec<u8> {
+        packed_data(TokenAccount {
+            mint: fixture.metadata_state.mint,
+            owner,
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        })
+    }
+
+    #[test]
+    fn test_owning_depositor() {
+        let mut fixture = CustodyFixture::new();
+
+        assert_eq!(fixture.assert_depositor_owns_nft(), Ok(()));
+    }
+
+    #[test]
+    fn test_non_owner() {
+        let mut fixture = CustodyFixture::new();
+        fixture.depositor_token_account.data =
+            holding(&fixture.validation, Pubkey::new_unique(), 1);
+
+        assert_eq!(
+            fixture.assert_depositor_owns_nft(),
+            Err(MetaplexError::DepositorDoesNotOwnNft.into())
+        );
+    }
+
+    #[test]
+    fn test_empty_token_account() {
+        let mut fixture = CustodyFixture::new();
+        fixture.depositor_token_account.data =
+            holding(&fixture.validation, fixture.depositor.key, 0);
+
+        assert_eq!(
+            fixture.assert_depositor_owns_nft(),
+            Err(MetaplexError::DepositorDoesNotOwnNft.into())
+        );
+    }
+
+    #[test]
+    fn test_other_mint() {
+        let mut fixture = CustodyFixture::new();
+        fixture.validation.metadata_state.mint = Pubkey::new_unique();
+
+        assert_eq!(
+            fixture.assert_depositor_owns_nft(),
+            Err(MetaplexError::DepositorDoesNotOwnNft.into())
+        );
+    }
+
+    #[test]
+    fn test_depositor_must_sign() {
+        let mut fixture = CustodyFixture::new();
+        fixture.depositor.is_signer = false;
+
+        assert_eq!(
+            fixture.assert_depositor_owns_nft(),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+}
+
+mod status_invariant {
+    use super::*;
+
+    #[test]
+    fn test_consistent_states() {
+        let mut manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        manager.state.vault_token_type_count = 2;
+        manager.state.safety_config_items_validated = 1;
+        assert_eq!(assert_fraction_manager_status_consistent(&manager), Ok(()));
+
+        manager.state.status = FractionManagerStatus::Validated;
+        manager.state.safety_config_items_validated = 2;
+        assert_eq!(assert_fraction_manager_status_consistent(&manager), Ok(()));
+    }
+
+    #[test]
+    fn test_validated_with_boxes_left() {
+        let mut manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Validated);
+        manager.state.vault_token_type_count = 2;
+        manager.state.safety_config_items_validated = 1;
+
+        assert_eq!(
+            assert_fraction_manager_status_consistent(&manager),
+            Err(MetaplexError::ManagerInvariantViolated.into())
+        );
+    }
+}
+
+mod disjoint_order_ranges {
+    use super::*;
+
+    pub const TOKEN_TYPE_COUNT: u8 = 6;
+
+    /// A manager whose vault had TOKEN_TYPE_COUNT boxes at init
+    pub fn initialized_manager() -> FractionManagerV1 {
+        let mut manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        manager.state.vault_token_type_count = TOKEN_TYPE_COUNT;
+        manager
+    }
+
+    /// Records the box against a vault whose fraction treasury is empty
+    pub fn record_box(
+        manager: &mut FractionManagerV1,
+        order: u8,
+        order_range: Option<FractionOrderRange>,
+    ) -> ProgramResult {
+        let mut vault: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        let mut fraction_treasury = treasury_holding(&mut vault, 0);
+        record_box_validated(
+            manager,
+            order,
+            order_range,
+            &vault,
+            &fraction_treasury.info(),
+        )
+    }
+
+    fn range(start: u64, end: u64) -> Option<FractionOrderRange> {
+        Some(FractionOrderRange { start, end })
+    }
+
+    fn validate_range(
+        manager: &mut FractionManagerV1,
+        orders: &[u8],
+        order_range: Option<FractionOrderRange>,
+    ) -> ProgramResult {
+        for &order in orders {
+            record_box(manager, order, order_range)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_disjoint_ranges_in_any_order() {
+        let mut manager = initialized_manager();
+
+        // The later range lands first, and boxes within a range land out of order
+        validate_range(&mut manager, &[5, 3, 4], range(3, 6)).unwrap();
+        assert_eq!(manager.state.safety_config_items_validated, 3);
+        assert_eq!(manager.status(), FractionManagerStatus::Initialized);
+
+        validate_range(&mut manager, &[1, 0, 2], range(0, 3)).unwrap();
+        assert_eq!(
+            manager.state.safety_config_items_validated,
+            TOKEN_TYPE_COUNT as u64
+        );
+        assert_eq!(manager.status(), FractionManagerStatus::Validated);
+        assert!((0..TOKEN_TYPE_COUNT).all(|order| manager.order_validated(order)));
+        assert_eq!(assert_fraction_manager_status_consistent(&manager), Ok(()));
+    }
+
+    #[test]
+    fn test_box_outside_range() {
+        let mut manager = initialized_manager();
+
+        assert_eq!(
+            record_box(&mut manager, 3, range(0, 3)),
+            Err(MetaplexError::SafetyDepositOrderOutsideRange.into())
+        );
+        assert_eq!(manager.state.safety_config_items_validated, 0);
+        assert!(!manager.order_validated(3));
+    }
+
+    #[test]
+    fn test_box_counted_once() {
+        let mut manager = initialized_manager();
+        record_box(&mut manager, 2, None).unwrap();
+
+        // Overlapping ranges both reaching the same box
+        assert_eq!(
+            record_box(&mut manager, 2, range(2, 4)),
+            Err(MetaplexError::AlreadyValidated.into())
+        );
+        assert_eq!(manager.state.safety_config_items_validated, 1);
+    }
+
+    #[test]
+    fn test_orders_map_to_own_bit() {
+        let mut manager =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Initialized);
+        manager.mark_order_validated(255).unwrap();
+        manager.mark_order_validated(8).unwrap();
+
+        assert!(manager.order_validated(255));
+        assert!(manager.order_validated(8));
+        assert!(!manager.order_validated(7));
+        assert!(!manager.order_validated(0));
+    }
+}
+
+mod finalization_order {
+    use super::{disjoint_order_ranges::*, *};
+
+    /// Validates boxes in `orders` on a copy of `initial`, checking the manager only turns
+    /// Validated on the last
+    fn finalize(initial: &FractionManagerV1, orders: &[u8]) -> FractionManagerV1 {
+        let mut manager = initial.clone();
+
+        for (validated, &order) in orders.iter().enumerate() {
+            assert_eq!(manager.status(), FractionManagerStatus::Initialized);
+            record_box(&mut manager, order, None).unwrap();
+            assert_eq!(manager.configs_validated(), validated as u64 + 1);
+        }
+        assert_eq!(manager.status(), FractionManagerStatus::Validated);
+
+        manager
+    }
+
+    #[test]
+    fn test_any_order_finalizes_identically() {
+        let initial = initialized_manager();
+        let in_order = fraction_manager_data(&finalize(&initial, &[0, 1, 2, 3, 4, 5]));
+
+        for orders in [[5, 4, 3, 2, 1, 0], [3, 0, 5, 1, 4, 2], [1, 3, 5, 0, 2, 4]].iter() {
+            assert_eq!(fraction_manager_data(&finalize(&initial, orders)), in_order);
+        }
+    }
+
+    #[test]
+    fn test_validated_only_once() {
+        let mut manager = finalize(&initialized_manager(), &[2, 0, 1, 5, 3, 4]);
+        let before = fraction_manager_data(&manager);
+
+        // A box the vault never had at init
+        assert_eq!(
+            record_box(&mut manager, TOKEN_TYPE_COUNT, None),
+            Err(MetaplexError::InvalidStatus.into())
+        );
+        assert_eq!(fraction_manager_data(&manager), before);
+    }
+
+    #[test]
+    fn test_pre_funded_treasury_blocks_validation() {
+        let mut manager = initialized_manager();
+        manager.state.vault_token_type_count = 2;
+        record_box(&mut manager, 0, None).unwrap();
+
+        // Only the box finishing validation looks at the treasury
+        let mut vault: Vault = try_from_slice_unchecked(&[0; MAX_VAULT_SIZE]).unwrap();
+        let mut fraction_treasury = treasury_holding(&mut vault, 1);
+        assert_eq!(
+            record_box_validated(&mut manager, 1, None, &vault, &fraction_treasury.info()),
+            Err(MetaplexError::VaultTreasuryNotEmpty.into())
+        );
+        assert_eq!(manager.status(), FractionManagerStatus::Initialized);
+    }
+
+    #[test]
+    fn test_finalizes_on_snapshot_count() {
+        let mut manager = initialized_manager();
+        manager.state.vault_token_type_count = 1;
+
+        assert_eq!(record_box(&mut manager, 4, None), Ok(()));
+        assert_eq!(manager.status(), FractionManagerStatus::Validated);
+    }
+}
+
+mod instruction_builder {
+    use super::*;
+    use borsh::BorshDeserialize;
+    use mpl_metaplex::instruction::{
+        create_validate_fraction_safety_deposit_box_instruction, MetaplexInstruction,
+        ValidateFractionSafetyDepositBoxAccounts, ValidateFractionSafetyDepositBoxOptions,
+    };
+
+    fn accounts() -> ValidateFractionSafetyDepositBoxAccounts {
+        ValidateFractionSafetyDepositBoxAccounts {
+            fraction_manager: Pubkey::new_unique(),
+            metadata: Pubkey::new_unique(),
+            original_authority_lookup: Pubkey::new_unique(),
+            whitelisted_creator: Pubkey::new_unique(),
+            store: Pubkey::new_unique(),
+            safety_deposit_box: Pubkey::new_unique(),
+            safety_deposit_token_store: Pubkey::new_unique(),
+            safety_deposit_mint: Pubkey::new_unique(),
+            edition: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            fraction_treasury: Pubkey::new_unique(),
+            fraction_manager_authority: Pubkey::new_unique(),
+            metadata_authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_default_options() {
+        let instruction = create_validate_fraction_safety_deposit_box_instruction(
+            id(),
+            accounts(),
+            make_config(Pubkey::new_unique(), 0),
+            ValidateFractionSafetyDepositBoxOptions::default(),
+        );
+
+        assert_eq!(instruction.accounts.len(), 21);
+        // The metadata authority signs unless a pda authority stands in for it
+        assert!(instruction.accounts[12].is_signer);
+        match MetaplexInstruction::try_from_slice(&instruction.data).unwrap() {
+            MetaplexInstruction::ValidateFractionSafetyDepositBox(args) => {
+                assert_eq!(args.max_slot, None);
+                assert!(args.pda_authority.is_none());
+                assert!(!args.report_cu);
+                assert_eq!(args.return_to, None);
+                assert!(!args.require_depositor_custody);
+                assert_eq!(args.order_range, None);
+            }
+            _ => panic!("not a ValidateFractionSafetyDepositBox"),
+        }
+    }
+
+    #[test]
+    fn test_depositor_requires_custody() {
+        let depositor = Pubkey::new_unique();
+        let instruction = create_validate_fraction_safety_deposit_box_instruction(
+            id(),
+            accounts(),
+            make_config(Pubkey::new_unique(), 0),
+            ValidateFractionSafetyDepositBoxOptions {
+                depositor: Some(depositor),
+                ..ValidateFractionSafetyDepositBoxOptions::default()
+            },
+        );
+
+        assert_eq!(instruction.accounts.len(), 23);
+        assert_eq!(instruction.accounts[21].pubkey, depositor);
+        assert!(instruction.accounts[21].is_signer);
+        match MetaplexInstruction::try_from_slice(&instruction.data).unwrap() {
+            MetaplexInstruction::ValidateFractionSafetyDepositBox(args) => {
+                assert!(args.require_depositor_custody)
+            }
+            _ => panic!("not a ValidateFractionSafetyDepositBox"),
+        }
+    }
+}
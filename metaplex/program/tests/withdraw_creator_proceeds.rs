@@ -0,0 +1,334 @@
+mod utils;
+
+use mpl_metaplex::{
+    error::MetaplexError,
+    id,
+    processor::{
+        set_royalty_vesting::process_set_royalty_vesting,
+        validate_and_activate::activate_redemption,
+        withdraw_creator_proceeds::process_withdraw_creator_proceeds,
+    },
+    state::{
+        CreatorSnapshot, FractionCreatorProceeds, FractionCreatorSnapshot, FractionManagerStatus,
+        FractionManagerV1, Key, Store, CREATORS, MAX_FRACTION_CREATOR_PROCEEDS_SIZE,
+        MAX_FRACTION_CREATOR_SNAPSHOT_SIZE, MAX_ROYALTY_VESTING_SECONDS, MAX_STORE_SIZE, PREFIX,
+        PROCEEDS,
+    },
+};
+use solana_program::{
+    entrypoint::ProgramResult,
+    program_option::COption,
+    pubkey::Pubkey,
+    sysvar::{self, clock::Clock},
+};
+use spl_token::state::{Account as TokenAccount, AccountState};
+use utils::*;
+
+const VESTING_START: i64 = 1_000;
+const VESTING_SECONDS: u64 = 100;
+const TOTAL_OWED: u64 = 1_000;
+
+struct ProceedsFixture {
+    program_id: Pubkey,
+    creator_proceeds: TestAccount,
+    fraction_manager: TestAccount,
+    fraction_manager_state: FractionManagerV1,
+    accept_payment: TestAccount,
+    destination: TestAccount,
+    creator: TestAccount,
+    creator_snapshot: TestAccount,
+    store: TestAccount,
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    packed_data(TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    })
+}
+
+fn clock_at(unix_timestamp: i64) -> TestAccount {
+    TestAccount::sysvar(
+        sysvar::clock::id(),
+        &Clock {
+            unix_timestamp,
+            ..Clock::default()
+        },
+    )
+}
+
+impl ProceedsFixture {
+    /// A verified creator owed TOTAL_OWED by a validated fraction manager with no vesting
+    /// schedule
+    fn new() -> ProceedsFixture {
+        let program_id = id();
+        let creator = TestAccount::wallet(Pubkey::new_unique(), true);
+        let payment_mint = Pubkey::new_unique();
+
+        let mut store = TestAccount::new(
+            program_id,
+            account_data(
+                &Store {
+                    key: Key::StoreV1,
+                    public: true,
+                    auction_program: Pubkey::new_unique(),
+                    token_vault_program: mpl_token_vault::id(),
+                    token_metadata_program: mpl_token_metadata::id(),
+                    token_program: spl_token::id(),
+                },
+                MAX_STORE_SIZE,
+            ),
+        );
+        store.key = Pubkey::new_unique();
+
+        let mut fraction_manager_state =
+            make_fraction_manager(Pubkey::new_unique(), FractionManagerStatus::Validated);
+        fraction_manager_state.store = store.key;
+        let mut fraction_manager =
+            TestAccount::new(program_id, fraction_manager_data(&fraction_manager_state));
+        fraction_manager.key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), fraction_manager_state.vault.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let mut accept_payment = TestAccount::new(
+            spl_token::id(),
+            token_account(payment_mint, fraction_manager.key, TOTAL_OWED),
+        );
+        accept_payment.key = fraction_manager_state.accept_payment;
+
+        let destination =
+            TestAccount::new(spl_token::id(), token_account(payment_mint, creator.key, 0));
+
+        let mut creator_proceeds = TestAccount::new(
+            program_id,
+            account_data(
+                &FractionCreatorProceeds {
+                    key: Key::FractionCreatorProceedsV1,
+                    fraction_manager: fraction_manager.key,
+                    creator: creator.key,
+                    total_owed: TOTAL_OWED,
+                    withdrawn: 0,
+                },
+                MAX_FRACTION_CREATOR_PROCEEDS_SIZE,
+            ),
+        );
+        creator_proceeds.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.key.as_ref(),
+                creator.key.as_ref(),
+                PROCEEDS.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let metadata = Pubkey::new_unique();
+        let mut creator_snapshot = TestAccount::new(
+            program_id,
+            account_data(
+                &FractionCreatorSnapshot {
+                    key: Key::FractionCreatorSnapshotV1,
+                    fraction_manager: fraction_manager.key,
+                    metadata,
+                    creators: vec![CreatorSnapshot {
+                        address: creator.key,
+                        verified: true,
+                        share: 100,
+                    }],
+                },
+                MAX_FRACTION_CREATOR_SNAPSHOT_SIZE,
+            ),
+        );
+        creator_snapshot.key = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                fraction_manager.key.as_ref(),
+                metadata.as_ref(),
+                CREATORS.as_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        ProceedsFixture {
+            program_id,
+            creator_proceeds,
+            fraction_manager,
+            fraction_manager_state,
+            accept_payment,
+            destination,
+            creator,
+            creator_snapshot,
+            store,
+        }
+    }
+
+    fn set_vesting(&mut self, vesting_seconds: u64) -> ProgramResult {
+        let mut authority = TestAccount::wallet(self.fraction_manager_state.authority, true);
+
+        process_set_royalty_vesting(
+            &self.program_id,
+            &[self.fraction_manager.info(), authority.info()],
+            vesting_seconds,
+        )
+    }
+
+    /// Stands in for ValidateAndActivate opening redemption at VESTING_START
+    fn activate(&mut self) {
+        let mut manager: FractionManagerV1 = self.fraction_manager.read();
+        activate_redemption(&mut manager, 1, VESTING_START).unwrap();
+        self.fraction_manager.data = fraction_manager_data(&manager);
+    }
+
+    fn vest_over(&mut self, vesting_seconds: u64) -> ProgramResult {
+        self.set_vesting(vesting_seconds)?;
+        self.activate();
+
+        Ok(())
+    }
+
+    fn withdraw_at(&mut self, unix_timestamp: i64) -> ProgramResult {
+        let mut token_program = TestAccount::wallet(spl_token::id(), false);
+        let mut clock = clock_at(unix_timestamp);
+
+        process_withdraw_creator_proceeds(
+            &self.program_id,
+            &[
+                self.creator_proceeds.info(),
+                self.fraction_manager.info(),
+                self.accept_payment.info(),
+                self.destination.info(),
+                self.creator.info(),
+                self.creator_snapshot.info(),
+                self.store.info(),
+                token_program.info(),
+                clock.info(),
+            ],
+        )
+    }
+
+    fn withdrawn(&self) -> u64 {
+        self.creator_proceeds
+            .read::<FractionCreatorProceeds>()
+            .withdrawn
+    }
+}
+
+#[test]
+fn test_nothing_vested_at_start() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.vest_over(VESTING_SECONDS).unwrap();
+
+    assert_eq!(
+        fixture.withdraw_at(VESTING_START),
+        Err(MetaplexError::NoVestedCreatorProceeds.into())
+    );
+    assert_eq!(fixture.withdrawn(), 0);
+}
+
+#[test]
+fn test_half_vested() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.vest_over(VESTING_SECONDS).unwrap();
+
+    let halfway = VESTING_START + VESTING_SECONDS as i64 / 2;
+    assert_eq!(fixture.withdraw_at(halfway), Ok(()));
+    assert_eq!(fixture.withdrawn(), TOTAL_OWED / 2);
+
+    // What was already withdrawn is not paid out twice
+    assert_eq!(
+        fixture.withdraw_at(halfway),
+        Err(MetaplexError::NoVestedCreatorProceeds.into())
+    );
+}
+
+#[test]
+fn test_fully_vested_pays_remainder() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.vest_over(VESTING_SECONDS).unwrap();
+    fixture
+        .withdraw_at(VESTING_START + VESTING_SECONDS as i64 / 2)
+        .unwrap();
+
+    assert_eq!(
+        fixture.withdraw_at(VESTING_START + 2 * VESTING_SECONDS as i64),
+        Ok(())
+    );
+    assert_eq!(fixture.withdrawn(), TOTAL_OWED);
+}
+
+#[test]
+fn test_without_vesting_everything_withdrawable() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.activate();
+
+    assert_eq!(fixture.withdraw_at(VESTING_START), Ok(()));
+    assert_eq!(fixture.withdrawn(), TOTAL_OWED);
+}
+
+#[test]
+fn test_rejects_unverified_creator() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.activate();
+    let mut snapshot: FractionCreatorSnapshot = fixture.creator_snapshot.read();
+    snapshot.creators[0].verified = false;
+    fixture.creator_snapshot.data = account_data(&snapshot, MAX_FRACTION_CREATOR_SNAPSHOT_SIZE);
+
+    assert_eq!(
+        fixture.withdraw_at(VESTING_START),
+        Err(MetaplexError::UnverifiedCreatorPayout.into())
+    );
+}
+
+#[test]
+fn test_vesting_fixed_once_activated() {
+    let mut fixture = ProceedsFixture::new();
+    // Changing the schedule before redemption opens is fine
+    fixture.set_vesting(VESTING_SECONDS * 2).unwrap();
+    fixture.vest_over(VESTING_SECONDS).unwrap();
+
+    assert_eq!(
+        fixture.set_vesting(0),
+        Err(MetaplexError::RoyaltyVestingAlreadySet.into())
+    );
+}
+
+#[test]
+fn test_vesting_starts_at_activation() {
+    let mut fixture = ProceedsFixture::new();
+    fixture.set_vesting(VESTING_SECONDS).unwrap();
+
+    // Configured long before, but nothing has vested when redemption opens
+    fixture.activate();
+    assert_eq!(
+        fixture.withdraw_at(VESTING_START),
+        Err(MetaplexError::NoVestedCreatorProceeds.into())
+    );
+    assert_eq!(
+        fixture.withdraw_at(VESTING_START + VESTING_SECONDS as i64),
+        Ok(())
+    );
+    assert_eq!(fixture.withdrawn(), TOTAL_OWED);
+}
+
+#[test]
+fn test_vesting_capped() {
+    let mut fixture = ProceedsFixture::new();
+
+    assert_eq!(
+        fixture.set_vesting(MAX_ROYALTY_VESTING_SECONDS + 1),
+        Err(MetaplexError::RoyaltyVestingTooLong.into())
+    );
+    assert_eq!(fixture.set_vesting(MAX_ROYALTY_VESTING_SECONDS), Ok(()));
+}